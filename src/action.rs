@@ -0,0 +1,42 @@
+use super::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub(crate) enum Action {
+  BudgetExhausted,
+  Corrected,
+  Deleted,
+  Error,
+  Gone,
+  Metrics,
+  Published,
+  QuotaExhausted,
+  Rejected,
+  Selected,
+  SyncFinished,
+  SyncStarted,
+}
+
+impl Action {
+  pub(crate) fn as_str(self) -> &'static str {
+    match self {
+      Self::BudgetExhausted => "budget-exhausted",
+      Self::Corrected => "corrected",
+      Self::Deleted => "deleted",
+      Self::Error => "error",
+      Self::Gone => "gone",
+      Self::Metrics => "metrics",
+      Self::Published => "published",
+      Self::QuotaExhausted => "quota-exhausted",
+      Self::Rejected => "rejected",
+      Self::Selected => "selected",
+      Self::SyncFinished => "sync-finished",
+      Self::SyncStarted => "sync-started",
+    }
+  }
+}
+
+impl Display for Action {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}", self.as_str())
+  }
+}
@@ -0,0 +1,92 @@
+use super::*;
+
+/// A RustSec advisory from the advisory database.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Advisory {
+  /// CVSS v3 base score, if the advisory has a CVSS v3 vector.
+  pub(crate) cvss: Option<f64>,
+  pub(crate) id: String,
+  /// Informational advisories, like `unmaintained`, don't describe a
+  /// vulnerability.
+  pub(crate) informational: Option<String>,
+  pub(crate) package: String,
+  pub(crate) patched: Vec<String>,
+  pub(crate) unaffected: Vec<String>,
+}
+
+impl Advisory {
+  /// Parse the TOML front matter of an advisory markdown file. Withdrawn
+  /// advisories return `None`.
+  pub(crate) fn parse(markdown: &str) -> Result<Option<Self>> {
+    #[derive(Deserialize)]
+    struct Front {
+      advisory: Fields,
+      #[serde(default)]
+      versions: Versions,
+    }
+
+    #[derive(Deserialize)]
+    struct Fields {
+      cvss: Option<String>,
+      id: String,
+      informational: Option<String>,
+      package: String,
+      withdrawn: Option<toml::Value>,
+    }
+
+    #[derive(Default, Deserialize)]
+    struct Versions {
+      #[serde(default)]
+      patched: Vec<String>,
+      #[serde(default)]
+      unaffected: Vec<String>,
+    }
+
+    let front = markdown
+      .trim_start()
+      .strip_prefix("```toml")
+      .and_then(|rest| rest.split_once("```"))
+      .map(|(front, _)| front)
+      .ok_or_else(|| anyhow!("Advisory has no TOML front matter"))?;
+
+    let Front { advisory, versions } = toml::from_str::<Front>(front)?;
+
+    if advisory.withdrawn.is_some() {
+      return Ok(None);
+    }
+
+    Ok(Some(Self {
+      cvss: advisory.cvss.as_deref().and_then(cvss::score),
+      id: advisory.id,
+      informational: advisory.informational,
+      package: advisory.package,
+      patched: versions.patched,
+      unaffected: versions.unaffected,
+    }))
+  }
+
+  /// Whether `version` is neither patched nor unaffected. Requirements that
+  /// don't parse are ignored, which errs towards treating a version as
+  /// affected.
+  pub(crate) fn affects(&self, version: &str) -> bool {
+    let Ok(version) = semver::Version::parse(version) else {
+      return true;
+    };
+
+    !self
+      .patched
+      .iter()
+      .chain(&self.unaffected)
+      .filter_map(|requirement| semver::VersionReq::parse(requirement).ok())
+      .any(|requirement| requirement.matches(&version))
+  }
+
+  pub(crate) fn is_critical(&self, threshold: f64) -> bool {
+    self.informational.is_none()
+      && self.cvss.is_some_and(|score| score >= threshold)
+  }
+
+  pub(crate) fn is_unpatched(&self) -> bool {
+    self.patched.is_empty()
+  }
+}
@@ -0,0 +1,57 @@
+use {super::*, flate2::read::GzDecoder, std::io::Read, tar::Archive};
+
+/// Parses every advisory under `crates/` in a tarball of the RustSec
+/// advisory database.
+pub(crate) fn parse(tarball: &[u8]) -> Result<Vec<Advisory>> {
+  let mut advisories = Vec::new();
+
+  for entry in Archive::new(GzDecoder::new(tarball)).entries()? {
+    let mut entry = entry?;
+
+    let path = entry.path()?.into_owned();
+
+    let mut components = path.components().skip(1);
+
+    if components.next().and_then(|c| c.as_os_str().to_str()) != Some("crates")
+      || path.extension().and_then(|e| e.to_str()) != Some("md")
+    {
+      continue;
+    }
+
+    let mut markdown = String::new();
+
+    entry.read_to_string(&mut markdown)?;
+
+    match Advisory::parse(&markdown) {
+      Ok(Some(advisory)) => advisories.push(advisory),
+      Ok(None) => {}
+      Err(error) => {
+        log::warn!("Skipping advisory {}: {error}", path.display());
+      }
+    }
+  }
+
+  Ok(advisories)
+}
+
+/// Re-downloads the advisory database if it was last synced more than
+/// `advisory_refresh` seconds ago, or never.
+pub(crate) async fn refresh(bot: &Bot) -> Result {
+  if let Some(synced) = bot.db.state(ADVISORIES_SYNCED)? {
+    let age = Utc::now() - synced.parse::<DateTime<Utc>>()?;
+
+    if age.num_seconds() < i64::try_from(bot.config.advisory_refresh)? {
+      return Ok(());
+    }
+  }
+
+  let advisories = parse(&bot.api.advisory_db().await?)?;
+
+  log::info!("Synced {} RustSec advisories", advisories.len());
+
+  bot.db.replace_advisories(&advisories)?;
+
+  bot
+    .db
+    .set_state(ADVISORIES_SYNCED, &Db::timestamp(Utc::now()))
+}
@@ -0,0 +1,745 @@
+use super::*;
+
+/// Client for the crates.io API, or any registry serving the same API, such
+/// as staging.crates.io, a mirror, or a private registry. Private registries
+/// often serve only part of the API, so version data can come from their
+/// sparse index instead.
+pub(crate) struct Api {
+  advisory_db_url: String,
+  github_token: Option<String>,
+  github_url: String,
+  http: reqwest::Client,
+  index: Option<String>,
+  limiter: RateLimiter,
+  pub(crate) metrics: Metrics,
+  token: Option<String>,
+  url: String,
+}
+
+impl Api {
+  pub(crate) fn new(config: &Config) -> Result<Self> {
+    Ok(Self {
+      advisory_db_url: config.advisory_db_url.clone(),
+      github_token: config.github_token.clone(),
+      github_url: config.github_url.trim_end_matches('/').into(),
+      http: reqwest::Client::builder()
+        .user_agent(config.user_agent())
+        .build()?,
+      index: config.index_url(),
+      limiter: RateLimiter::new(config.api_rate, config.api_burst)?,
+      metrics: Metrics::default(),
+      token: config.registry_token.clone(),
+      url: config.api_url(),
+    })
+  }
+
+  fn url(&self, path: &str, query: &[(&str, &str)]) -> Result<reqwest::Url> {
+    Ok(reqwest::Url::parse_with_params(
+      &format!("{}/{path}", self.url),
+      query,
+    )?)
+  }
+
+  async fn request<T, F, Fut>(&self, f: F) -> Result<T>
+  where
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<T>>,
+  {
+    let mut attempt = 0;
+
+    loop {
+      self.wait(self.limiter.acquire().await);
+
+      Metrics::increment(&self.metrics.requests, 1);
+
+      let result = f().await;
+
+      match &result {
+        Err(error) if is_throttled(error) => {
+          Metrics::increment(&self.metrics.throttled, 1);
+
+          if attempt == API_RETRIES {
+            return result;
+          }
+
+          attempt += 1;
+
+          Metrics::increment(&self.metrics.retries, 1);
+
+          let backoff = Duration::from_secs(2u64.pow(attempt));
+
+          log::warn!(
+            "Rate limited by crates.io, retrying in {}s...",
+            backoff.as_secs()
+          );
+
+          tokio::time::sleep(backoff).await;
+
+          self.wait(backoff);
+        }
+        _ => return result,
+      }
+    }
+  }
+
+  fn wait(&self, duration: Duration) {
+    Metrics::increment(
+      &self.metrics.waited_millis,
+      duration.as_millis().try_into().unwrap_or(u64::MAX),
+    );
+  }
+
+  async fn get(&self, url: reqwest::Url) -> Result<reqwest::Response> {
+    self
+      .request(|| async {
+        let mut request = self.http.get(url.clone());
+
+        if let Some(token) = &self.token {
+          request = request.header(reqwest::header::AUTHORIZATION, token);
+        }
+
+        let response = request.send().await.map_err(RegistryError)?;
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+          return Err(
+            RegistryError(response.error_for_status().unwrap_err()).into(),
+          );
+        }
+
+        Ok(response)
+      })
+      .await
+  }
+
+  async fn json<T: DeserializeOwned>(
+    &self,
+    path: &str,
+    query: &[(&str, &str)],
+  ) -> Result<T> {
+    Ok(
+      self
+        .get(self.url(path, query)?)
+        .await?
+        .error_for_status()
+        .map_err(RegistryError)?
+        .json()
+        .await
+        .map_err(RegistryError)?,
+    )
+  }
+
+  /// Like `json`, but `None` if the registry doesn't serve `path`.
+  async fn optional<T: DeserializeOwned>(
+    &self,
+    path: &str,
+    query: &[(&str, &str)],
+  ) -> Result<Option<T>> {
+    match self.json(path, query).await {
+      Ok(value) => Ok(Some(value)),
+      Err(error) if is_not_found(&error) => Ok(None),
+      Err(error) => Err(error),
+    }
+  }
+
+  /// Every version of `name` listed in the sparse index, if one is
+  /// configured and lists the crate.
+  async fn index_entries(&self, name: &str) -> Result<Option<Vec<IndexEntry>>> {
+    let Some(index) = &self.index else {
+      return Ok(None);
+    };
+
+    let response = self
+      .get(reqwest::Url::parse(&format!(
+        "{index}/{}",
+        IndexEntry::path(name)
+      ))?)
+      .await?;
+
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+      return Ok(None);
+    }
+
+    Ok(Some(IndexEntry::parse(
+      &response
+        .error_for_status()
+        .map_err(RegistryError)?
+        .text()
+        .await
+        .map_err(RegistryError)?,
+    )?))
+  }
+
+  async fn crates_page(&self, query: &[(&str, &str)]) -> Result<CratesPage> {
+    let per_page = PAGE_SIZE.to_string();
+
+    self
+      .json(
+        "crates",
+        &[query, &[("per_page", per_page.as_str())]].concat(),
+      )
+      .await
+  }
+
+  async fn download(&self, name: &str, version: &str) -> Result<Vec<u8>> {
+    Ok(
+      self
+        .get(self.url(&format!("crates/{name}/{version}/download"), &[])?)
+        .await?
+        .error_for_status()
+        .map_err(RegistryError)?
+        .bytes()
+        .await
+        .map_err(RegistryError)?
+        .to_vec(),
+    )
+  }
+
+  pub(crate) async fn manifest(
+    &self,
+    name: &str,
+    version: &str,
+  ) -> Result<Manifest> {
+    log::info!("Downloading crate {name} {version} for its manifest...");
+
+    Manifest::from_crate(&self.download(name, version).await?, name, version)
+  }
+
+  pub(crate) async fn build_script(
+    &self,
+    name: &str,
+    version: &str,
+  ) -> Result<Option<String>> {
+    log::info!("Downloading crate {name} {version} for its build script...");
+
+    safety::build_script(&self.download(name, version).await?, name, version)
+  }
+
+  /// Tarball of the RustSec advisory database. It's hosted on GitHub rather
+  /// than the registry, so it bypasses the registry rate limiter.
+  pub(crate) async fn advisory_db(&self) -> Result<Vec<u8>> {
+    log::info!("Downloading RustSec advisory database...");
+
+    Ok(
+      self
+        .http
+        .get(&self.advisory_db_url)
+        .send()
+        .await?
+        .error_for_status()?
+        .bytes()
+        .await?
+        .to_vec(),
+    )
+  }
+
+  /// Crate names listed one per line in the deny feed at `url`.
+  pub(crate) async fn deny_feed(&self, url: &str) -> Result<Vec<String>> {
+    log::info!("Fetching deny feed {url}...");
+
+    Ok(
+      self
+        .http
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_owned)
+        .collect(),
+    )
+  }
+
+  /// The GitHub profile of `login`, or `None` if there's no such user. Like
+  /// the advisory database, it bypasses the registry rate limiter.
+  pub(crate) async fn github_user(
+    &self,
+    login: &str,
+  ) -> Result<Option<handle::Profile>> {
+    log::info!("Fetching GitHub profile for {login}...");
+
+    let mut request = self
+      .http
+      .get(format!("{}/users/{login}", self.github_url))
+      .header(reqwest::header::ACCEPT, "application/vnd.github+json");
+
+    if let Some(token) = &self.github_token {
+      request = request.bearer_auth(token);
+    }
+
+    let response = request.send().await?;
+
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+      return Ok(None);
+    }
+
+    Ok(Some(response.error_for_status()?.json().await?))
+  }
+
+  pub(crate) async fn readme(
+    &self,
+    name: &str,
+    version: &str,
+  ) -> Result<Option<String>> {
+    log::info!("Fetching readme for crate {name} {version}...");
+
+    let response = self
+      .get(self.url(&format!("crates/{name}/{version}/readme"), &[])?)
+      .await?;
+
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+      return Ok(None);
+    }
+
+    Ok(Some(
+      response
+        .error_for_status()
+        .map_err(RegistryError)?
+        .text()
+        .await
+        .map_err(RegistryError)?,
+    ))
+  }
+
+  /// The crate with its owners, download counts, reverse dependencies, and
+  /// every version, latest first. Only the latest version has its authors
+  /// and dependencies filled in. Only the crate endpoint itself is required,
+  /// the rest default to empty when the registry doesn't serve them. With a
+  /// sparse index, dependencies and yank status come from the index.
+  pub(crate) async fn get_crate(&self, crate_name: &str) -> Result<FullCrate> {
+    #[derive(Deserialize)]
+    struct Authors {
+      meta: AuthorsMeta,
+    }
+
+    #[derive(Deserialize)]
+    struct AuthorsMeta {
+      names: Vec<String>,
+    }
+
+    #[derive(Deserialize)]
+    struct ReverseDependenciesPage {
+      dependencies: Vec<Dependency>,
+      meta: Meta,
+      versions: Vec<Version>,
+    }
+
+    let path = format!("crates/{crate_name}");
+
+    let CrateResponse {
+      categories,
+      crate_data,
+      keywords,
+      versions,
+    } = self.json(&path, &[]).await?;
+
+    let mut versions = versions.into_iter();
+
+    let mut latest = versions
+      .next()
+      .ok_or_else(|| anyhow!("Crate {crate_name} has no versions"))?;
+
+    let mut older = versions.collect::<Vec<Version>>();
+
+    let version_path = format!("{path}/{}", latest.num);
+
+    let authors = self
+      .optional::<Authors>(&format!("{version_path}/authors"), &[])
+      .await?
+      .map(|authors| authors.meta.names)
+      .unwrap_or_default();
+
+    let entries = self.index_entries(crate_name).await?;
+
+    for version in &mut older {
+      if let Some(entry) = entries
+        .iter()
+        .flatten()
+        .find(|entry| entry.vers == version.num)
+      {
+        version.yanked = entry.yanked;
+      }
+    }
+
+    let indexed = entries.and_then(|entries| {
+      entries.into_iter().find(|entry| entry.vers == latest.num)
+    });
+
+    let dependencies = match indexed {
+      Some(entry) => {
+        latest.yanked = entry.yanked;
+        entry.dependencies(latest.id)
+      }
+      None => self
+        .optional::<Dependencies>(&format!("{version_path}/dependencies"), &[])
+        .await?
+        .map(|dependencies| dependencies.dependencies)
+        .unwrap_or_default(),
+    };
+
+    let downloads = self
+      .optional(&format!("{path}/downloads"), &[])
+      .await?
+      .unwrap_or_else(|| CrateDownloads {
+        version_downloads: Vec::new(),
+        meta: CrateDownloadsMeta {
+          extra_downloads: Vec::new(),
+        },
+      });
+
+    let owners = self
+      .optional::<Owners>(&format!("{path}/owners"), &[])
+      .await?
+      .map(|owners| owners.users)
+      .unwrap_or_default();
+
+    let mut reverse_dependencies = ReverseDependencies {
+      dependencies: Vec::new(),
+      meta: Meta { total: 0 },
+    };
+
+    for page in 1.. {
+      let Some(page) = self
+        .optional::<ReverseDependenciesPage>(
+          &format!("{path}/reverse_dependencies"),
+          &[
+            ("page", &page.to_string()),
+            ("per_page", &PAGE_SIZE.to_string()),
+          ],
+        )
+        .await?
+      else {
+        break;
+      };
+
+      if page.dependencies.is_empty() {
+        break;
+      }
+
+      reverse_dependencies.meta.total = page.meta.total;
+
+      for dependency in page.dependencies {
+        if let Some(version) = page
+          .versions
+          .iter()
+          .find(|version| version.id == dependency.version_id)
+        {
+          reverse_dependencies.dependencies.push(ReverseDependency {
+            crate_version: version.clone(),
+            dependency,
+          });
+        }
+      }
+    }
+
+    Ok(FullCrate {
+      id: crate_data.id,
+      name: crate_data.name,
+      description: crate_data.description,
+      license: latest.license.clone(),
+      documentation: crate_data.documentation,
+      homepage: crate_data.homepage,
+      repository: crate_data.repository,
+      total_downloads: crate_data.downloads,
+      max_version: crate_data.max_version,
+      created_at: crate_data.created_at,
+      updated_at: crate_data.updated_at,
+      categories,
+      keywords,
+      downloads,
+      owners,
+      reverse_dependencies,
+      versions: iter::once(full_version(latest, authors, dependencies))
+        .chain(
+          older
+            .into_iter()
+            .map(|version| full_version(version, Vec::new(), Vec::new())),
+        )
+        .collect(),
+    })
+  }
+
+  /// Daily downloads of crate `name` within `range`, in total and per
+  /// version.
+  pub(crate) async fn downloads(
+    &self,
+    name: &str,
+    range: &RangeInclusive<NaiveDate>,
+  ) -> Result<Downloads> {
+    log::info!("Fetching downloads of crate {name}...");
+
+    let path = format!("crates/{name}");
+
+    // Download counts refer to versions by ID, only the crate endpoint maps
+    // them to version numbers.
+    let CrateResponse { versions, .. } = self.json(&path, &[]).await?;
+
+    let response = self.json(&format!("{path}/downloads"), &[]).await?;
+
+    Ok(Downloads::new(response, &versions, range))
+  }
+
+  /// Total number of crates and downloads on the registry.
+  pub(crate) async fn summary(&self) -> Result<(u64, u64)> {
+    #[derive(Deserialize)]
+    struct Summary {
+      num_crates: u64,
+      num_downloads: u64,
+    }
+
+    log::info!("Fetching registry summary...");
+
+    let summary = self.json::<Summary>("summary", &[]).await?;
+
+    Ok((summary.num_crates, summary.num_downloads))
+  }
+
+  /// Number of crates in each category, by slug.
+  pub(crate) async fn categories(&self) -> Result<BTreeMap<String, u64>> {
+    #[derive(Deserialize)]
+    struct Categories {
+      categories: Vec<Category>,
+    }
+
+    let mut categories = BTreeMap::new();
+
+    for page in 1.. {
+      log::info!("Fetching categories from page {page}...");
+
+      let response = self
+        .json::<Categories>(
+          "categories",
+          &[
+            ("page", &page.to_string()),
+            ("per_page", &PAGE_SIZE.to_string()),
+          ],
+        )
+        .await?;
+
+      if response.categories.is_empty() {
+        break;
+      }
+
+      categories.extend(
+        response
+          .categories
+          .into_iter()
+          .map(|category| (category.slug, category.crates_cnt)),
+      );
+    }
+
+    Ok(categories)
+  }
+
+  /// Number of crates with a version published after `since`.
+  pub(crate) async fn updated_since(
+    &self,
+    since: DateTime<Utc>,
+  ) -> Result<u64> {
+    let mut updated = 0;
+
+    for page in 1.. {
+      log::info!("Fetching recently updated crates from page {page}...");
+
+      let response = self
+        .crates_page(&[("page", &page.to_string()), ("sort", "recent-updates")])
+        .await?;
+
+      let recent = response
+        .crates
+        .iter()
+        .filter(|krate| krate.updated_at >= since)
+        .count();
+
+      updated += u64::try_from(recent)?;
+
+      if recent < response.crates.len() || response.crates.is_empty() {
+        break;
+      }
+    }
+
+    Ok(updated)
+  }
+
+  pub(crate) async fn search(&self, keyword: &str) -> Result<Vec<Crate>> {
+    log::info!("Searching crates matching {keyword}...");
+
+    Ok(self.crates_page(&[("q", keyword)]).await?.crates)
+  }
+
+  /// Crates first published after `since`, newest first.
+  pub(crate) async fn new_crates(
+    &self,
+    since: DateTime<Utc>,
+  ) -> Result<Vec<Crate>> {
+    let mut crates = Vec::new();
+
+    for page in 1.. {
+      log::info!("Fetching new crates from page {page}...");
+
+      let response = self
+        .crates_page(&[("page", &page.to_string()), ("sort", "new")])
+        .await?;
+
+      let done = response.crates.is_empty()
+        || response.crates.iter().any(|krate| krate.created_at < since);
+
+      crates.extend(
+        response
+          .crates
+          .into_iter()
+          .filter(|krate| krate.created_at >= since),
+      );
+
+      if done {
+        break;
+      }
+    }
+
+    Ok(crates)
+  }
+
+  /// Every crate owned by user `user_id`, fetched a page at a time.
+  pub(crate) async fn owner_crates(&self, user_id: u64) -> Result<Vec<Crate>> {
+    let mut crates = Vec::new();
+
+    for page in 1.. {
+      log::info!("Fetching crates owned by user {user_id} from page {page}...");
+
+      let response = self
+        .crates_page(&[
+          ("page", &page.to_string()),
+          ("user_id", &user_id.to_string()),
+        ])
+        .await?;
+
+      let done = response.crates.is_empty()
+        || u64::try_from(crates.len() + response.crates.len())?
+          >= response.meta.total;
+
+      crates.extend(response.crates);
+
+      if done {
+        break;
+      }
+    }
+
+    Ok(crates)
+  }
+
+  /// Fetches every page of crates from `cursor` onward, returning them with
+  /// the cursor of the last page. With a `source`, only crates tagged with
+  /// that keyword, or in that category when written `category:slug`, are
+  /// fetched.
+  pub(crate) async fn crates(
+    &self,
+    source: Option<&str>,
+    mut cursor: SyncCursor,
+    systemd: &Systemd,
+  ) -> Result<(Vec<Crate>, SyncCursor)> {
+    #[derive(Deserialize)]
+    struct Page {
+      crates: Vec<Crate>,
+      meta: PageMeta,
+    }
+
+    #[derive(Deserialize)]
+    struct PageMeta {
+      next_page: Option<String>,
+    }
+
+    let mut crates = Vec::new();
+
+    let mut last = cursor.clone();
+
+    loop {
+      // A first sync fetches every page, which can outlast the watchdog.
+      systemd.ping()?;
+
+      log::info!(
+        "Fetching crates from page {}{}...",
+        cursor.page,
+        source
+          .map(|source| format!(" matching {source}"))
+          .unwrap_or_default()
+      );
+
+      let per_page = PAGE_SIZE.to_string();
+
+      let mut query = cursor.query();
+
+      query.push(("per_page", per_page));
+
+      if let Some(source) = source {
+        query.push(match source.strip_prefix("category:") {
+          Some(category) => ("category", category.into()),
+          None => ("keyword", source.into()),
+        });
+      }
+
+      let response = self
+        .json::<Page>(
+          "crates",
+          &query
+            .iter()
+            .map(|(key, value)| (*key, value.as_str()))
+            .collect::<Vec<(&str, &str)>>(),
+        )
+        .await?;
+
+      if response.crates.is_empty() {
+        break;
+      }
+
+      log::trace!(
+        "Fetched crates: {:?}",
+        response
+          .crates
+          .iter()
+          .map(|c| c.name.clone())
+          .collect::<Vec<String>>()
+      );
+
+      crates.extend(response.crates);
+
+      let next = cursor.next(response.meta.next_page.as_deref())?;
+
+      last = mem::replace(&mut cursor, next);
+    }
+
+    Ok((crates, last))
+  }
+}
+
+/// A failed request to the registry. The same HTTP client also fetches from
+/// GitHub, the advisory database, and deny feeds, so only errors wrapped in
+/// this are reported as registry errors, or tell whether the registry is down
+/// or doesn't have a crate.
+#[derive(Debug, thiserror::Error)]
+#[error(transparent)]
+pub(crate) struct RegistryError(pub(crate) reqwest::Error);
+
+fn full_version(
+  version: Version,
+  author_names: Vec<String>,
+  dependencies: Vec<Dependency>,
+) -> FullVersion {
+  FullVersion {
+    created_at: version.created_at,
+    updated_at: version.updated_at,
+    dl_path: version.dl_path,
+    downloads: version.downloads,
+    features: version.features,
+    id: version.id,
+    num: version.num,
+    yanked: version.yanked,
+    license: version.license,
+    readme_path: version.readme_path,
+    links: version.links,
+    author_names,
+    dependencies,
+  }
+}
@@ -0,0 +1,16 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+#[clap(about, version)]
+pub(crate) struct Arguments {
+  #[clap(long, global = true, help = "Print machine-readable JSON output")]
+  json: bool,
+  #[clap(subcommand)]
+  subcommand: Option<Subcommand>,
+}
+
+impl Arguments {
+  pub(crate) async fn run(self) -> Result {
+    self.subcommand.unwrap_or_default().run(self.json).await
+  }
+}
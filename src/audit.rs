@@ -0,0 +1,22 @@
+use super::*;
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct Audit {
+  pub(crate) action: String,
+  pub(crate) date: DateTime<Utc>,
+  pub(crate) detail: String,
+  pub(crate) name: Option<String>,
+}
+
+impl Display for Audit {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(
+      f,
+      "{}\t{}\t{}\t{}",
+      self.date.format("%Y-%m-%d %H:%M:%S"),
+      self.action,
+      self.name.as_deref().unwrap_or("-"),
+      self.detail
+    )
+  }
+}
@@ -0,0 +1,35 @@
+use super::*;
+
+/// The account bio: how many crates were featured since when, and when the
+/// next pick is due, in the display timezone.
+pub(crate) fn text(bot: &Bot, next: DateTime<Utc>) -> Result<String> {
+  let (featured, first) = bot.db.featured()?;
+
+  let timezone = bot.config.display_timezone();
+
+  let next = next.with_timezone(&timezone).format("%H:%M %Z");
+
+  let text = match first {
+    Some(first) => format!(
+      "Featured {} crates since {} • next pick at {next}",
+      bot.config.locale.number(featured),
+      first.with_timezone(&timezone).year(),
+    ),
+    None => format!("Next pick at {next}"),
+  };
+
+  Ok(format::truncate(&text, BIO_LENGTH))
+}
+
+/// Updates the account bio, unless it's unchanged since the last update.
+pub(crate) async fn update(bot: &Bot, next: DateTime<Utc>) -> Result {
+  let text = text(bot, next)?;
+
+  if bot.db.state(BIO)?.as_deref() == Some(text.as_str()) {
+    return Ok(());
+  }
+
+  bot.client.update_bio(&text).await?;
+
+  bot.db.set_state(BIO, &text)
+}
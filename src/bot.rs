@@ -0,0 +1,1276 @@
+use super::*;
+
+/// The bot: the registry and Twitter clients, the database in `db.sqlite`,
+/// and the config they were opened with.
+pub struct Bot {
+  pub(crate) api: Api,
+  pub(crate) client: Client,
+  pub(crate) config: Config,
+  pub(crate) db: Db,
+  pub(crate) publishers: Vec<Box<dyn Publisher>>,
+  pub(crate) screenshot: Option<Screenshot>,
+  pub(crate) script: Option<Script>,
+  pub(crate) systemd: Systemd,
+}
+
+impl Bot {
+  pub async fn new(config: Config) -> Result<Self, Error> {
+    let db = Db::open(Some(PathBuf::from(DB_PATH)))?;
+
+    Self::with_db(config, db).await
+  }
+
+  /// The bot with an already opened database, rather than `db.sqlite` in the
+  /// working directory.
+  pub(crate) async fn with_db(config: Config, db: Db) -> Result<Self, Error> {
+    Self::open(config, db).await.map_err(Error::from)
+  }
+
+  async fn open(config: Config, db: Db) -> Result<Self> {
+    let client = Client::new(&config).await;
+
+    client.set_quota(
+      db.state(QUOTA)?
+        .map(|quota| serde_json::from_str(&quota))
+        .transpose()?,
+    );
+
+    // Platforms to cross-post to, each enabled by its own settings.
+    let mut publishers = Vec::<Box<dyn Publisher>>::new();
+
+    if let Some(refresh_token) = &config.linkedin_refresh_token {
+      publishers.push(Box::new(LinkedIn::new(&config, refresh_token)?));
+    }
+
+    if let Some(key) = &config.nostr_key {
+      publishers.push(Box::new(Nostr::new(
+        key,
+        config.nostr_relays.clone(),
+        config.nostr_locale.unwrap_or(config.locale),
+      )?));
+    }
+
+    for publisher in &publishers {
+      db.add_publisher(publisher.name())?;
+    }
+
+    Ok(Self {
+      api: Api::new(&config)?,
+      client,
+      db,
+      publishers,
+      screenshot: if config.screenshots {
+        Some(Screenshot::new(&config.screenshot_font)?)
+      } else {
+        None
+      },
+      script: config.script.as_deref().map(Script::load).transpose()?,
+      systemd: Systemd::new(),
+      config,
+    })
+  }
+
+  /// Returns the cached value for `key` if it's younger than `ttl`, otherwise
+  /// fetches and caches it. A `ttl` of `None` never expires, which is used for
+  /// data tied to a published version, since published versions are immutable.
+  async fn cached<T, F>(
+    &self,
+    key: &str,
+    ttl: Option<Duration>,
+    fetch: impl FnOnce() -> F,
+  ) -> Result<T>
+  where
+    T: DeserializeOwned + Serialize,
+    F: Future<Output = Result<T>>,
+  {
+    if let Some(value) = self.db.cached(key, ttl)? {
+      Metrics::increment(&self.api.metrics.cache_hits, 1);
+      return Ok(serde_json::from_str(&value)?);
+    }
+
+    Metrics::increment(&self.api.metrics.cache_misses, 1);
+
+    let value = fetch().await?;
+
+    self.db.cache(key, &serde_json::to_string(&value)?, ttl)?;
+
+    Ok(value)
+  }
+
+  pub(crate) async fn full_crate(&self, name: &str) -> Result<FullCrate> {
+    self
+      .cached(
+        &format!("crate:{name}"),
+        Some(Duration::from_secs(self.config.cache_ttl)),
+        || self.api.get_crate(name),
+      )
+      .await
+  }
+
+  pub(crate) async fn owner_crates(&self, user_id: u64) -> Result<Vec<Crate>> {
+    self
+      .cached(
+        &format!("owner:{user_id}"),
+        Some(Duration::from_secs(self.config.cache_ttl)),
+        || self.api.owner_crates(user_id),
+      )
+      .await
+  }
+
+  pub(crate) async fn downloads(
+    &self,
+    name: &str,
+    range: RangeInclusive<NaiveDate>,
+  ) -> Result<Downloads> {
+    self
+      .cached(
+        &format!("downloads:{name}:{}:{}", range.start(), range.end()),
+        Some(Duration::from_secs(self.config.cache_ttl)),
+        || self.api.downloads(name, &range),
+      )
+      .await
+  }
+
+  /// Crate `name` in full, or why it can't be featured right now, without
+  /// recording anything. Failures reaching the registry are returned, since
+  /// every other candidate would fail the same way.
+  pub(crate) async fn lookup(
+    &self,
+    name: &str,
+  ) -> Result<Result<FullCrate, Rejection>> {
+    match self.full_crate(name).await {
+      Ok(full_crate) => Ok(Ok(full_crate)),
+      Err(error) if is_not_found(&error) => Ok(Err(Rejection::Gone)),
+      Err(error) if is_unavailable(&error) => Err(error),
+      Err(error) => Ok(Err(Rejection::Skip(format!(
+        "failed to fetch crate: {error:#}"
+      )))),
+    }
+  }
+
+  /// Crate `name` in full, or `None` if it can't be featured right now.
+  /// Crates that were deleted from the registry are marked gone so they're
+  /// never picked again, and other failures specific to the crate skip it
+  /// this time.
+  pub(crate) async fn candidate(
+    &self,
+    name: &str,
+  ) -> Result<Option<FullCrate>> {
+    match self.lookup(name).await? {
+      Ok(full_crate) => Ok(Some(full_crate)),
+      Err(Rejection::Gone) => {
+        log::info!("Crate {name} no longer exists, marking it gone");
+        self.db.mark_gone(name)?;
+        self.db.audit(
+          Action::Gone,
+          Some(name),
+          &Rejection::Gone.to_string(),
+        )?;
+        Ok(None)
+      }
+      Err(rejection) => {
+        log::warn!("Skipping crate {name}, {rejection}");
+        self
+          .db
+          .audit(Action::Rejected, Some(name), &rejection.to_string())?;
+        Ok(None)
+      }
+    }
+  }
+
+  pub(crate) async fn manifest(
+    &self,
+    name: &str,
+    version: &str,
+  ) -> Result<Manifest> {
+    self
+      .cached(&format!("manifest:{name}:{version}"), None, || {
+        self.api.manifest(name, version)
+      })
+      .await
+  }
+
+  /// Advisories affecting `version` of crate `name`.
+  pub(crate) async fn advisories(
+    &self,
+    name: &str,
+    version: &str,
+  ) -> Result<Vec<Advisory>> {
+    advisory_db::refresh(self).await?;
+
+    let mut advisories = self.db.advisories(name)?;
+
+    advisories.retain(|advisory| advisory.affects(version));
+
+    Ok(advisories)
+  }
+
+  pub(crate) async fn build_script(
+    &self,
+    name: &str,
+    version: &str,
+  ) -> Result<Option<String>> {
+    self
+      .cached(&format!("build:{name}:{version}"), None, || {
+        self.api.build_script(name, version)
+      })
+      .await
+  }
+
+  pub(crate) async fn readme(
+    &self,
+    name: &str,
+    version: &str,
+  ) -> Result<Option<String>> {
+    self
+      .cached(&format!("readme:{name}:{version}"), None, || {
+        self.api.readme(name, version)
+      })
+      .await
+  }
+
+  pub(crate) async fn sync(&self) -> Result<Vec<Synced>> {
+    if self.config.focus.is_empty() {
+      return Ok(vec![self.sync_source(None).await?]);
+    }
+
+    let mut synced = Vec::new();
+
+    for source in &self.config.focus {
+      synced.push(self.sync_source(Some(source)).await?);
+    }
+
+    Ok(synced)
+  }
+
+  /// Syncs `source` from where the last sync left off. Databases from before
+  /// cursors were stored resume from the page the crate count suggests, which
+  /// is only a guess, since deleted crates shift the pages after them.
+  async fn sync_source(&self, source: Option<&str>) -> Result<Synced> {
+    let cursor = match self.db.sync_cursor(source)? {
+      Some(cursor) if cursor.sort == SYNC_SORT => cursor,
+      _ => {
+        let count = match source {
+          Some(source) => self.db.count_source(source)?,
+          None => self.db.count("crates")?,
+        };
+
+        SyncCursor::new(u64::try_from(count)? / PAGE_SIZE + 1)
+      }
+    };
+
+    let page = cursor.page;
+
+    let detail = match source {
+      Some(source) => format!("{source} page {page}"),
+      None => format!("page {page}"),
+    };
+
+    self.db.audit(Action::SyncStarted, None, &detail)?;
+
+    let (crates, cursor) =
+      self.api.crates(source, cursor, &self.systemd).await?;
+
+    let count = crates.len();
+
+    self.db.sync(crates, source)?;
+
+    self.db.set_sync_cursor(source, &cursor)?;
+
+    self.db.audit(
+      Action::SyncFinished,
+      None,
+      &format!("{detail}: {count} crates"),
+    )?;
+
+    Ok(Synced {
+      crates: count,
+      page,
+      source: source.map(str::to_owned),
+    })
+  }
+
+  /// Picks `count` crates to feature, with their drafted posts. Candidates
+  /// are validated in ranked order until enough pass: each must be
+  /// fetchable, pass every filter, and have a post that can be composed
+  /// within limits. Rejected candidates are logged and make way for the next
+  /// one.
+  pub(crate) async fn select(
+    &self,
+    count: usize,
+    filter: impl Fn(&str) -> bool,
+  ) -> Result<Vec<(FullCrate, Draft)>> {
+    let mut candidates = self.db.crates(&self.config.focus)?;
+
+    candidates.retain(|name| filter(name));
+
+    let candidates = Ranking::load(&self.db, &self.config)?
+      .order(candidates, &mut rand::thread_rng());
+
+    // With a scoring script, a shortlist of eligible candidates is gathered
+    // so the highest scoring ones can be drafted first.
+    let scoring = self.script.as_ref().filter(|script| script.scores());
+
+    let shortlist = if scoring.is_some() {
+      count * SCRIPT_SHORTLIST
+    } else {
+      count
+    };
+
+    let series = self.db.series()? + 1;
+
+    let mut eligible = Vec::new();
+
+    let mut selected = Vec::new();
+
+    for name in candidates {
+      if selected.len() == count || eligible.len() == shortlist {
+        break;
+      }
+
+      let Some(full_crate) = self.candidate(&name).await? else {
+        continue;
+      };
+
+      self.db.index(&full_crate)?;
+
+      if let Some(reason) = self.rejection(&full_crate).await? {
+        log::info!("Skipping crate {name}, {reason}");
+        self.db.audit(Action::Rejected, Some(&name), &reason)?;
+        continue;
+      }
+
+      if scoring.is_some() {
+        eligible.push(full_crate);
+        continue;
+      }
+
+      let series = series + u64::try_from(selected.len())?;
+
+      if let Some(draft) = self.validate(&full_crate, series).await? {
+        selected.push((full_crate, draft));
+      }
+    }
+
+    if let Some(script) = scoring {
+      let mut scored = eligible
+        .into_iter()
+        .map(|full_crate| Ok((script.score(&full_crate)?, full_crate)))
+        .collect::<Result<Vec<(f64, FullCrate)>>>()?;
+
+      scored.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+
+      for (score, full_crate) in scored {
+        if selected.len() == count {
+          break;
+        }
+
+        log::info!("Script scored crate {} {score}", full_crate.name);
+
+        let series = series + u64::try_from(selected.len())?;
+
+        if let Some(draft) = self.validate(&full_crate, series).await? {
+          selected.push((full_crate, draft));
+        }
+      }
+    }
+
+    for (full_crate, _) in &selected {
+      self.db.audit(
+        Action::Selected,
+        Some(&full_crate.name),
+        "passed all filters",
+      )?;
+
+      Hook::Selected
+        .run(
+          &self.config,
+          serde_json::json!({
+            "name": full_crate.name,
+            "version": full_crate.max_version,
+          }),
+        )
+        .await;
+    }
+
+    if selected.len() < count {
+      bail!(
+        "Failed to choose {count} random crates from crates in the database"
+      );
+    }
+
+    Ok(selected)
+  }
+
+  /// Drafts the post featuring `full_crate` as number `series`, or `None` if
+  /// it can't be composed within limits, which rejects the crate.
+  async fn validate(
+    &self,
+    full_crate: &FullCrate,
+    series: u64,
+  ) -> Result<Option<Draft>> {
+    let problem = match self.draft(full_crate, series).await {
+      Ok(draft) => match draft.problem() {
+        Some(problem) => problem,
+        None => return Ok(Some(draft)),
+      },
+      Err(error) if is_unavailable(&error) => return Err(error),
+      Err(error) => format!("failed to compose post: {error:#}"),
+    };
+
+    log::info!("Skipping crate {}, {problem}", full_crate.name);
+
+    self
+      .db
+      .audit(Action::Rejected, Some(&full_crate.name), &problem)?;
+
+    Ok(None)
+  }
+
+  /// Why `full_crate` can't be featured, if it can't, flagging it for review
+  /// if a filter holds it back for a reason it was never flagged for.
+  pub(crate) async fn rejection(
+    &self,
+    full_crate: &FullCrate,
+  ) -> Result<Option<String>> {
+    for filter in Filter::ALL {
+      let Some(rejection) = self.check(*filter, full_crate).await? else {
+        continue;
+      };
+
+      if let Rejection::Review {
+        reason,
+        status: None,
+      } = &rejection
+      {
+        log::info!("Flagging crate {} for review, {reason}", full_crate.name);
+        self.db.flag(&full_crate.name, reason)?;
+        return Ok(Some(format!("flagged for review, {reason}")));
+      }
+
+      return Ok(Some(rejection.to_string()));
+    }
+
+    Ok(None)
+  }
+
+  /// Runs `filter` on `full_crate`, without recording anything.
+  pub(crate) async fn check(
+    &self,
+    filter: Filter,
+    full_crate: &FullCrate,
+  ) -> Result<Option<Rejection>> {
+    let reason = match filter {
+      Filter::Optout => self.db.exclusion(full_crate),
+      Filter::Typosquat => return self.typosquat(&full_crate.name),
+      Filter::Script => match &self.script {
+        Some(script) => script.rejection(full_crate),
+        None => Ok(None),
+      },
+      Filter::Advisories => {
+        let advisories = self
+          .advisories(&full_crate.name, &full_crate.max_version)
+          .await?;
+
+        Ok(
+          advisories
+            .iter()
+            .find(|advisory| {
+              advisory.is_unpatched()
+                && advisory.is_critical(self.config.advisory_severity)
+            })
+            .map(|advisory| {
+              format!(
+                "unpatched critical advisory {} (CVSS {:.1})",
+                advisory.id,
+                advisory.cvss.unwrap_or_default()
+              )
+            }),
+        )
+      }
+      Filter::Yanked => {
+        let yanked = Yanked::from(full_crate);
+
+        if yanked.latest {
+          log::info!("Latest version of crate {} is yanked", full_crate.name);
+        }
+
+        Ok((!yanked.available).then(|| "all versions yanked".into()))
+      }
+      Filter::Moderation => {
+        if self.config.moderation != Moderation::Reject {
+          return Ok(None);
+        }
+
+        Ok(
+          full_crate
+            .description
+            .as_deref()
+            .and_then(|description| {
+              Moderation::violation(description, &self.config)
+            })
+            .map(|violation| format!("description {violation}")),
+        )
+      }
+      Filter::Squat => {
+        let squat = self.squat(full_crate).await?;
+
+        Ok(squat.is_squat(self.config.squat_threshold).then(|| {
+          format!(
+            "likely name squat, score {:.2} >= {:.2}",
+            squat.total(),
+            self.config.squat_threshold
+          )
+        }))
+      }
+      Filter::Manifest => {
+        if self.config.editions.is_empty()
+          && self.config.max_rust_version.is_none()
+        {
+          return Ok(None);
+        }
+
+        let manifest = self
+          .manifest(&full_crate.name, &full_crate.max_version)
+          .await?;
+
+        Ok(
+          (!manifest.allowed(
+            &self.config.editions,
+            self.config.max_rust_version.as_deref(),
+          ))
+          .then(|| {
+            format!(
+              "edition {} and rust-version {} filtered out",
+              manifest.edition,
+              manifest.rust_version.as_deref().unwrap_or("unset")
+            )
+          }),
+        )
+      }
+      Filter::Safety => return self.safety(full_crate).await,
+    }?;
+
+    Ok(reason.map(Rejection::Skip))
+  }
+
+  /// Crates with RustSec advisories, or listed in the deny feed, are
+  /// rejected. Crates with suspicious build scripts or names that mimic a
+  /// popular crate are flagged for manual review and rejected until an
+  /// operator approves them.
+  async fn safety(&self, full_crate: &FullCrate) -> Result<Option<Rejection>> {
+    if !self.config.safety {
+      return Ok(None);
+    }
+
+    let (name, version) = (&full_crate.name, &full_crate.max_version);
+
+    let ttl = Some(Duration::from_secs(self.config.cache_ttl));
+
+    if let Some(url) = &self.config.deny_feed {
+      let denied = self
+        .cached(&format!("deny:{url}"), ttl, || self.api.deny_feed(url))
+        .await?;
+
+      if denied.contains(name) {
+        return Ok(Some(Rejection::Skip("listed in deny feed".into())));
+      }
+    }
+
+    let advisories = self.advisories(name, version).await?;
+
+    if !advisories.is_empty() {
+      return Ok(Some(Rejection::Skip(format!(
+        "RustSec advisories {}",
+        advisories
+          .iter()
+          .map(|advisory| advisory.id.as_str())
+          .collect::<Vec<&str>>()
+          .join(", ")
+      ))));
+    }
+
+    let reason =
+      match safety::lookalike(name, &self.db.popular(POPULAR_CRATES)?) {
+        Some(popular) => {
+          Some(format!("name resembles popular crate {popular}"))
+        }
+        None => self
+          .build_script(name, version)
+          .await?
+          .as_deref()
+          .and_then(safety::suspicious_pattern)
+          .map(|pattern| format!("build script contains {pattern:?}")),
+      };
+
+    match reason {
+      Some(reason) => self.review(name, reason),
+      None => Ok(None),
+    }
+  }
+
+  /// Crates named one edit away from a popular crate are skipped, or flagged
+  /// for manual review and rejected until an operator approves them.
+  fn typosquat(&self, name: &str) -> Result<Option<Rejection>> {
+    if self.config.typosquat == Typosquat::Off {
+      return Ok(None);
+    }
+
+    let popular = self.db.popular(POPULAR_CRATES)?;
+
+    let Some(target) = Typosquat::target(name, &popular) else {
+      return Ok(None);
+    };
+
+    let reason = format!("name is one edit away from popular crate {target}");
+
+    if self.config.typosquat == Typosquat::Skip {
+      return Ok(Some(Rejection::Skip(reason)));
+    }
+
+    self.review(name, reason)
+  }
+
+  /// Holds `name` back for `reason` until an operator approves it. Each
+  /// reason is reviewed on its own, so approving one doesn't wave through
+  /// another.
+  fn review(&self, name: &str, reason: String) -> Result<Option<Rejection>> {
+    match self.db.review(name, &reason)? {
+      Some(ReviewStatus::Approved) => Ok(None),
+      status => Ok(Some(Rejection::Review { reason, status })),
+    }
+  }
+
+  pub(crate) async fn squat(&self, full_crate: &FullCrate) -> Result<Squat> {
+    let squat =
+      Squat::score(self, full_crate, self.config.squat_duplicate_limit).await?;
+
+    log::info!("Squat score for crate {}: {:?}", full_crate.name, squat);
+
+    Ok(squat)
+  }
+
+  /// Credits the owners in `fields` by their handles rather than their
+  /// logins, first looking handles up on GitHub if discovery is enabled.
+  /// Discovery is best effort, a failed lookup is retried on the next post.
+  pub(crate) async fn attribute(
+    &self,
+    fields: &mut BTreeMap<&'static str, String>,
+    owners: &[User],
+  ) -> Result {
+    if self.config.handle_discovery {
+      for owner in owners {
+        let Some(login) = Handles::github_login(owner) else {
+          continue;
+        };
+
+        if !self.db.needs_discovery(&owner.login, HANDLE_TTL)? {
+          continue;
+        }
+
+        match self.api.github_user(login).await {
+          Ok(profile) => self.db.record_handles(
+            &owner.login,
+            &profile.as_ref().map(Handles::discover).unwrap_or_default(),
+          )?,
+          Err(error) => {
+            log::warn!("Failed to discover handles for {login}: {error}");
+          }
+        }
+      }
+    }
+
+    fields.insert(
+      "owners",
+      self
+        .db
+        .attribution(owners, &self.config.mention_optout)?
+        .iter()
+        .map(|owner| format::sanitize(owner))
+        .collect::<Vec<String>>()
+        .join(", "),
+    );
+
+    Ok(())
+  }
+
+  pub(crate) async fn feature(
+    &self,
+    full_crate: &FullCrate,
+  ) -> Result<Published> {
+    let draft = self.draft(full_crate, self.db.series()? + 1).await?;
+
+    if let Some(problem) = draft.problem() {
+      bail!("Can't feature crate {}, {problem}", full_crate.name);
+    }
+
+    self.publish(full_crate, draft).await
+  }
+
+  /// Composes post number `series` featuring `full_crate` and its thread,
+  /// without publishing anything.
+  pub(crate) async fn draft(
+    &self,
+    full_crate: &FullCrate,
+    series: u64,
+  ) -> Result<Draft> {
+    let mut fields = compose::fields(full_crate, &self.config);
+
+    self.attribute(&mut fields, &full_crate.owners).await?;
+
+    let crate_override = self
+      .db
+      .crate_override(&full_crate.name)?
+      .unwrap_or_default();
+
+    crate_override.apply(&mut fields);
+
+    fields.insert("series", series.to_string());
+
+    if let Some(archive_url) = &self.config.archive_url {
+      fields.insert("archive", archive_url.clone());
+    }
+
+    if self.config.template.uses("edition") || self.config.template.uses("msrv")
+    {
+      let manifest = self
+        .manifest(&full_crate.name, &full_crate.max_version)
+        .await?;
+
+      fields.insert("edition", manifest.edition);
+
+      if let Some(rust_version) = manifest.rust_version {
+        fields.insert("msrv", rust_version);
+      }
+    }
+
+    if self.config.template.uses("advisories")
+      && self
+        .advisories(&full_crate.name, &full_crate.max_version)
+        .await?
+        .is_empty()
+    {
+      fields.insert("advisories", "No known advisories".into());
+    }
+
+    self.db.index(full_crate)?;
+
+    let similar = match self.config.see_also {
+      SeeAlso::Off => Vec::new(),
+      SeeAlso::Post | SeeAlso::Reply => {
+        self.db.similar(&full_crate.name, SEE_ALSO_LENGTH)?
+      }
+    };
+
+    let mentions = self
+      .db
+      .mentions(&full_crate.owners, &self.config.mention_optout)?;
+
+    let hashtags = crate_override.hashtags(&self.config.hashtags);
+
+    let text = self.text(
+      full_crate,
+      fields.clone(),
+      &mentions,
+      hashtags,
+      &similar,
+      self.config.locale,
+    )?;
+
+    let mut translations = Vec::<(Locale, String)>::new();
+
+    for publisher in &self.publishers {
+      let locale = publisher.locale();
+
+      if locale == self.config.locale
+        || translations
+          .iter()
+          .any(|(translation, _)| *translation == locale)
+      {
+        continue;
+      }
+
+      translations.push((
+        locale,
+        self.text(
+          full_crate,
+          fields.clone(),
+          &mentions,
+          hashtags,
+          &similar,
+          locale,
+        )?,
+      ));
+    }
+
+    let mut thread = Vec::<(String, Option<Vec<u8>>)>::new();
+
+    let readme = if self.config.readme_excerpt || self.screenshot.is_some() {
+      self
+        .readme(&full_crate.name, &full_crate.max_version)
+        .await?
+    } else {
+      None
+    };
+
+    if let Some(readme) = readme {
+      let screenshot = match &self.screenshot {
+        Some(screenshot) => readme::code_blocks(&readme)
+          .into_iter()
+          .find(|code| Screenshot::fits(code))
+          .map(|code| screenshot.render(&code))
+          .transpose()?,
+        None => None,
+      };
+
+      if self.config.readme_excerpt {
+        match Excerpt::extract(&readme, TWEET_LENGTH) {
+          Some(Excerpt::Code(_)) if screenshot.is_some() => {}
+          Some(excerpt) => {
+            thread.push((compose::fit(&excerpt.to_string()), None))
+          }
+          None => {}
+        }
+      }
+
+      if let Some(png) = screenshot {
+        thread.push((
+          format!("Example from the {} README", full_crate.name),
+          Some(png),
+        ));
+      }
+    }
+
+    if self.config.see_also == SeeAlso::Reply && !similar.is_empty() {
+      thread.push((
+        compose::see_also(
+          &similar
+            .iter()
+            .map(|name| self.config.crate_url(name))
+            .collect::<Vec<String>>(),
+          self.config.locale,
+        ),
+        None,
+      ));
+    }
+
+    Ok(Draft {
+      series,
+      text,
+      thread,
+      translations,
+    })
+  }
+
+  /// The text of a post featuring `full_crate` in `locale`, rendered from
+  /// template `fields`, with the notes that fit appended.
+  fn text(
+    &self,
+    full_crate: &FullCrate,
+    mut fields: BTreeMap<&'static str, String>,
+    mentions: &[String],
+    hashtags: &[String],
+    similar: &[String],
+    locale: Locale,
+  ) -> Result<String> {
+    fields.insert("downloads", locale.number(full_crate.total_downloads));
+
+    let mut text = compose(
+      &self.config.template_for(locale),
+      fields,
+      mentions,
+      hashtags,
+      locale,
+    );
+
+    if self.config.typosquat_note {
+      if let Some(target) =
+        Typosquat::target(&full_crate.name, &self.db.popular(POPULAR_CRATES)?)
+      {
+        match compose::append(&text, &locale.not_to_be_confused_with(target)) {
+          Some(appended) => text = appended,
+          None => {
+            log::info!("Skipping typosquat note, tweet length budget exhausted")
+          }
+        }
+      }
+    }
+
+    if self.config.see_also == SeeAlso::Post && !similar.is_empty() {
+      match compose::append(&text, &compose::see_also(similar, locale)) {
+        Some(appended) => text = appended,
+        None => log::info!("Skipping see also, tweet length budget exhausted"),
+      }
+    }
+
+    Ok(text)
+  }
+
+  async fn publish(
+    &self,
+    full_crate: &FullCrate,
+    draft: Draft,
+  ) -> Result<Published> {
+    self.afford(true)?;
+
+    // Twitter is posted to alongside the other platforms, so one platform
+    // being down doesn't keep the post off the rest. Only when every
+    // platform fails is the crate left unvisited, to be picked again.
+    let (tweet, mut outcomes) = futures::join!(
+      self.client.tweet(draft.text.clone()),
+      publisher::fan_out(
+        self
+          .publishers
+          .iter()
+          .map(|publisher| { (&**publisher, draft.text(publisher.locale())) })
+      ),
+    );
+
+    let Draft {
+      series,
+      text,
+      thread,
+      translations,
+    } = draft;
+
+    let tweet_id = match tweet {
+      Ok(id) => Some(id),
+      Err(error) if outcomes.iter().all(|(_, outcome)| outcome.is_err()) => {
+        for (platform, outcome) in &outcomes {
+          if let Err(error) = outcome {
+            log::warn!(
+              "Failed to publish {} to {platform}: {error}",
+              full_crate.name
+            );
+          }
+        }
+
+        return Err(error);
+      }
+      Err(error) => {
+        outcomes.insert(0, (TWITTER, Err(error)));
+        None
+      }
+    };
+
+    let entry =
+      self
+        .db
+        .record_sent("feature", Some(&full_crate.name), tweet_id, true)?;
+
+    self.db.update(&full_crate.name)?;
+
+    self.db.record_post(series, full_crate, tweet_id, &text)?;
+
+    // Kept so failed cross-posts are retried in the right language.
+    for publisher in &self.publishers {
+      if let Some((_, translation)) = translations
+        .iter()
+        .find(|(locale, _)| *locale == publisher.locale())
+      {
+        self
+          .db
+          .record_translation(series, publisher.name(), translation)?;
+      }
+    }
+
+    if let Some(id) = tweet_id {
+      outcomes.insert(0, (TWITTER, Ok(id.to_string())));
+    }
+
+    // Failed platforms are retried on their own in later ticks.
+    publisher::record(self, series, &full_crate.name, &outcomes)?;
+
+    let mut replies = Vec::new();
+
+    // Only the primary post is cross-posted, threads are Twitter's own.
+    if let Some(id) = tweet_id {
+      for (reply, image) in thread {
+        let parent = replies.last().copied().unwrap_or(id);
+
+        match self
+          .reply(&full_crate.name, reply, image.as_deref(), parent, entry)
+          .await?
+        {
+          Some(reply) => replies.push(reply),
+          None => break,
+        }
+      }
+    }
+
+    if !replies.is_empty() {
+      self.db.set_thread(series, &replies)?;
+    }
+
+    if let Some(path) = &self.config.archive_path {
+      site::write_archive(
+        &self.db.posts()?,
+        path,
+        self.config.display_timezone(),
+        self.config.locale,
+      )?;
+    }
+
+    let published = Published {
+      kind: "feature",
+      name: full_crate.name.clone(),
+      replies,
+      series: Some(series),
+      text,
+      tweet_id,
+    };
+
+    Ok(published)
+  }
+
+  /// Fails with `Exhausted` unless the budget allows another tweet, counted
+  /// as a new post if `post` is set.
+  pub(crate) fn afford(&self, post: bool) -> Result {
+    match Budget::new(&self.config).exhausted(&self.db, Utc::now(), post)? {
+      Some(reason) => Err(Exhausted(reason).into()),
+      None => Ok(()),
+    }
+  }
+
+  /// Tweets `text` as a new post of `kind` about `name`, if the budget
+  /// allows, and cross-posts it to the other platforms. It goes on the
+  /// ledger as soon as it's sent, so it counts even if what follows fails.
+  /// Returns the tweet and its ledger entry, which replies under it are
+  /// counted in.
+  ///
+  /// Only featured posts are kept in post history, so cross-posts of these
+  /// are best-effort: failures are audited, but not retried or deleted. And
+  /// only featured posts are translated, so these only go to platforms in
+  /// the configured locale.
+  pub(crate) async fn tweet(
+    &self,
+    kind: &str,
+    name: &str,
+    text: String,
+  ) -> Result<(u64, i64)> {
+    self.afford(true)?;
+
+    // The tweet goes first, since a post that fails to tweet is tried again
+    // next tick, and would be cross-posted twice.
+    let id = self.client.tweet(text.clone()).await?;
+
+    let entry = self.db.record_sent(kind, Some(name), Some(id), true)?;
+
+    publisher::audit(
+      self,
+      &format!("{kind} post"),
+      name,
+      &publisher::fan_out(
+        self
+          .publishers
+          .iter()
+          .filter(|publisher| publisher.locale() == self.config.locale)
+          .map(|publisher| (&**publisher, text.as_str())),
+      )
+      .await,
+    )?;
+
+    Ok((id, entry))
+  }
+
+  /// Replies to tweet `parent` under a post about `name`, counting the reply
+  /// in the post's ledger `entry`, and returning `None` if the reply failed.
+  /// Replies are best-effort, since the post above them is already live and
+  /// recorded: an image that fails to upload is dropped, and any other
+  /// failure, or reaching the monthly tweet cap, is logged and audited for
+  /// the caller to end the thread.
+  pub(crate) async fn reply(
+    &self,
+    name: &str,
+    text: String,
+    image: Option<&[u8]>,
+    parent: u64,
+    entry: i64,
+  ) -> Result<Option<u64>> {
+    if let Some(reason) =
+      Budget::new(&self.config).exhausted(&self.db, Utc::now(), false)?
+    {
+      log::warn!("Ending thread under tweet {parent}, {reason}");
+      self
+        .db
+        .audit(Action::BudgetExhausted, Some(name), &reason)?;
+      return Ok(None);
+    }
+
+    let mut sent = None;
+
+    if let Some(png) = image {
+      match self
+        .client
+        .reply_with_image(text.clone(), png, parent)
+        .await
+      {
+        Ok(id) => sent = Some(id),
+        Err(error) => {
+          log::warn!("Failed to reply to tweet {parent} with image: {error:#}");
+          self.db.audit(
+            Action::Error,
+            Some(name),
+            &format!("image reply to tweet {parent} failed: {error:#}"),
+          )?;
+        }
+      }
+    }
+
+    let id = match sent {
+      Some(id) => id,
+      None => match self.client.reply(text, parent).await {
+        Ok(id) => id,
+        Err(error) => {
+          log::warn!("Failed to reply to tweet {parent}: {error:#}");
+          self.db.audit(
+            Action::Error,
+            Some(name),
+            &format!("reply to tweet {parent} failed: {error:#}"),
+          )?;
+          return Ok(None);
+        }
+      },
+    };
+
+    self.db.record_reply(entry)?;
+
+    Ok(Some(id))
+  }
+
+  /// Deletes a post and its thread, newest reply first, and its cross-posts
+  /// on every platform, and marks it deleted in history so it drops out of
+  /// the archive, site, and milestones.
+  pub(crate) async fn delete(
+    &self,
+    post: &crate::post::Post,
+    reason: &str,
+  ) -> Result {
+    for id in post.thread.iter().rev().chain(&post.tweet_id) {
+      self.client.delete(*id).await?;
+    }
+
+    for publisher in &self.publishers {
+      if let Some(id) = self.db.publication(post.series, publisher.name())? {
+        log::info!("Deleting {} post {id}", publisher.name());
+        publisher.delete(&id).await?;
+      }
+    }
+
+    self.db.mark_deleted(post.series, reason)?;
+
+    self.db.audit(
+      Action::Deleted,
+      Some(&post.name),
+      &format!("post #{}: {reason}", post.series),
+    )?;
+
+    if let Some(path) = &self.config.archive_path {
+      site::write_archive(
+        &self.db.posts()?,
+        path,
+        self.config.display_timezone(),
+        self.config.locale,
+      )?;
+    }
+
+    Ok(())
+  }
+
+  pub(crate) fn save_quota(&self) -> Result {
+    match self.client.quota() {
+      Some(quota) => self.db.set_state(QUOTA, &serde_json::to_string(&quota)?),
+      None => Ok(()),
+    }
+  }
+
+  /// Syncs crates from the registry, then publishes according to the
+  /// configured mode.
+  pub async fn tick(&self) -> Result<Report, Error> {
+    let result = self.cycle().await;
+
+    if let Err(error) = &result {
+      Hook::Error
+        .run(
+          &self.config,
+          serde_json::json!({ "error": format!("{error:#}") }),
+        )
+        .await;
+    }
+
+    result.map_err(Error::from)
+  }
+
+  async fn cycle(&self) -> Result<Report> {
+    let mut report = Report {
+      synced: self.sync().await?,
+      ..Report::default()
+    };
+
+    if self.config.weekly_stats {
+      self.db.record_snapshot(&Snapshot::take(self).await?)?;
+    }
+
+    // Every tweet is checked against the budget before it's sent, and once
+    // it runs out the rest of the tick's posts wait for a later tick.
+    if let Err(error) = self.post(&mut report).await {
+      let Some(Exhausted(reason)) = error.downcast_ref::<Exhausted>() else {
+        return Err(error);
+      };
+
+      log::warn!("Stopping publishing for this tick, {reason}");
+      self.db.audit(Action::BudgetExhausted, None, reason)?;
+    }
+
+    for published in &report.published {
+      Hook::Published
+        .run(&self.config, serde_json::to_value(published)?)
+        .await;
+    }
+
+    self.db.scan::<Utc>(Utc::now())?;
+
+    log::info!("crates.io API metrics: {}", self.api.metrics);
+
+    self
+      .db
+      .audit(Action::Metrics, None, &self.api.metrics.to_string())?;
+
+    Ok(report)
+  }
+
+  /// Publishes according to the configured mode, then the extra posts that
+  /// are enabled, adding each to `report` as it's sent.
+  async fn post(&self, report: &mut Report) -> Result {
+    publisher::retry(self).await?;
+
+    match self.config.mode {
+      Mode::Random => {
+        for (full_crate, draft) in self.select(1, |_| true).await? {
+          report
+            .published
+            .push(self.publish(&full_crate, draft).await?);
+        }
+      }
+      Mode::Comparison => report.published.push(comparison::tick(self).await?),
+      Mode::Daily => report.published.extend(daily::tick(self).await?),
+      Mode::DeepDive => report.published.extend(deep_dive::tick(self).await?),
+      Mode::Poll => report.published.extend(poll::tick(self).await?),
+      Mode::Spotlight => report.published.push(spotlight::tick(self).await?),
+    }
+
+    if self.config.milestones {
+      report.published.extend(milestone::tick(self).await?);
+    }
+
+    if self.config.on_this_day {
+      report.published.extend(on_this_day::tick(self).await?);
+    }
+
+    if self.config.weekly_stats {
+      report.published.extend(weekly::tick(self).await?);
+    }
+
+    Ok(())
+  }
+}
@@ -0,0 +1,89 @@
+use super::*;
+
+/// Limits on how many posts may be published per hour, day, and week, counted
+/// from the ledger of everything the bot has published, and on the tweets,
+/// replies included, that the API tier allows per calendar month. A limit of
+/// zero disables it.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Budget {
+  monthly_cap: u64,
+  per_day: u64,
+  per_hour: u64,
+  per_week: u64,
+}
+
+impl Budget {
+  pub(crate) fn new(config: &Config) -> Self {
+    Self {
+      monthly_cap: config.monthly_tweet_cap,
+      per_day: config.max_posts_per_day,
+      per_hour: config.max_posts_per_hour,
+      per_week: config.max_posts_per_week,
+    }
+  }
+
+  /// Returns why no more posts may be published at `now`, if the budget is
+  /// exhausted. Tweets that aren't new posts, like replies, are only held to
+  /// the monthly tweet cap, so `post` is unset for them.
+  pub(crate) fn exhausted(
+    &self,
+    db: &Db,
+    now: DateTime<Utc>,
+    post: bool,
+  ) -> Result<Option<String>> {
+    let limits = if post {
+      vec![
+        (self.per_hour, "hourly", chrono::Duration::hours(1)),
+        (self.per_day, "daily", chrono::Duration::days(1)),
+        (self.per_week, "weekly", chrono::Duration::weeks(1)),
+      ]
+    } else {
+      Vec::new()
+    };
+
+    for (limit, period, window) in limits {
+      if limit == 0 {
+        continue;
+      }
+
+      let published = db.published_since(now - window, false)?;
+
+      if published >= limit {
+        return Ok(Some(format!(
+          "{period} budget of {limit} posts exhausted, {published} published"
+        )));
+      }
+    }
+
+    if self.monthly_cap > 0 {
+      let month = Utc
+        .with_ymd_and_hms(now.year(), now.month(), 1, 0, 0, 0)
+        .single()
+        .ok_or_else(|| anyhow!("Invalid start of month for {now}"))?;
+
+      let tweets = db.published_since(month, true)?;
+
+      if tweets >= self.monthly_cap {
+        return Ok(Some(format!(
+          "monthly tweet cap of {} reached, {tweets} tweeted",
+          self.monthly_cap
+        )));
+      }
+
+      if tweets * 10 >= self.monthly_cap * 9 {
+        log::warn!(
+          "Approaching monthly tweet cap, {tweets} of {} tweeted",
+          self.monthly_cap
+        );
+      }
+    }
+
+    Ok(None)
+  }
+}
+
+/// What sending a tweet fails with when the budget is exhausted, which ends
+/// the tick's publishing early rather than failing the tick.
+#[derive(Debug, thiserror::Error)]
+#[error("{0}")]
+pub(crate) struct Exhausted(pub(crate) String);
@@ -0,0 +1,303 @@
+use super::*;
+
+/// Client for the Twitter API at `twitter_url`. Replies with images go
+/// through egg-mode, which always sends them to twitter.com.
+#[derive(Debug)]
+pub(crate) struct Client {
+  quota: Mutex<Option<Quota>>,
+  token: Token,
+  url: String,
+}
+
+impl Client {
+  pub(crate) async fn new(config: &Config) -> Self {
+    Client {
+      quota: Mutex::new(None),
+      url: config.twitter_url.trim_end_matches('/').to_owned(),
+      token: Token::Access {
+        consumer: KeyPair::new(
+          config.consumer_key.clone(),
+          config.consumer_secret.clone(),
+        ),
+        access: KeyPair::new(
+          config.access_token_key.clone(),
+          config.access_token_secret.clone(),
+        ),
+      },
+    }
+  }
+
+  /// The posting quota reported by the latest post, if any.
+  pub(crate) fn quota(&self) -> Option<Quota> {
+    *self.quota.lock().unwrap()
+  }
+
+  pub(crate) fn set_quota(&self, quota: Option<Quota>) {
+    if quota.is_some() {
+      *self.quota.lock().unwrap() = quota;
+    }
+  }
+
+  fn endpoint(&self, path: &str) -> String {
+    format!("{}/{path}", self.url)
+  }
+
+  async fn update(&self, params: ParamList) -> Result<u64> {
+    #[derive(Deserialize)]
+    struct Response {
+      id: u64,
+    }
+
+    let (headers, body) = raw::response_raw_bytes(raw::request_post(
+      &self.endpoint("1.1/statuses/update.json"),
+      &self.token,
+      Some(&params),
+    ))
+    .await?;
+
+    self.set_quota(Quota::from_headers(&headers));
+
+    Ok(serde_json::from_slice::<Response>(&body)?.id)
+  }
+
+  pub(crate) async fn tweet(&self, text: String) -> Result<u64> {
+    log::info!("Publishing tweet {:?}", text);
+
+    self
+      .update(ParamList::new().add_param("status", text))
+      .await
+  }
+
+  pub(crate) async fn delete(&self, id: u64) -> Result {
+    log::info!("Deleting tweet {id}");
+
+    raw::response_raw_bytes(raw::request_post(
+      &self.endpoint(&format!("1.1/statuses/destroy/{id}.json")),
+      &self.token,
+      None,
+    ))
+    .await?;
+
+    Ok(())
+  }
+
+  /// Replaces the account's profile description.
+  pub(crate) async fn update_bio(&self, description: &str) -> Result {
+    log::info!("Updating bio to {:?}", description);
+
+    raw::response_raw_bytes(raw::request_post(
+      &self.endpoint("1.1/account/update_profile.json"),
+      &self.token,
+      Some(&ParamList::new().add_param("description", description.to_owned())),
+    ))
+    .await?;
+
+    Ok(())
+  }
+
+  pub(crate) async fn reply(
+    &self,
+    text: String,
+    in_reply_to: u64,
+  ) -> Result<u64> {
+    log::info!("Replying to tweet {in_reply_to} with {:?}", text);
+
+    self
+      .update(
+        ParamList::new()
+          .add_param("status", text)
+          .add_param("in_reply_to_status_id", in_reply_to.to_string())
+          .add_param("auto_populate_reply_metadata", "true"),
+      )
+      .await
+  }
+
+  pub(crate) async fn reply_with_image(
+    &self,
+    text: String,
+    png: &[u8],
+    in_reply_to: u64,
+  ) -> Result<u64> {
+    log::info!(
+      "Uploading {} byte image for reply to {in_reply_to}",
+      png.len()
+    );
+
+    let media =
+      media::upload_media(png, &media_types::image_png(), &self.token).await?;
+
+    log::info!("Replying to tweet {in_reply_to} with {:?} and image", text);
+
+    let mut draft = DraftTweet::new(text)
+      .in_reply_to(in_reply_to)
+      .auto_populate_reply_metadata(true);
+
+    draft.add_media(media.id);
+
+    let response = draft.send(&self.token).await?;
+
+    self.set_quota(Quota::from_rate_limit(&response.rate_limit_status));
+
+    Ok(response.id)
+  }
+
+  pub(crate) async fn poll(
+    &self,
+    text: String,
+    options: &[String],
+    duration_minutes: u64,
+  ) -> Result<u64> {
+    log::info!("Publishing poll {:?} with options {:?}", text, options);
+
+    #[derive(Deserialize)]
+    struct Response {
+      data: Data,
+    }
+
+    #[derive(Deserialize)]
+    struct Data {
+      id: String,
+    }
+
+    let (headers, body) = raw::response_raw_bytes(raw::request_post_json(
+      &self.endpoint("2/tweets"),
+      &self.token,
+      serde_json::json!({
+        "text": text,
+        "poll": {
+          "options": options,
+          "duration_minutes": duration_minutes,
+        },
+      }),
+    ))
+    .await?;
+
+    self.set_quota(Quota::from_headers(&headers));
+
+    Ok(serde_json::from_slice::<Response>(&body)?.data.id.parse()?)
+  }
+
+  pub(crate) async fn poll_results(
+    &self,
+    id: u64,
+  ) -> Result<Option<Vec<(String, u64)>>> {
+    log::info!("Fetching results for poll {id}");
+
+    #[derive(Deserialize)]
+    struct Response {
+      includes: Includes,
+    }
+
+    #[derive(Deserialize)]
+    struct Includes {
+      polls: Vec<Poll>,
+    }
+
+    #[derive(Deserialize)]
+    struct Poll {
+      options: Vec<PollOption>,
+      voting_status: String,
+    }
+
+    #[derive(Deserialize)]
+    struct PollOption {
+      label: String,
+      votes: u64,
+    }
+
+    let (_, body) = raw::response_raw_bytes(raw::request_get(
+      &self.endpoint(&format!("2/tweets/{id}")),
+      &self.token,
+      Some(
+        &ParamList::new()
+          .add_param("expansions", "attachments.poll_ids")
+          .add_param("poll.fields", "options,voting_status"),
+      ),
+    ))
+    .await?;
+
+    let poll = serde_json::from_slice::<Response>(&body)?
+      .includes
+      .polls
+      .into_iter()
+      .next()
+      .ok_or_else(|| anyhow!("Tweet {id} has no poll attached"))?;
+
+    if poll.voting_status != "closed" {
+      return Ok(None);
+    }
+
+    Ok(Some(
+      poll
+        .options
+        .into_iter()
+        .map(|option| (option.label, option.votes))
+        .collect(),
+    ))
+  }
+
+  pub(crate) async fn engagement(
+    &self,
+    ids: &[u64],
+  ) -> Result<Vec<(u64, Engagement)>> {
+    log::info!("Fetching engagement for {} tweets", ids.len());
+
+    #[derive(Deserialize)]
+    struct Response {
+      #[serde(default)]
+      data: Vec<Data>,
+    }
+
+    #[derive(Deserialize)]
+    struct Data {
+      id: String,
+      public_metrics: Engagement,
+    }
+
+    let (_, body) = raw::response_raw_bytes(raw::request_get(
+      &self.endpoint("2/tweets"),
+      &self.token,
+      Some(
+        &ParamList::new()
+          .add_param(
+            "ids",
+            ids
+              .iter()
+              .map(u64::to_string)
+              .collect::<Vec<String>>()
+              .join(","),
+          )
+          .add_param("tweet.fields", "public_metrics"),
+      ),
+    ))
+    .await?;
+
+    serde_json::from_slice::<Response>(&body)?
+      .data
+      .into_iter()
+      .map(|data| Ok((data.id.parse()?, data.public_metrics)))
+      .collect()
+  }
+
+  pub(crate) async fn mentions(
+    &self,
+    since_id: Option<u64>,
+  ) -> Result<Vec<Tweet>> {
+    log::info!("Fetching mentions since {:?}", since_id);
+
+    Ok(
+      raw::response_json(raw::request_get(
+        &self.endpoint("1.1/statuses/mentions_timeline.json"),
+        &self.token,
+        Some(
+          &ParamList::new()
+            .add_param("count", MENTIONS_PAGE_SIZE.to_string())
+            .add_param("tweet_mode", "extended")
+            .add_opt_param("since_id", since_id.map(|id| id.to_string())),
+        ),
+      ))
+      .await?
+      .response,
+    )
+  }
+}
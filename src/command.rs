@@ -0,0 +1,76 @@
+use super::*;
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum Command {
+  Info { name: String },
+  Suggest { keyword: String },
+}
+
+impl Command {
+  pub(crate) fn parse(text: &str) -> Option<Self> {
+    let mut words = text
+      .split_whitespace()
+      .filter(|word| !word.starts_with('@'));
+
+    match words.next()?.to_lowercase().as_str() {
+      "info" => Some(Self::Info {
+        name: words.next()?.to_string(),
+      }),
+      "suggest" => Some(Self::Suggest {
+        keyword: words.next()?.to_lowercase(),
+      }),
+      _ => None,
+    }
+  }
+
+  pub(crate) async fn respond(self, bot: &Bot) -> Result<String> {
+    let Bot { api, config, .. } = bot;
+
+    match self {
+      Self::Info { name } => match bot.full_crate(&name).await {
+        Ok(full_crate) => Ok(compose::info(&full_crate, config)),
+        Err(error) if is_not_found(&error) => {
+          Ok(format!("Sorry, I couldn't find a crate named {name}."))
+        }
+        Err(error) => Err(error),
+      },
+      Self::Suggest { keyword } => {
+        let mut candidates = api.search(&keyword).await?;
+
+        candidates.shuffle(&mut rand::thread_rng());
+
+        // Hits go through the same filters as featured crates. One that
+        // can't be fetched or checked is skipped rather than failing the
+        // reply, only an unavailable registry does that.
+        for candidate in candidates {
+          let Some(full_crate) = bot.candidate(&candidate.name).await? else {
+            continue;
+          };
+
+          match bot.rejection(&full_crate).await {
+            Ok(None) => {}
+            Ok(Some(reason)) => {
+              log::info!("Not suggesting crate {}, {reason}", candidate.name);
+              continue;
+            }
+            Err(error) => {
+              log::warn!(
+                "Not suggesting crate {}, failed to check it: {error:#}",
+                candidate.name
+              );
+              continue;
+            }
+          }
+
+          // Drafted like the next featured post, so owners are credited
+          // and every template field is filled in.
+          return Ok(bot.draft(&full_crate, bot.db.series()? + 1).await?.text);
+        }
+
+        Ok(format!(
+          "Sorry, I couldn't find a crate matching {keyword}."
+        ))
+      }
+    }
+  }
+}
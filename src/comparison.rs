@@ -0,0 +1,229 @@
+use super::*;
+
+/// Compares the most downloaded crates in a category that hasn't been
+/// compared before: downloads, last update, and dependency count, as a table
+/// image when screenshots are enabled, or a reply per crate otherwise.
+pub(crate) async fn tick(bot: &Bot) -> Result<Published> {
+  // Categories are only known for crates fetched in full, so index a few of
+  // the most downloaded crates each time until the popular ones are covered.
+  for name in bot.db.unindexed(COMPARISON_CANDIDATES)? {
+    if let Some(full_crate) = bot.candidate(&name).await? {
+      bot.db.index(&full_crate)?;
+    }
+  }
+
+  let mut categories = bot
+    .db
+    .categories(COMPARISON_CANDIDATES)?
+    .into_iter()
+    .collect::<Vec<(String, Vec<String>)>>();
+
+  categories.shuffle(&mut rand::thread_rng());
+
+  for (category, names) in categories {
+    let mut crates = Vec::new();
+
+    for name in names {
+      let Some(full_crate) = bot.candidate(&name).await? else {
+        continue;
+      };
+
+      if let Some(reason) = bot.rejection(&full_crate).await? {
+        log::info!("Skipping crate {name} in comparison, {reason}");
+        bot.db.audit(Action::Rejected, Some(&name), &reason)?;
+        continue;
+      }
+
+      crates.push(full_crate);
+
+      if crates.len() == COMPARISON_SIZE {
+        break;
+      }
+    }
+
+    if crates.len() < 2 {
+      log::info!(
+        "Skipping comparison of {category}, only {} eligible crates",
+        crates.len()
+      );
+      continue;
+    }
+
+    return post(bot, &category, &crates).await;
+  }
+
+  bail!("Failed to find a category with crates to compare")
+}
+
+struct Row {
+  dependencies: usize,
+  downloads: String,
+  name: String,
+  updated: String,
+}
+
+impl Row {
+  fn new(full_crate: &FullCrate, locale: Locale) -> Self {
+    Self {
+      dependencies: full_crate
+        .versions
+        .first()
+        .map(|version| {
+          version
+            .dependencies
+            .iter()
+            .filter(|dependency| dependency.kind != "dev")
+            .count()
+        })
+        .unwrap_or_default(),
+      downloads: locale.number(full_crate.total_downloads),
+      name: full_crate.name.clone(),
+      updated: full_crate.updated_at.format("%Y-%m-%d").to_string(),
+    }
+  }
+}
+
+/// The rows as a plain text table with aligned columns.
+fn table(rows: &[Row]) -> String {
+  let header = ["crate", "downloads", "updated", "deps"];
+
+  let cells = rows
+    .iter()
+    .map(|row| {
+      [
+        row.name.clone(),
+        row.downloads.clone(),
+        row.updated.clone(),
+        row.dependencies.to_string(),
+      ]
+    })
+    .collect::<Vec<[String; 4]>>();
+
+  let widths = (0..header.len())
+    .map(|column| {
+      cells
+        .iter()
+        .map(|row| row[column].chars().count())
+        .chain([header[column].len()])
+        .max()
+        .unwrap_or_default()
+    })
+    .collect::<Vec<usize>>();
+
+  [header.map(str::to_owned)]
+    .iter()
+    .chain(&cells)
+    .map(|row| {
+      row
+        .iter()
+        .zip(&widths)
+        .map(|(cell, width)| format!("{cell:<width$}"))
+        .collect::<Vec<String>>()
+        .join("  ")
+        .trim_end()
+        .to_owned()
+    })
+    .collect::<Vec<String>>()
+    .join("\n")
+}
+
+async fn post(
+  bot: &Bot,
+  category: &str,
+  crates: &[FullCrate],
+) -> Result<Published> {
+  let rows = crates
+    .iter()
+    .map(|full_crate| Row::new(full_crate, bot.config.locale))
+    .collect::<Vec<Row>>();
+
+  let text = compose::fit(&format!(
+    "Comparing {category} crates: {}",
+    rows
+      .iter()
+      .map(|row| row.name.as_str())
+      .collect::<Vec<&str>>()
+      .join(" vs "),
+  ));
+
+  let table = table(&rows);
+
+  let (id, entry) = bot.tweet("comparison", category, text.clone()).await?;
+
+  let mut replies = Vec::new();
+
+  match &bot.screenshot {
+    Some(screenshot) if Screenshot::fits(&table) => {
+      let links = rows
+        .iter()
+        .map(|row| bot.config.crate_url(&row.name))
+        .collect::<Vec<String>>()
+        .join("\n");
+
+      replies.extend(
+        bot
+          .reply(
+            category,
+            compose::fit(&links),
+            Some(&screenshot.render_text(&table)?),
+            id,
+            entry,
+          )
+          .await?,
+      );
+    }
+    _ => {
+      for row in &rows {
+        let text = format!(
+          "{}: {} downloads, last updated {}, {} dependencies\n{}",
+          row.name,
+          row.downloads,
+          row.updated,
+          row.dependencies,
+          bot.config.crate_url(&row.name),
+        );
+
+        let Some(reply) = bot
+          .reply(
+            category,
+            compose::fit(&text),
+            None,
+            replies.last().copied().unwrap_or(id),
+            entry,
+          )
+          .await?
+        else {
+          break;
+        };
+
+        replies.push(reply);
+      }
+    }
+  }
+
+  bot.db.record_comparison(
+    category,
+    &rows
+      .iter()
+      .map(|row| row.name.as_str())
+      .collect::<Vec<&str>>(),
+    id,
+  )?;
+
+  bot.db.audit(
+    Action::Published,
+    Some(category),
+    &format!("comparison tweet {id}"),
+  )?;
+
+  let published = Published {
+    kind: "comparison",
+    name: category.into(),
+    replies,
+    series: None,
+    text,
+    tweet_id: Some(id),
+  };
+
+  Ok(published)
+}
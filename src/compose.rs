@@ -0,0 +1,183 @@
+use super::*;
+
+pub(crate) fn fields(
+  full_crate: &FullCrate,
+  config: &Config,
+) -> BTreeMap<&'static str, String> {
+  let FullCrate {
+    name,
+    description,
+    owners,
+    max_version,
+    total_downloads,
+    created_at,
+    updated_at,
+    ..
+  } = full_crate;
+
+  let mut fields = format::fields(
+    name,
+    description.as_deref(),
+    &owners
+      .iter()
+      .map(|user| user.login.as_str())
+      .collect::<Vec<&str>>(),
+    config.crate_url(name),
+  );
+
+  if config.moderation == Moderation::Replace {
+    if let Some(violation) = description
+      .as_deref()
+      .and_then(|description| Moderation::violation(description, config))
+    {
+      log::info!("Dropping description of crate {name}, it {violation}");
+      fields.insert("description", String::new());
+    }
+  }
+
+  fields.extend([
+    ("created", created_at.format("%Y-%m-%d").to_string()),
+    ("downloads", config.locale.number(*total_downloads)),
+    ("updated", updated_at.format("%Y-%m-%d").to_string()),
+    ("version", max_version.clone()),
+  ]);
+
+  fields
+}
+
+pub(crate) fn append(text: &str, line: &str) -> Option<String> {
+  let appended = format!("{text}\n{line}");
+
+  if format::length(&appended) > TWEET_LENGTH {
+    return None;
+  }
+
+  Some(appended)
+}
+
+pub(crate) fn fit(text: &str) -> String {
+  let overflow = format::length(text).saturating_sub(TWEET_LENGTH);
+
+  if overflow == 0 {
+    return text.to_string();
+  }
+
+  format::truncate(text, format::width(text).saturating_sub(overflow))
+}
+
+pub(crate) fn see_also(similar: &[String], locale: Locale) -> String {
+  format!("{}: {}", locale.see_also(), similar.join(", "))
+}
+
+pub(crate) fn compose(
+  template: &Template,
+  mut fields: BTreeMap<&'static str, String>,
+  mentions: &[String],
+  hashtags: &[String],
+  locale: Locale,
+) -> String {
+  let mut text = template.render(&fields, locale);
+
+  // Shorten the description until the post fits, dropping it entirely if
+  // need be, and then the owners, which are never dropped since custom
+  // templates might not put them in an optional group.
+  for (field, optional) in [("description", true), ("owners", false)] {
+    while let Some(value) = fields.get_mut(field) {
+      let overflow = format::length(&text).saturating_sub(TWEET_LENGTH);
+
+      if overflow == 0 || value.is_empty() || value == format::ELLIPSIS {
+        break;
+      }
+
+      let width = format::width(value);
+
+      *value = if optional && width <= overflow + 1 {
+        String::new()
+      } else {
+        format::truncate(value, width.saturating_sub(overflow))
+      };
+
+      text = template.render(&fields, locale);
+    }
+  }
+
+  tag(&text, mentions, hashtags)
+}
+
+/// Appends `mentions` and `hashtags` to `text` on a line of their own, each
+/// with its `@` or `#` whether or not it was configured with one, skipping
+/// tags already in the text and tags that don't fit.
+pub(crate) fn tag(
+  text: &str,
+  mentions: &[String],
+  hashtags: &[String],
+) -> String {
+  let mut text = text.to_owned();
+  let mut tags = String::new();
+
+  for tag in mentions
+    .iter()
+    .map(|handle| format!("@{}", handle.trim_start_matches('@')))
+    .chain(
+      hashtags
+        .iter()
+        .map(|hashtag| format!("#{}", hashtag.trim_start_matches('#'))),
+    )
+  {
+    if text
+      .split(|c: char| c.is_whitespace() || matches!(c, ',' | '(' | ')'))
+      .any(|word| word == tag)
+    {
+      continue;
+    }
+
+    let separator = if tags.is_empty() { "\n" } else { " " };
+
+    if format::length(&text) + format::length(&tags) + 1 + format::length(&tag)
+      > TWEET_LENGTH
+    {
+      log::info!("Skipping {tag}, tweet length budget exhausted");
+      continue;
+    }
+
+    tags.push_str(separator);
+    tags.push_str(&tag);
+  }
+
+  text.push_str(&tags);
+
+  text
+}
+
+pub(crate) fn info(full_crate: &FullCrate, config: &Config) -> String {
+  let FullCrate {
+    name,
+    description,
+    max_version,
+    total_downloads,
+    reverse_dependencies,
+    updated_at,
+    ..
+  } = full_crate;
+
+  let stats = format!(
+    "{total_downloads} downloads, {} dependents, updated {}\n{}",
+    reverse_dependencies.meta.total,
+    updated_at.format("%Y-%m-%d"),
+    config.crate_url(name),
+  );
+
+  let header = format!("{name} v{max_version}");
+
+  match description.as_deref().map(str::trim) {
+    Some(description) if !description.is_empty() => format!(
+      "{header}: {}\n{stats}",
+      format::truncate(
+        description,
+        TWEET_LENGTH
+          .saturating_sub(format::length(&header) + format::length(&stats) + 3)
+      )
+    ),
+    _ => format!("{header}\n{stats}"),
+  }
+}
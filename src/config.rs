@@ -0,0 +1,442 @@
+use super::*;
+
+/// Bot configuration, read from environment variables, `.env`, and
+/// `cratebot.toml`, whose settings take precedence.
+#[derive(Debug, Deserialize)]
+pub struct Config {
+  pub(crate) access_token_key: String,
+  pub(crate) access_token_secret: String,
+  #[serde(default = "Config::default_advisory_db_url")]
+  pub(crate) advisory_db_url: String,
+  #[serde(default = "Config::default_advisory_refresh")]
+  pub(crate) advisory_refresh: u64,
+  #[serde(default = "Config::default_advisory_severity")]
+  pub(crate) advisory_severity: f64,
+  #[serde(default = "Config::default_api_burst")]
+  pub(crate) api_burst: u32,
+  #[serde(default = "Config::default_api_rate")]
+  pub(crate) api_rate: f64,
+  #[serde(default)]
+  api_url: Option<String>,
+  #[serde(default)]
+  pub(crate) archive_path: Option<PathBuf>,
+  #[serde(default)]
+  pub(crate) archive_url: Option<String>,
+  #[serde(default)]
+  pub(crate) aws_access_key_id: Option<String>,
+  #[serde(default)]
+  pub(crate) aws_endpoint_url: Option<String>,
+  #[serde(default = "Config::default_aws_region")]
+  pub(crate) aws_region: String,
+  #[serde(default)]
+  pub(crate) aws_secret_access_key: Option<String>,
+  #[serde(default)]
+  pub(crate) aws_session_token: Option<String>,
+  #[serde(default)]
+  pub(crate) bio: bool,
+  #[serde(default = "Config::default_bio_interval")]
+  pub(crate) bio_interval: u64,
+  #[serde(default)]
+  pub(crate) catch_up_limit: u64,
+  #[serde(default = "Config::default_cache_ttl")]
+  pub(crate) cache_ttl: u64,
+  pub(crate) consumer_key: String,
+  pub(crate) consumer_secret: String,
+  pub(crate) contact: String,
+  #[serde(default = "Config::default_daily_count")]
+  pub(crate) daily_count: usize,
+  #[serde(default)]
+  pub(crate) deny_feed: Option<String>,
+  #[serde(default)]
+  display_timezone: Option<Tz>,
+  #[serde(default)]
+  pub(crate) editions: Vec<String>,
+  #[serde(default)]
+  pub(crate) engagement: bool,
+  #[serde(default = "Config::default_engagement_interval")]
+  pub(crate) engagement_interval: u64,
+  #[serde(default = "Config::default_exploration")]
+  pub(crate) exploration: f64,
+  #[serde(default)]
+  pub(crate) focus: Vec<String>,
+  #[serde(default)]
+  pub(crate) github_token: Option<String>,
+  #[serde(default = "Config::default_github_url")]
+  pub(crate) github_url: String,
+  #[serde(default)]
+  pub(crate) handle_discovery: bool,
+  #[serde(default = "Config::default_hashtags")]
+  pub(crate) hashtags: Vec<String>,
+  #[serde(default)]
+  pub(crate) hook_on_error: Option<String>,
+  #[serde(default)]
+  pub(crate) hook_on_published: Option<String>,
+  #[serde(default)]
+  pub(crate) hook_on_selected: Option<String>,
+  #[serde(default)]
+  index_url: Option<String>,
+  #[serde(default)]
+  pub(crate) jitter: u64,
+  #[serde(default = "Config::default_linkedin_api_url")]
+  pub(crate) linkedin_api_url: String,
+  #[serde(default = "Config::default_linkedin_auth_url")]
+  pub(crate) linkedin_auth_url: String,
+  #[serde(default)]
+  pub(crate) linkedin_client_id: Option<String>,
+  #[serde(default)]
+  pub(crate) linkedin_client_secret: Option<String>,
+  #[serde(default)]
+  pub(crate) linkedin_locale: Option<Locale>,
+  #[serde(default)]
+  pub(crate) linkedin_organization: Option<String>,
+  #[serde(default)]
+  pub(crate) linkedin_refresh_token: Option<String>,
+  #[serde(default)]
+  pub(crate) locale: Locale,
+  #[serde(default = "Config::default_max_posts_per_day")]
+  pub(crate) max_posts_per_day: u64,
+  #[serde(default = "Config::default_max_posts_per_hour")]
+  pub(crate) max_posts_per_hour: u64,
+  #[serde(default = "Config::default_max_posts_per_week")]
+  pub(crate) max_posts_per_week: u64,
+  #[serde(default)]
+  pub(crate) max_rust_version: Option<String>,
+  #[serde(default = "Config::default_mention_poll_interval")]
+  pub(crate) mention_poll_interval: u64,
+  #[serde(default)]
+  pub(crate) mention_optout: Vec<String>,
+  #[serde(default)]
+  pub(crate) milestones: bool,
+  #[serde(default)]
+  pub(crate) moderation: Moderation,
+  #[serde(default = "Config::default_moderation_max_emoji")]
+  pub(crate) moderation_max_emoji: usize,
+  #[serde(default)]
+  pub(crate) moderation_max_urls: usize,
+  #[serde(default)]
+  pub(crate) moderation_words: Vec<String>,
+  #[serde(default)]
+  pub(crate) mode: Mode,
+  #[serde(default)]
+  pub(crate) nostr_key: Option<String>,
+  #[serde(default)]
+  pub(crate) nostr_locale: Option<Locale>,
+  #[serde(default)]
+  pub(crate) nostr_relays: Vec<String>,
+  #[serde(default)]
+  pub(crate) on_this_day: bool,
+  #[serde(default)]
+  pub(crate) posting_days: Vec<Weekday>,
+  #[serde(default)]
+  pub(crate) posting_hours: Hours,
+  #[serde(default)]
+  pub(crate) monthly_tweet_cap: u64,
+  #[serde(default = "Config::default_poll_duration")]
+  pub(crate) poll_duration: u64,
+  #[serde(default = "Config::default_quota_reserve")]
+  pub(crate) quota_reserve: u64,
+  #[serde(default)]
+  pub(crate) readme_excerpt: bool,
+  #[serde(default = "Config::default_registry_url")]
+  pub(crate) registry_url: String,
+  #[serde(default)]
+  pub(crate) registry_token: Option<String>,
+  #[serde(default)]
+  pub(crate) reply_to_mentions: bool,
+  #[serde(default)]
+  pub(crate) safety: bool,
+  #[serde(default)]
+  pub(crate) script: Option<PathBuf>,
+  #[serde(default = "Config::default_screenshot_font")]
+  pub(crate) screenshot_font: PathBuf,
+  #[serde(default)]
+  pub(crate) screenshots: bool,
+  #[serde(default)]
+  pub(crate) see_also: SeeAlso,
+  #[serde(default)]
+  pub(crate) selection: Selection,
+  #[serde(default = "Config::default_squat_duplicate_limit")]
+  pub(crate) squat_duplicate_limit: usize,
+  #[serde(default = "Config::default_squat_threshold")]
+  pub(crate) squat_threshold: f64,
+  #[serde(default)]
+  pub(crate) state_token: Option<String>,
+  #[serde(default)]
+  pub(crate) state_url: Option<String>,
+  #[serde(skip)]
+  pub(crate) template: Template,
+  #[serde(default, rename = "template")]
+  template_override: Option<Template>,
+  #[serde(default = "Config::default_timezone")]
+  pub(crate) timezone: Tz,
+  #[serde(default = "Config::default_twitter_url")]
+  pub(crate) twitter_url: String,
+  #[serde(default)]
+  pub(crate) typosquat: Typosquat,
+  #[serde(default)]
+  pub(crate) typosquat_note: bool,
+  #[serde(default)]
+  pub(crate) weekly_stats: bool,
+}
+
+impl Config {
+  fn default_advisory_db_url() -> String {
+    ADVISORY_DB_URL.into()
+  }
+
+  fn default_advisory_refresh() -> u64 {
+    24 * 60 * 60
+  }
+
+  fn default_advisory_severity() -> f64 {
+    9.0
+  }
+
+  fn default_api_burst() -> u32 {
+    1
+  }
+
+  fn default_api_rate() -> f64 {
+    1.0
+  }
+
+  fn default_aws_region() -> String {
+    "us-east-1".into()
+  }
+
+  fn default_bio_interval() -> u64 {
+    60 * 60
+  }
+
+  fn default_cache_ttl() -> u64 {
+    60 * 60
+  }
+
+  fn default_daily_count() -> usize {
+    3
+  }
+
+  fn default_engagement_interval() -> u64 {
+    6 * 60 * 60
+  }
+
+  fn default_exploration() -> f64 {
+    0.2
+  }
+
+  fn default_github_url() -> String {
+    GITHUB_URL.into()
+  }
+
+  fn default_hashtags() -> Vec<String> {
+    vec!["rustlang".into(), "crates".into()]
+  }
+
+  fn default_linkedin_api_url() -> String {
+    LINKEDIN_API_URL.into()
+  }
+
+  fn default_linkedin_auth_url() -> String {
+    LINKEDIN_AUTH_URL.into()
+  }
+
+  fn default_max_posts_per_day() -> u64 {
+    100
+  }
+
+  fn default_max_posts_per_hour() -> u64 {
+    10
+  }
+
+  fn default_max_posts_per_week() -> u64 {
+    500
+  }
+
+  fn default_mention_poll_interval() -> u64 {
+    5 * 60
+  }
+
+  fn default_moderation_max_emoji() -> usize {
+    3
+  }
+
+  fn default_poll_duration() -> u64 {
+    55
+  }
+
+  fn default_quota_reserve() -> u64 {
+    1
+  }
+
+  fn default_registry_url() -> String {
+    REGISTRY_URL.into()
+  }
+
+  fn default_screenshot_font() -> PathBuf {
+    "/usr/share/fonts/truetype/dejavu/DejaVuSansMono.ttf".into()
+  }
+
+  fn default_squat_duplicate_limit() -> usize {
+    3
+  }
+
+  fn default_squat_threshold() -> f64 {
+    1.0
+  }
+
+  fn default_timezone() -> Tz {
+    Tz::UTC
+  }
+
+  fn default_twitter_url() -> String {
+    TWITTER_URL.into()
+  }
+
+  /// The registry API, at `API_URL` if set, otherwise under `REGISTRY_URL`,
+  /// so staging.crates.io or a mirror only needs the one setting.
+  pub(crate) fn api_url(&self) -> String {
+    match &self.api_url {
+      Some(api_url) => api_url.trim_end_matches('/').into(),
+      None => format!("{}/api/v1", self.registry_url.trim_end_matches('/')),
+    }
+  }
+
+  /// The timezone dates are shown in, `DISPLAY_TIMEZONE` if set, otherwise
+  /// the posting schedule's `TIMEZONE`.
+  /// The template for posts in `locale`: the configured one, which is
+  /// written in a single language, or else the locale's default.
+  pub(crate) fn template_for(&self, locale: Locale) -> Template {
+    self
+      .template_override
+      .clone()
+      .unwrap_or_else(|| locale.template())
+  }
+
+  pub(crate) fn display_timezone(&self) -> Tz {
+    self.display_timezone.unwrap_or(self.timezone)
+  }
+
+  /// The registry's sparse index, with cargo's `sparse+` prefix removed.
+  pub(crate) fn index_url(&self) -> Option<String> {
+    self.index_url.as_ref().map(|index_url| {
+      index_url
+        .strip_prefix("sparse+")
+        .unwrap_or(index_url)
+        .trim_end_matches('/')
+        .into()
+    })
+  }
+
+  /// Link to the page for crate `name` on the registry's website.
+  pub(crate) fn crate_url(&self, name: &str) -> String {
+    format!("{}/crates/{name}", self.registry_url.trim_end_matches('/'))
+  }
+
+  /// A TOML setting as the equivalent environment variable value, with lists
+  /// separated by commas.
+  fn setting(key: &str, value: &toml::Value) -> Result<String> {
+    Ok(match value {
+      toml::Value::String(value) => value.clone(),
+      toml::Value::Array(values) => values
+        .iter()
+        .map(|value| Self::setting(key, value))
+        .collect::<Result<Vec<String>>>()?
+        .join(","),
+      toml::Value::Table(_) => bail!("Setting `{key}` must not be a table"),
+      value => value.to_string(),
+    })
+  }
+
+  pub(crate) fn user_agent(&self) -> String {
+    format!(
+      "{AGENT}/{} ({})",
+      env!("CARGO_PKG_VERSION"),
+      self.contact.trim()
+    )
+  }
+
+  pub fn from_env() -> Result<Self, Error> {
+    Self::load().map_err(Error::Config)
+  }
+
+  /// The config from `vars`, named like the environment variables, with the
+  /// settings in the TOML file at `path` on top. Unlike `from_env`, neither
+  /// the environment nor `.env` is read.
+  pub fn from_vars(
+    vars: impl IntoIterator<Item = (impl Into<String>, impl Into<String>)>,
+    path: Option<&Path>,
+  ) -> Result<Self, Error> {
+    path
+      .map(Self::file)
+      .transpose()
+      .and_then(|file| {
+        Self::parse(
+          vars
+            .into_iter()
+            .map(|(key, value)| (key.into(), value.into())),
+          &file.unwrap_or_default(),
+        )
+      })
+      .map_err(Error::Config)
+  }
+
+  fn load() -> Result<Self> {
+    Self::with_file(&Self::file(CONFIG_PATH.as_ref())?)
+  }
+
+  /// The settings in the TOML config file at `path`, keyed like the
+  /// environment variables but in lowercase. A missing file has no settings.
+  pub(crate) fn file(path: &Path) -> Result<toml::Table> {
+    match fs::read_to_string(path) {
+      Ok(text) => text
+        .parse::<toml::Table>()
+        .map_err(|error| anyhow!("Invalid {}: {error}", path.display())),
+      Err(error) if error.kind() == io::ErrorKind::NotFound => {
+        Ok(toml::Table::new())
+      }
+      Err(error) => Err(error.into()),
+    }
+  }
+
+  /// The config from the environment and `.env`, with `file` on top.
+  pub(crate) fn with_file(file: &toml::Table) -> Result<Self> {
+    dotenv().ok();
+
+    Self::parse(env::vars(), file)
+  }
+
+  fn parse(
+    vars: impl IntoIterator<Item = (String, String)>,
+    file: &toml::Table,
+  ) -> Result<Self> {
+    let mut vars = vars
+      .into_iter()
+      .map(|(key, value)| (key.to_lowercase(), value))
+      .collect::<BTreeMap<String, String>>();
+
+    for (key, value) in file {
+      vars.insert(key.to_lowercase(), Self::setting(key, value)?);
+    }
+
+    let mut config = envy::from_iter::<_, Self>(vars)?;
+
+    config.template = config.template_for(config.locale);
+
+    if config.contact.trim().is_empty() {
+      bail!(
+        "CONTACT must be set to an email address or URL, it's sent in the \
+         user agent of every crates.io request"
+      );
+    }
+
+    for url in [config.registry_url.clone(), config.api_url()]
+      .into_iter()
+      .chain(config.index_url())
+    {
+      reqwest::Url::parse(&url)
+        .map_err(|error| anyhow!("Invalid registry URL `{url}`: {error}"))?;
+    }
+
+    Ok(config)
+  }
+}
@@ -0,0 +1,93 @@
+use super::*;
+
+/// Computes the CVSS v3 base score of a vector like
+/// `CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H`, following the
+/// specification's formulas and rounding. Other CVSS versions return `None`.
+pub(crate) fn score(vector: &str) -> Option<f64> {
+  let mut parts = vector.split('/');
+
+  if !matches!(parts.next(), Some("CVSS:3.0" | "CVSS:3.1")) {
+    return None;
+  }
+
+  let metrics = parts
+    .filter_map(|part| part.split_once(':'))
+    .collect::<BTreeMap<&str, &str>>();
+
+  let changed = match *metrics.get("S")? {
+    "U" => false,
+    "C" => true,
+    _ => return None,
+  };
+
+  let attack_vector = match *metrics.get("AV")? {
+    "N" => 0.85,
+    "A" => 0.62,
+    "L" => 0.55,
+    "P" => 0.2,
+    _ => return None,
+  };
+
+  let attack_complexity = match *metrics.get("AC")? {
+    "L" => 0.77,
+    "H" => 0.44,
+    _ => return None,
+  };
+
+  let privileges_required = match (*metrics.get("PR")?, changed) {
+    ("N", _) => 0.85,
+    ("L", false) => 0.62,
+    ("L", true) => 0.68,
+    ("H", false) => 0.27,
+    ("H", true) => 0.5,
+    _ => return None,
+  };
+
+  let user_interaction = match *metrics.get("UI")? {
+    "N" => 0.85,
+    "R" => 0.62,
+    _ => return None,
+  };
+
+  let impact = |metric| match *metrics.get(metric)? {
+    "H" => Some(0.56),
+    "L" => Some(0.22),
+    "N" => Some(0.0),
+    _ => None,
+  };
+
+  let iss: f64 =
+    1.0 - (1.0 - impact("C")?) * (1.0 - impact("I")?) * (1.0 - impact("A")?);
+
+  let impact = if changed {
+    7.52 * (iss - 0.029) - 3.25 * (iss - 0.02).powi(15)
+  } else {
+    6.42 * iss
+  };
+
+  if impact <= 0.0 {
+    return Some(0.0);
+  }
+
+  let exploitability: f64 = 8.22
+    * attack_vector
+    * attack_complexity
+    * privileges_required
+    * user_interaction;
+
+  Some(roundup(if changed {
+    (1.08 * (impact + exploitability)).min(10.0)
+  } else {
+    (impact + exploitability).min(10.0)
+  }))
+}
+
+fn roundup(value: f64) -> f64 {
+  let int = (value * 100_000.0).round() as u64;
+
+  if int.is_multiple_of(10_000) {
+    int as f64 / 100_000.0
+  } else {
+    (int / 10_000 + 1) as f64 / 10.0
+  }
+}
@@ -0,0 +1,152 @@
+use super::*;
+
+/// Posts the most promising crates first published in the last day, once per
+/// day. Daily picks are tracked in their own table, separately from the crates
+/// visited by the random and poll modes.
+pub(crate) async fn tick(bot: &Bot) -> Result<Vec<Published>> {
+  let today = Utc::now()
+    .with_timezone(&bot.config.timezone)
+    .date_naive()
+    .to_string();
+
+  if bot.db.state(DAILY)?.as_deref() == Some(today.as_str()) {
+    log::info!("Already posted new crates for {today}, waiting...");
+    return Ok(Vec::new());
+  }
+
+  let mut candidates = Vec::new();
+
+  for krate in bot
+    .api
+    .new_crates(Utc::now() - chrono::Duration::days(1))
+    .await?
+  {
+    if !bot.db.featured_daily(&krate.name)? {
+      candidates.push((promise(&krate), krate));
+    }
+  }
+
+  candidates.retain(|(promise, _)| *promise > 0.0);
+
+  candidates.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+
+  let mut picks = Vec::new();
+
+  for (promise, krate) in candidates
+    .into_iter()
+    .take(bot.config.daily_count * DAILY_SHORTLIST)
+  {
+    let Some(full_crate) = bot.candidate(&krate.name).await? else {
+      continue;
+    };
+
+    if let Some(reason) = bot.rejection(&full_crate).await? {
+      log::info!("Skipping new crate {}, {reason}", krate.name);
+      bot.db.audit(Action::Rejected, Some(&krate.name), &reason)?;
+      continue;
+    }
+
+    let mut history = 0;
+
+    for owner in &full_crate.owners {
+      history = bot
+        .owner_crates(owner.id)
+        .await?
+        .len()
+        .saturating_sub(1)
+        .max(history);
+    }
+
+    picks.push((promise + (history as f64 / 10.0).min(1.0), full_crate));
+  }
+
+  picks.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+
+  picks.truncate(bot.config.daily_count);
+
+  if picks.is_empty() {
+    log::info!("No promising new crates for {today}");
+    bot.db.set_state(DAILY, &today)?;
+    return Ok(Vec::new());
+  }
+
+  let headline = format!(
+    "New crates of the day, {}:",
+    bot.config.locale.date(
+      Utc::now()
+        .with_timezone(&bot.config.display_timezone())
+        .date_naive()
+    )
+  );
+
+  let text = compose::tag(&headline, &[], &bot.config.hashtags);
+
+  let (id, entry) = bot.tweet("daily", &today, text.clone()).await?;
+
+  bot.db.set_state(DAILY, &today)?;
+
+  let mut published = Published {
+    kind: "daily",
+    name: today.clone(),
+    replies: Vec::new(),
+    series: None,
+    text,
+    tweet_id: Some(id),
+  };
+
+  let mut parent = id;
+
+  // Replies are drafted like featured posts, numbered as the posts that
+  // would come next, since they aren't posts of their own.
+  let series = bot.db.series()? + 1;
+
+  for (i, (score, full_crate)) in picks.into_iter().enumerate() {
+    log::info!(
+      "Posting new crate {} with score {score:.2}",
+      full_crate.name
+    );
+
+    let text = bot
+      .draft(&full_crate, series + u64::try_from(i)?)
+      .await?
+      .text;
+
+    let Some(reply) = bot
+      .reply(&full_crate.name, text, None, parent, entry)
+      .await?
+    else {
+      break;
+    };
+
+    parent = reply;
+
+    published.replies.push(parent);
+
+    bot.db.record_daily(&full_crate.name, parent)?;
+
+    bot.db.audit(
+      Action::Published,
+      Some(&full_crate.name),
+      &format!("daily tweet {parent}"),
+    )?;
+  }
+
+  Ok(vec![published])
+}
+
+/// A cheap score of how promising a crate looks from its listing alone: not a
+/// placeholder, a descriptive description, and a linked repository.
+fn promise(krate: &Crate) -> f64 {
+  let squat = Squat::estimate(&krate.into());
+
+  let words = krate
+    .description
+    .as_deref()
+    .unwrap_or_default()
+    .split_whitespace()
+    .count();
+
+  (1.0 - squat.total()).max(0.0)
+    + (words as f64 / 10.0).min(1.0)
+    + if krate.repository.is_some() { 0.5 } else { 0.0 }
+}
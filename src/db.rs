@@ -0,0 +1,2497 @@
+use super::*;
+
+/// Columns holding timestamps, which older versions stored in chrono's
+/// display format rather than RFC 3339.
+const TIMESTAMP_COLUMNS: &[&str] = &[
+  "created_at",
+  "date",
+  "deleted",
+  "discovered",
+  "engagement_date",
+  "refreshed",
+  "updated_at",
+];
+
+/// State keys holding timestamps.
+const TIMESTAMP_STATE: &[&str] = &[ADVISORIES_SYNCED, LAST_RUN, NEXT_RUN];
+
+const POST_COLUMNS: &str = "series, name, tweet_id, date, description, \
+  keywords, text, downloads, likes, quotes, replies, reposts, thread, deleted";
+
+pub(crate) struct Db {
+  conn: Connection,
+}
+
+impl Db {
+  pub(crate) fn open(path: Option<PathBuf>) -> Result<Self> {
+    let mut conn = sqlite::open(path.unwrap_or(PathBuf::from(":memory:")))?;
+
+    conn.set_busy_timeout(DB_BUSY_TIMEOUT.as_millis().try_into()?)?;
+
+    conn.execute("PRAGMA journal_mode = WAL")?;
+    conn.execute("PRAGMA synchronous = NORMAL")?;
+
+    let db = Self { conn };
+
+    db.transaction(|| db.tables())?;
+
+    Ok(db)
+  }
+
+  /// Runs `f` in a transaction, committing if it succeeds and rolling back if
+  /// it fails. `BEGIN IMMEDIATE` takes the write lock up front, so a second
+  /// process waits out the busy timeout instead of failing on upgrade.
+  fn transaction<T>(&self, f: impl FnOnce() -> Result<T>) -> Result<T> {
+    self.conn.execute("BEGIN IMMEDIATE")?;
+
+    match f() {
+      Ok(value) => {
+        self.conn.execute("COMMIT")?;
+        Ok(value)
+      }
+      Err(error) => {
+        self.conn.execute("ROLLBACK")?;
+        Err(error)
+      }
+    }
+  }
+
+  fn tables(&self) -> Result {
+    self.table(
+      "advisories",
+      &[
+        ("id", "TEXT PRIMARY KEY"),
+        ("package", "TEXT"),
+        ("cvss", "REAL"),
+        ("informational", "TEXT"),
+        ("patched", "TEXT"),
+        ("unaffected", "TEXT"),
+      ],
+    )?;
+
+    self.table(
+      "audit",
+      &[
+        ("id", "INTEGER PRIMARY KEY AUTOINCREMENT"),
+        ("date", "TEXT"),
+        ("action", "TEXT"),
+        ("name", "TEXT"),
+        ("detail", "TEXT"),
+      ],
+    )?;
+
+    self.table(
+      "cache",
+      &[
+        ("key", "TEXT PRIMARY KEY"),
+        ("value", "TEXT"),
+        ("date", "TEXT"),
+        ("expires", "TEXT"),
+      ],
+    )?;
+
+    self.table(
+      "comparisons",
+      &[
+        ("category", "TEXT PRIMARY KEY"),
+        ("names", "TEXT"),
+        ("tweet_id", "INTEGER"),
+        ("date", "TEXT"),
+      ],
+    )?;
+
+    self.table(
+      "crates",
+      &[
+        ("name", "TEXT"),
+        ("visited", "INTEGER"),
+        ("date", "TEXT"),
+        ("source", "TEXT"),
+      ],
+    )?;
+
+    self.table(
+      "daily",
+      &[
+        ("name", "TEXT PRIMARY KEY"),
+        ("tweet_id", "INTEGER"),
+        ("date", "TEXT"),
+      ],
+    )?;
+
+    self.table(
+      "deep_dives",
+      &[
+        ("name", "TEXT PRIMARY KEY"),
+        ("tweet_id", "INTEGER"),
+        ("date", "TEXT"),
+      ],
+    )?;
+
+    self.table(
+      "handles",
+      &[
+        ("login", "TEXT PRIMARY KEY"),
+        ("handle", "TEXT"),
+        ("fediverse", "TEXT"),
+        ("discovered", "TEXT"),
+      ],
+    )?;
+
+    self.table("gone", &[("name", "TEXT PRIMARY KEY"), ("date", "TEXT")])?;
+
+    self.table(
+      "keywords",
+      &[
+        ("name", "TEXT"),
+        ("keyword", "TEXT"),
+        ("PRIMARY KEY", "(name, keyword)"),
+      ],
+    )?;
+
+    self.table(
+      "metadata",
+      &[
+        ("name", "TEXT PRIMARY KEY"),
+        ("description", "TEXT"),
+        ("downloads", "INTEGER"),
+        ("max_version", "TEXT"),
+        ("repository", "TEXT"),
+        ("created_at", "TEXT"),
+        ("updated_at", "TEXT"),
+        ("refreshed", "TEXT"),
+      ],
+    )?;
+
+    self.table(
+      "milestones",
+      &[
+        ("name", "TEXT"),
+        ("milestone", "TEXT"),
+        ("tweet_id", "INTEGER"),
+        ("date", "TEXT"),
+        ("PRIMARY KEY", "(name, milestone)"),
+      ],
+    )?;
+
+    self.table(
+      "optouts",
+      &[
+        ("kind", "TEXT"),
+        ("name", "TEXT"),
+        ("date", "TEXT"),
+        ("PRIMARY KEY", "(kind, name)"),
+      ],
+    )?;
+
+    self.table(
+      "overrides",
+      &[
+        ("name", "TEXT PRIMARY KEY"),
+        ("description", "TEXT"),
+        ("hashtags", "TEXT"),
+        ("link", "TEXT"),
+        ("note", "TEXT"),
+        ("date", "TEXT"),
+      ],
+    )?;
+
+    self.table(
+      "owners",
+      &[
+        ("login", "TEXT PRIMARY KEY"),
+        ("id", "INTEGER"),
+        ("name", "TEXT"),
+        ("kind", "TEXT"),
+        ("url", "TEXT"),
+        ("avatar", "TEXT"),
+        ("date", "TEXT"),
+      ],
+    )?;
+
+    self.table(
+      "ownership",
+      &[
+        ("login", "TEXT"),
+        ("name", "TEXT"),
+        ("PRIMARY KEY", "(login, name)"),
+      ],
+    )?;
+
+    self.table(
+      "polls",
+      &[
+        ("id", "INTEGER PRIMARY KEY"),
+        ("candidates", "TEXT"),
+        ("status", "TEXT"),
+        ("winner", "TEXT"),
+        ("date", "TEXT"),
+      ],
+    )?;
+
+    self.table(
+      "posts",
+      &[
+        ("series", "INTEGER PRIMARY KEY"),
+        ("name", "TEXT"),
+        ("tweet_id", "INTEGER"),
+        ("date", "TEXT"),
+        ("description", "TEXT"),
+        ("keywords", "TEXT"),
+        ("text", "TEXT"),
+        ("downloads", "INTEGER"),
+        ("likes", "INTEGER"),
+        ("quotes", "INTEGER"),
+        ("replies", "INTEGER"),
+        ("reposts", "INTEGER"),
+        ("engagement_date", "TEXT"),
+        ("thread", "TEXT"),
+        ("deleted", "TEXT"),
+        ("deleted_reason", "TEXT"),
+        ("twitter_error", "TEXT"),
+        ("twitter_attempts", "INTEGER"),
+      ],
+    )?;
+
+    self.table(
+      "published",
+      &[
+        ("id", "INTEGER PRIMARY KEY AUTOINCREMENT"),
+        ("date", "TEXT"),
+        ("kind", "TEXT"),
+        ("name", "TEXT"),
+        ("tweet_id", "INTEGER"),
+        ("replies", "INTEGER"),
+        ("post", "INTEGER"),
+      ],
+    )?;
+
+    self.table("queue", &[("name", "TEXT PRIMARY KEY"), ("date", "TEXT")])?;
+
+    // Reviews were once keyed by name alone, so approving one flag approved
+    // every other reason to hold the crate back. Those tables are rebuilt
+    // keyed by reason too, keeping their reviews.
+    let rekey = self.primary_key("reviews")? == ["name"];
+
+    if rekey {
+      self
+        .conn
+        .execute("ALTER TABLE reviews RENAME TO reviews_by_name")?;
+    }
+
+    self.table(
+      "reviews",
+      &[
+        ("name", "TEXT"),
+        ("reason", "TEXT"),
+        ("status", "TEXT"),
+        ("date", "TEXT"),
+        ("PRIMARY KEY", "(name, reason)"),
+      ],
+    )?;
+
+    if rekey {
+      self.conn.execute(
+        "INSERT INTO reviews (name, reason, status, date) \
+         SELECT name, reason, status, date FROM reviews_by_name; \
+         DROP TABLE reviews_by_name;",
+      )?;
+    }
+
+    self.table(
+      "snapshots",
+      &[
+        ("date", "TEXT PRIMARY KEY"),
+        ("crates", "INTEGER"),
+        ("downloads", "INTEGER"),
+        ("versions", "INTEGER"),
+        ("categories", "TEXT"),
+      ],
+    )?;
+
+    self.table(
+      "spotlights",
+      &[
+        ("login", "TEXT PRIMARY KEY"),
+        ("tweet_id", "INTEGER"),
+        ("date", "TEXT"),
+      ],
+    )?;
+
+    self.table("state", &[("key", "TEXT PRIMARY KEY"), ("value", "TEXT")])?;
+
+    self.migrate_timestamps(
+      "state",
+      "value",
+      &format!(
+        "key IN ({})",
+        TIMESTAMP_STATE
+          .iter()
+          .map(|key| format!("'{key}'"))
+          .collect::<Vec<String>>()
+          .join(", ")
+      ),
+    )?;
+
+    self.table(
+      "sync_state",
+      &[
+        ("source", "TEXT PRIMARY KEY"),
+        ("page", "INTEGER"),
+        ("seek", "TEXT"),
+        ("sort", "TEXT"),
+        ("date", "TEXT"),
+      ],
+    )?;
+
+    self.table(
+      "yanked",
+      &[
+        ("name", "TEXT PRIMARY KEY"),
+        ("latest", "INTEGER"),
+        ("available", "INTEGER"),
+        ("date", "TEXT"),
+      ],
+    )?;
+
+    Ok(())
+  }
+
+  pub(crate) fn table(&self, name: &str, columns: &[(&str, &str)]) -> Result {
+    log::info!("Creating table {name} with columns {:?}", columns);
+
+    self.conn.execute(format!(
+      "CREATE TABLE IF NOT EXISTS {} ({})",
+      name,
+      columns
+        .iter()
+        .map(|(column, data_type)| format!("{column} {data_type}"))
+        .collect::<Vec<String>>()
+        .join(", ")
+    ))?;
+
+    let mut statement =
+      self.conn.prepare(format!("PRAGMA table_info({name})"))?;
+
+    let mut existing = Vec::new();
+
+    while let State::Row = statement.next()? {
+      existing.push(statement.read::<String>(1)?);
+    }
+
+    for (column, data_type) in columns {
+      if column.contains(' ') || existing.iter().any(|name| name == column) {
+        continue;
+      }
+
+      log::info!("Adding column {column} to table {name}");
+
+      self.conn.execute(format!(
+        "ALTER TABLE {name} ADD COLUMN {column} {data_type}"
+      ))?;
+    }
+
+    for (column, _) in columns {
+      if TIMESTAMP_COLUMNS.contains(column) {
+        self.migrate_timestamps(name, column, "1")?;
+      }
+    }
+
+    Ok(())
+  }
+
+  /// The columns of `table`'s primary key, in key order, or none if the table
+  /// doesn't exist.
+  fn primary_key(&self, table: &str) -> Result<Vec<String>> {
+    let mut statement =
+      self.conn.prepare(format!("PRAGMA table_info({table})"))?;
+
+    let mut key = Vec::new();
+
+    while let State::Row = statement.next()? {
+      let position = statement.read::<i64>(5)?;
+
+      if position > 0 {
+        key.push((position, statement.read::<String>(1)?));
+      }
+    }
+
+    key.sort();
+
+    Ok(key.into_iter().map(|(_, column)| column).collect())
+  }
+
+  /// Rewrites timestamps in `column` of the rows matching `filter` from the
+  /// display format, `2022-08-01 12:00:00.5 UTC`, to the fixed-width RFC 3339
+  /// of `timestamp`, `2022-08-01T12:00:00.500000000Z`, padding the fraction
+  /// of a second, which the display format leaves out or shortens, to
+  /// nanoseconds.
+  fn migrate_timestamps(
+    &self,
+    table: &str,
+    column: &str,
+    filter: &str,
+  ) -> Result {
+    self.conn.execute(format!(
+      "UPDATE {table} \
+       SET {column} = SUBSTR({column}, 1, 10) || 'T' \
+       || SUBSTR({column}, 12, 8) || '.' \
+       || SUBSTR(SUBSTR({column}, 21, MAX(LENGTH({column}) - 24, 0)) \
+       || '000000000', 1, 9) || 'Z' \
+       WHERE {filter} AND {column} GLOB \
+       '[0-9][0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9] \
+       [0-9][0-9]:[0-9][0-9]:[0-9][0-9]*UTC'"
+    ))?;
+
+    Ok(())
+  }
+
+  /// Timestamps are stored as fixed-width RFC 3339 in UTC, which compare
+  /// correctly as strings, and are only converted to a timezone for display.
+  pub(crate) fn timestamp(date: DateTime<Utc>) -> String {
+    date.to_rfc3339_opts(SecondsFormat::Nanos, true)
+  }
+
+  pub(crate) fn replace_advisories(&self, advisories: &[Advisory]) -> Result {
+    self.transaction(|| self.insert_advisories(advisories))
+  }
+
+  fn insert_advisories(&self, advisories: &[Advisory]) -> Result {
+    self.conn.execute("DELETE FROM advisories")?;
+
+    for advisory in advisories {
+      self
+        .conn
+        .prepare(
+          "INSERT OR REPLACE INTO advisories \
+           (id, package, cvss, informational, patched, unaffected) \
+           VALUES (:id, :package, :cvss, :informational, :patched, :unaffected)",
+        )?
+        .bind_by_name(":id", advisory.id.as_str())?
+        .bind_by_name(":package", advisory.package.as_str())?
+        .bind_by_name(":cvss", advisory.cvss)?
+        .bind_by_name(":informational", advisory.informational.as_deref())?
+        .bind_by_name(
+          ":patched",
+          serde_json::to_string(&advisory.patched)?.as_str(),
+        )?
+        .bind_by_name(
+          ":unaffected",
+          serde_json::to_string(&advisory.unaffected)?.as_str(),
+        )?
+        .next()?;
+    }
+
+    Ok(())
+  }
+
+  pub(crate) fn advisories(&self, package: &str) -> Result<Vec<Advisory>> {
+    let mut statement = self
+      .conn
+      .prepare(
+        "SELECT id, package, cvss, informational, patched, unaffected \
+         FROM advisories WHERE package = :package ORDER BY id",
+      )?
+      .bind_by_name(":package", package)?;
+
+    let mut advisories = Vec::new();
+
+    while let State::Row = statement.next()? {
+      advisories.push(Advisory {
+        id: statement.read::<String>(0)?,
+        package: statement.read::<String>(1)?,
+        cvss: statement.read::<Option<f64>>(2)?,
+        informational: statement.read::<Option<String>>(3)?,
+        patched: serde_json::from_str(&statement.read::<String>(4)?)?,
+        unaffected: serde_json::from_str(&statement.read::<String>(5)?)?,
+      });
+    }
+
+    Ok(advisories)
+  }
+
+  pub(crate) fn audit(
+    &self,
+    action: Action,
+    name: Option<&str>,
+    detail: &str,
+  ) -> Result {
+    self
+      .conn
+      .prepare(
+        "INSERT INTO audit (date, action, name, detail) \
+         VALUES (:date, :action, :name, :detail)",
+      )?
+      .bind_by_name(":date", Self::timestamp(Utc::now()).as_str())?
+      .bind_by_name(":action", action.as_str())?
+      .bind_by_name(":name", name)?
+      .bind_by_name(":detail", detail)?
+      .next()?;
+
+    Ok(())
+  }
+
+  pub(crate) fn audits(
+    &self,
+    action: Option<Action>,
+    name: Option<&str>,
+    limit: usize,
+  ) -> Result<Vec<Audit>> {
+    let mut statement = self
+      .conn
+      .prepare(
+        "SELECT date, action, name, detail FROM audit \
+         WHERE (:action IS NULL OR action = :action) \
+         AND (:name IS NULL OR name = :name) \
+         ORDER BY id DESC LIMIT :limit",
+      )?
+      .bind_by_name(":action", action.map(Action::as_str))?
+      .bind_by_name(":name", name)?
+      .bind_by_name(":limit", i64::try_from(limit)?)?;
+
+    let mut audits = Vec::new();
+
+    while let State::Row = statement.next()? {
+      audits.push(Audit {
+        date: statement.read::<String>(0)?.parse()?,
+        action: statement.read::<String>(1)?,
+        name: statement.read::<Option<String>>(2)?,
+        detail: statement.read::<String>(3)?,
+      });
+    }
+
+    Ok(audits)
+  }
+
+  pub(crate) fn cached(
+    &self,
+    key: &str,
+    ttl: Option<Duration>,
+  ) -> Result<Option<String>> {
+    let mut statement = self
+      .conn
+      .prepare("SELECT value, date FROM cache WHERE key = :key")?
+      .bind_by_name(":key", key)?;
+
+    if let State::Row = statement.next()? {
+      let value = statement.read::<String>(0)?;
+
+      let date = statement.read::<String>(1)?.parse::<DateTime<Utc>>()?;
+
+      let fresh = match ttl {
+        Some(ttl) => (Utc::now() - date).to_std().unwrap_or_default() < ttl,
+        None => true,
+      };
+
+      if fresh {
+        return Ok(Some(value));
+      }
+    }
+
+    Ok(None)
+  }
+
+  /// Caches `value` under `key`, first evicting entries that expired, so
+  /// the cache doesn't grow with every crate ever fetched. Entries cached
+  /// without a `ttl` never expire.
+  pub(crate) fn cache(
+    &self,
+    key: &str,
+    value: &str,
+    ttl: Option<Duration>,
+  ) -> Result {
+    let now = Utc::now();
+
+    self
+      .conn
+      .prepare("DELETE FROM cache WHERE expires < :now")?
+      .bind_by_name(":now", Self::timestamp(now).as_str())?
+      .next()?;
+
+    let expires = ttl
+      .map(chrono::Duration::from_std)
+      .transpose()?
+      .map(|ttl| Self::timestamp(now + ttl));
+
+    self
+      .conn
+      .prepare(
+        "INSERT OR REPLACE INTO cache (key, value, date, expires) \
+         VALUES (:key, :value, :date, :expires)",
+      )?
+      .bind_by_name(":key", key)?
+      .bind_by_name(":value", value)?
+      .bind_by_name(":date", Self::timestamp(now).as_str())?
+      .bind_by_name(":expires", expires.as_deref())?
+      .next()?;
+
+    Ok(())
+  }
+
+  pub(crate) fn clear_cache(&self) -> Result<i64> {
+    let count = self.count("cache")?;
+
+    self.conn.execute("DELETE FROM cache")?;
+
+    Ok(count)
+  }
+
+  pub(crate) fn count(&self, name: &str) -> Result<i64> {
+    log::info!("Fetching row count for table {name}");
+
+    let mut statement =
+      self.conn.prepare(format!("SELECT COUNT(*) FROM {name}"))?;
+
+    if let State::Row = statement.next()? {
+      return Ok(statement.read::<i64>(0)?);
+    }
+
+    bail!("Failed reading COUNT(*) for table {name}")
+  }
+
+  /// Unvisited candidates. With `sources`, only crates synced from one of
+  /// those focus queries are returned.
+  pub(crate) fn crates(&self, sources: &[String]) -> Result<Vec<String>> {
+    log::info!("Fetching all crate names from db...");
+
+    let mut statement = self.conn.prepare(format!(
+      "SELECT name FROM crates WHERE visited = 0 AND name NOT IN \
+         (SELECT name FROM optouts WHERE kind = 'crate') AND name NOT IN \
+         (SELECT name FROM yanked WHERE available = 0) AND name NOT IN \
+         (SELECT name FROM gone){}",
+      if sources.is_empty() {
+        String::new()
+      } else {
+        format!(
+          " AND source IN ({})",
+          (1..=sources.len())
+            .map(|i| format!("?{i}"))
+            .collect::<Vec<String>>()
+            .join(", ")
+        )
+      }
+    ))?;
+
+    for (i, source) in sources.iter().enumerate() {
+      statement = statement.bind(i + 1, source.as_str())?;
+    }
+
+    let mut ret = Vec::new();
+
+    while let State::Row = statement.next()? {
+      ret.push(statement.read::<String>(0)?);
+    }
+
+    Ok(ret)
+  }
+
+  pub(crate) fn count_source(&self, source: &str) -> Result<i64> {
+    let mut statement = self
+      .conn
+      .prepare("SELECT COUNT(*) FROM crates WHERE source = :source")?
+      .bind_by_name(":source", source)?;
+
+    statement.next()?;
+
+    Ok(statement.read::<i64>(0)?)
+  }
+
+  pub(crate) fn mentions(
+    &self,
+    owners: &[User],
+    optout: &[String],
+  ) -> Result<Vec<String>> {
+    let mut mentions = Vec::new();
+
+    for owner in owners {
+      if optout
+        .iter()
+        .any(|login| login.eq_ignore_ascii_case(&owner.login))
+      {
+        log::info!("Owner {} opted out of mentions", owner.login);
+        continue;
+      }
+
+      let mut statement = self
+        .conn
+        .prepare(
+          "SELECT handle FROM handles \
+           WHERE login = :login AND handle IS NOT NULL",
+        )?
+        .bind_by_name(":login", owner.login.as_str())?;
+
+      if let State::Row = statement.next()? {
+        mentions.push(statement.read::<String>(0)?);
+      }
+    }
+
+    Ok(mentions)
+  }
+
+  /// How to credit each owner: their Twitter handle, their fediverse
+  /// address, or failing those, their login. Owners who opted out of
+  /// mentions are always credited by login.
+  pub(crate) fn attribution(
+    &self,
+    owners: &[User],
+    optout: &[String],
+  ) -> Result<Vec<String>> {
+    let mut attribution = Vec::new();
+
+    for owner in owners {
+      let mut statement = self
+        .conn
+        .prepare("SELECT handle, fediverse FROM handles WHERE login = :login")?
+        .bind_by_name(":login", owner.login.as_str())?;
+
+      let handle = if optout
+        .iter()
+        .any(|login| login.eq_ignore_ascii_case(&owner.login))
+      {
+        None
+      } else if let State::Row = statement.next()? {
+        statement
+          .read::<Option<String>>(0)?
+          .map(|handle| format!("@{}", handle.trim_start_matches('@')))
+          .or(statement.read::<Option<String>>(1)?)
+      } else {
+        None
+      };
+
+      attribution.push(handle.unwrap_or_else(|| owner.login.clone()));
+    }
+
+    Ok(attribution)
+  }
+
+  /// Whether `login` has no handles on record, or only discovered ones older
+  /// than `ttl`. Rows added by hand have no discovery date and are kept.
+  pub(crate) fn needs_discovery(
+    &self,
+    login: &str,
+    ttl: Duration,
+  ) -> Result<bool> {
+    let mut statement = self
+      .conn
+      .prepare("SELECT discovered FROM handles WHERE login = :login")?
+      .bind_by_name(":login", login)?;
+
+    if let State::Row = statement.next()? {
+      return Ok(match statement.read::<Option<String>>(0)? {
+        Some(date) => {
+          (Utc::now() - date.parse::<DateTime<Utc>>()?)
+            .to_std()
+            .unwrap_or_default()
+            >= ttl
+        }
+        None => false,
+      });
+    }
+
+    Ok(true)
+  }
+
+  pub(crate) fn record_handles(
+    &self,
+    login: &str,
+    handles: &Handles,
+  ) -> Result {
+    log::info!("Recording handles for {login}: {handles:?}");
+
+    self
+      .conn
+      .prepare(
+        "INSERT OR REPLACE INTO handles (login, handle, fediverse, discovered) \
+         VALUES (:login, :handle, :fediverse, :discovered)",
+      )?
+      .bind_by_name(":login", login)?
+      .bind_by_name(":handle", handles.twitter.as_deref())?
+      .bind_by_name(":fediverse", handles.fediverse.as_deref())?
+      .bind_by_name(":discovered", Self::timestamp(Utc::now()).as_str())?
+      .next()?;
+
+    Ok(())
+  }
+
+  /// The override for crate `name`, if an operator set one.
+  pub(crate) fn crate_override(&self, name: &str) -> Result<Option<Override>> {
+    let mut statement = self
+      .conn
+      .prepare(
+        "SELECT description, hashtags, link, note FROM overrides \
+         WHERE name = :name",
+      )?
+      .bind_by_name(":name", name)?;
+
+    if let State::Row = statement.next()? {
+      return Ok(Some(Self::read_override(&statement, 0)?));
+    }
+
+    Ok(None)
+  }
+
+  pub(crate) fn overrides(&self) -> Result<Vec<(String, Override)>> {
+    let mut statement = self.conn.prepare(
+      "SELECT name, description, hashtags, link, note FROM overrides \
+       ORDER BY name",
+    )?;
+
+    let mut ret = Vec::new();
+
+    while let State::Row = statement.next()? {
+      ret.push((
+        statement.read::<String>(0)?,
+        Self::read_override(&statement, 1)?,
+      ));
+    }
+
+    Ok(ret)
+  }
+
+  fn read_override(statement: &Statement, start: usize) -> Result<Override> {
+    Ok(Override {
+      description: statement.read::<Option<String>>(start)?,
+      hashtags: statement
+        .read::<Option<String>>(start + 1)?
+        .map(|hashtags| {
+          hashtags
+            .split(',')
+            .filter(|hashtag| !hashtag.is_empty())
+            .map(str::to_owned)
+            .collect()
+        }),
+      link: statement.read::<Option<String>>(start + 2)?,
+      note: statement.read::<Option<String>>(start + 3)?,
+    })
+  }
+
+  pub(crate) fn set_override(
+    &self,
+    name: &str,
+    crate_override: &Override,
+  ) -> Result {
+    log::info!("Setting override for {name}: {crate_override:?}");
+
+    self
+      .conn
+      .prepare(
+        "INSERT OR REPLACE INTO overrides \
+         (name, description, hashtags, link, note, date) \
+         VALUES (:name, :description, :hashtags, :link, :note, :date)",
+      )?
+      .bind_by_name(":name", name)?
+      .bind_by_name(":description", crate_override.description.as_deref())?
+      .bind_by_name(
+        ":hashtags",
+        crate_override
+          .hashtags
+          .as_ref()
+          .map(|hashtags| hashtags.join(","))
+          .as_deref(),
+      )?
+      .bind_by_name(":link", crate_override.link.as_deref())?
+      .bind_by_name(":note", crate_override.note.as_deref())?
+      .bind_by_name(":date", Self::timestamp(Utc::now()).as_str())?
+      .next()?;
+
+    Ok(())
+  }
+
+  pub(crate) fn remove_override(&self, name: &str) -> Result {
+    self
+      .conn
+      .prepare("DELETE FROM overrides WHERE name = :name")?
+      .bind_by_name(":name", name)?
+      .next()?;
+
+    Ok(())
+  }
+
+  pub(crate) fn optout(&self, kind: OptoutKind, name: &str) -> Result {
+    log::info!("Opting out {kind} {name}");
+
+    self
+      .conn
+      .prepare(
+        "INSERT OR IGNORE INTO optouts (kind, name, date) \
+         VALUES (:kind, :name, :date)",
+      )?
+      .bind_by_name(":kind", kind.as_str())?
+      .bind_by_name(":name", name)?
+      .bind_by_name(":date", Self::timestamp(Utc::now()).as_str())?
+      .next()?;
+
+    Ok(())
+  }
+
+  pub(crate) fn remove_optout(&self, kind: OptoutKind, name: &str) -> Result {
+    log::info!("Removing opt-out for {kind} {name}");
+
+    self
+      .conn
+      .prepare("DELETE FROM optouts WHERE kind = :kind AND name = :name")?
+      .bind_by_name(":kind", kind.as_str())?
+      .bind_by_name(":name", name)?
+      .next()?;
+
+    Ok(())
+  }
+
+  pub(crate) fn optouts(&self) -> Result<Vec<(String, String, String)>> {
+    let mut statement = self
+      .conn
+      .prepare("SELECT kind, name, date FROM optouts ORDER BY kind, name")?;
+
+    let mut ret = Vec::new();
+
+    while let State::Row = statement.next()? {
+      ret.push((
+        statement.read::<String>(0)?,
+        statement.read::<String>(1)?,
+        statement.read::<String>(2)?,
+      ));
+    }
+
+    Ok(ret)
+  }
+
+  pub(crate) fn opted_out(&self, kind: OptoutKind, name: &str) -> Result<bool> {
+    Ok(matches!(
+      self
+        .conn
+        .prepare("SELECT * FROM optouts WHERE kind = :kind AND name = :name")?
+        .bind_by_name(":kind", kind.as_str())?
+        .bind_by_name(":name", name)?
+        .next()?,
+      State::Row
+    ))
+  }
+
+  pub(crate) fn exclusion(
+    &self,
+    full_crate: &FullCrate,
+  ) -> Result<Option<String>> {
+    if self.opted_out(OptoutKind::Crate, &full_crate.name)? {
+      return Ok(Some("crate opted out".into()));
+    }
+
+    if full_crate
+      .keywords
+      .iter()
+      .any(|keyword| keyword.keyword == OPTOUT_KEYWORD)
+    {
+      return Ok(Some(format!("marked with `{OPTOUT_KEYWORD}`")));
+    }
+
+    for owner in &full_crate.owners {
+      if self.opted_out(OptoutKind::Owner, &owner.login)? {
+        return Ok(Some(format!("owner {} opted out", owner.login)));
+      }
+    }
+
+    Ok(None)
+  }
+
+  /// Adds a tweet, or a post that only made it to other platforms if
+  /// `tweet_id` is `None`, to the ledger the budget is counted from,
+  /// returning its entry. Senders call this as soon as it's sent, so it
+  /// counts even if the rest of the tick fails. Entries with `post` unset,
+  /// like mention replies and retried tweets, only count as tweets.
+  pub(crate) fn record_sent(
+    &self,
+    kind: &str,
+    name: Option<&str>,
+    tweet_id: Option<u64>,
+    post: bool,
+  ) -> Result<i64> {
+    self
+      .conn
+      .prepare(
+        "INSERT INTO published (date, kind, name, tweet_id, replies, post) \
+         VALUES (:date, :kind, :name, :tweet_id, 0, :post)",
+      )?
+      .bind_by_name(":date", Self::timestamp(Utc::now()).as_str())?
+      .bind_by_name(":kind", kind)?
+      .bind_by_name(":name", name)?
+      .bind_by_name(":tweet_id", tweet_id.map(i64::try_from).transpose()?)?
+      .bind_by_name(":post", i64::from(post))?
+      .next()?;
+
+    let mut statement = self.conn.prepare("SELECT last_insert_rowid()")?;
+
+    statement.next()?;
+
+    Ok(statement.read::<i64>(0)?)
+  }
+
+  /// Counts a thread reply, sent under ledger `entry`.
+  pub(crate) fn record_reply(&self, entry: i64) -> Result {
+    self
+      .conn
+      .prepare(
+        "UPDATE published SET replies = COALESCE(replies, 0) + 1 \
+         WHERE id = :id",
+      )?
+      .bind_by_name(":id", entry)?
+      .next()?;
+
+    Ok(())
+  }
+
+  /// Posts published after `since`, or, if `replies` is set, tweets
+  /// including thread replies. Ledger dates are fixed-width RFC 3339 in UTC,
+  /// so they compare correctly as strings.
+  pub(crate) fn published_since(
+    &self,
+    since: DateTime<Utc>,
+    replies: bool,
+  ) -> Result<u64> {
+    let mut statement = self
+      .conn
+      .prepare(if replies {
+        "SELECT COALESCE(SUM((tweet_id IS NOT NULL) + COALESCE(replies, 0)), \
+         0) FROM published WHERE date > :since"
+      } else {
+        "SELECT COUNT(*) FROM published \
+         WHERE date > :since AND COALESCE(post, 1)"
+      })?
+      .bind_by_name(":since", Self::timestamp(since).as_str())?;
+
+    statement.next()?;
+
+    Ok(statement.read::<i64>(0)?.try_into()?)
+  }
+
+  /// Published tweets per UTC day since `since`, oldest first, leaving out
+  /// days without any.
+  pub(crate) fn published_per_day(
+    &self,
+    since: Option<DateTime<Utc>>,
+  ) -> Result<Vec<(NaiveDate, u64)>> {
+    let mut statement = self
+      .conn
+      .prepare(
+        "SELECT substr(date, 1, 10), COUNT(*) FROM published \
+         WHERE :since IS NULL OR date >= :since \
+         GROUP BY substr(date, 1, 10) ORDER BY 1",
+      )?
+      .bind_by_name(":since", since.map(Self::timestamp).as_deref())?;
+
+    let mut days = Vec::new();
+
+    while let State::Row = statement.next()? {
+      days.push((
+        statement.read::<String>(0)?.parse()?,
+        statement.read::<i64>(1)?.try_into()?,
+      ));
+    }
+
+    Ok(days)
+  }
+
+  /// Totals over the history tables. Only activity since `since` is counted,
+  /// while crates tracked, the last sync, and the database size are current.
+  pub(crate) fn summary(
+    &self,
+    since: Option<DateTime<Utc>>,
+  ) -> Result<Summary> {
+    let since = since.map(Self::timestamp);
+
+    let read = |sql: &str| -> Result<Statement> {
+      let mut statement = self.conn.prepare(sql)?;
+
+      if sql.contains(":since") {
+        statement = statement.bind_by_name(":since", since.as_deref())?;
+      }
+
+      statement.next()?;
+
+      Ok(statement)
+    };
+
+    let total =
+      |sql: &str| -> Result<u64> { Ok(read(sql)?.read::<i64>(0)?.try_into()?) };
+
+    let posted = total(
+      "SELECT COUNT(*) FROM published WHERE :since IS NULL OR date >= :since",
+    )?;
+
+    let mut platforms = BTreeMap::from([("twitter".to_owned(), posted)]);
+
+    let mut statement = self.conn.prepare("PRAGMA table_info(posts)")?;
+
+    let mut columns = Vec::new();
+
+    while let State::Row = statement.next()? {
+      columns.push(statement.read::<String>(1)?);
+    }
+
+    for column in columns {
+      match column.strip_suffix("_id") {
+        Some("tweet") | None => {}
+        Some(platform) => {
+          platforms.insert(
+            platform.into(),
+            total(&format!(
+              "SELECT COUNT({column}) FROM posts WHERE deleted IS NULL \
+               AND (:since IS NULL OR date >= :since)"
+            ))?,
+          );
+        }
+      }
+    }
+
+    Ok(Summary {
+      bytes: total(
+        "SELECT page_count * page_size \
+         FROM pragma_page_count(), pragma_page_size()",
+      )?,
+      engagement: read(
+        "SELECT AVG(likes + quotes + replies + reposts) FROM posts \
+         WHERE likes IS NOT NULL AND deleted IS NULL \
+         AND (:since IS NULL OR date >= :since)",
+      )?
+      .read::<Option<f64>>(0)?,
+      last_sync: read(&format!(
+        "SELECT MAX(date) FROM audit WHERE action = '{}'",
+        Action::SyncFinished.as_str()
+      ))?
+      .read::<Option<String>>(0)?
+      .map(|date| date.parse())
+      .transpose()?,
+      platforms,
+      posted,
+      skipped: total(&format!(
+        "SELECT COUNT(*) FROM audit WHERE action = '{}' \
+         AND (:since IS NULL OR date >= :since)",
+        Action::Rejected.as_str()
+      ))?,
+      tracked: total("SELECT COUNT(*) FROM crates")?,
+      visited: total("SELECT COUNT(*) FROM crates WHERE visited")?,
+    })
+  }
+
+  /// Queues `name` for manual review for `reason`. Crates that were already
+  /// reviewed for the same reason keep their status.
+  pub(crate) fn flag(&self, name: &str, reason: &str) -> Result {
+    self
+      .conn
+      .prepare(
+        "INSERT OR IGNORE INTO reviews (name, reason, status, date) \
+         VALUES (:name, :reason, :status, :date)",
+      )?
+      .bind_by_name(":name", name)?
+      .bind_by_name(":reason", reason)?
+      .bind_by_name(":status", ReviewStatus::Pending.as_str())?
+      .bind_by_name(":date", Self::timestamp(Utc::now()).as_str())?
+      .next()?;
+
+    Ok(())
+  }
+
+  /// The status of the review of `name` for `reason`, if it was flagged for
+  /// it.
+  pub(crate) fn review(
+    &self,
+    name: &str,
+    reason: &str,
+  ) -> Result<Option<ReviewStatus>> {
+    let mut statement = self
+      .conn
+      .prepare(
+        "SELECT status FROM reviews WHERE name = :name AND reason = :reason",
+      )?
+      .bind_by_name(":name", name)?
+      .bind_by_name(":reason", reason)?;
+
+    if let State::Row = statement.next()? {
+      return Ok(Some(statement.read::<String>(0)?.parse()?));
+    }
+
+    Ok(None)
+  }
+
+  pub(crate) fn reviews(
+    &self,
+    status: Option<ReviewStatus>,
+  ) -> Result<Vec<(String, ReviewStatus, String, String)>> {
+    let mut statement = self
+      .conn
+      .prepare(
+        "SELECT name, status, reason, date FROM reviews \
+         WHERE :status IS NULL OR status = :status ORDER BY date",
+      )?
+      .bind_by_name(":status", status.map(ReviewStatus::as_str))?;
+
+    let mut reviews = Vec::new();
+
+    while let State::Row = statement.next()? {
+      reviews.push((
+        statement.read::<String>(0)?,
+        statement.read::<String>(1)?.parse()?,
+        statement.read::<String>(2)?,
+        statement.read::<String>(3)?,
+      ));
+    }
+
+    Ok(reviews)
+  }
+
+  /// Sets the status of every review of `name`, whatever it was flagged
+  /// for. Reasons it's flagged for later are reviewed on their own.
+  pub(crate) fn set_review(&self, name: &str, status: ReviewStatus) -> Result {
+    self
+      .conn
+      .prepare(
+        "UPDATE reviews SET status = :status, date = :date WHERE name = :name",
+      )?
+      .bind_by_name(":status", status.as_str())?
+      .bind_by_name(":date", Self::timestamp(Utc::now()).as_str())?
+      .bind_by_name(":name", name)?
+      .next()?;
+
+    if self.conn.change_count() == 0 {
+      bail!("Crate {name} is not flagged for review");
+    }
+
+    Ok(())
+  }
+
+  /// Records that `name` was deleted from the registry, or renamed, so it's
+  /// never selected again.
+  pub(crate) fn mark_gone(&self, name: &str) -> Result {
+    self
+      .conn
+      .prepare("INSERT OR IGNORE INTO gone (name, date) VALUES (:name, :date)")?
+      .bind_by_name(":name", name)?
+      .bind_by_name(":date", Self::timestamp(Utc::now()).as_str())?
+      .next()?;
+
+    Ok(())
+  }
+
+  pub(crate) fn gone(&self, name: &str) -> Result<bool> {
+    Ok(matches!(
+      self
+        .conn
+        .prepare("SELECT * FROM gone WHERE name = :name")?
+        .bind_by_name(":name", name)?
+        .next()?,
+      State::Row
+    ))
+  }
+
+  /// The most downloaded crates with locally stored metadata.
+  pub(crate) fn popular(&self, limit: usize) -> Result<Vec<String>> {
+    let mut statement = self
+      .conn
+      .prepare(
+        "SELECT name FROM metadata WHERE name NOT IN (SELECT name FROM gone) \
+         ORDER BY downloads DESC LIMIT :limit",
+      )?
+      .bind_by_name(":limit", i64::try_from(limit)?)?;
+
+    let mut names = Vec::new();
+
+    while let State::Row = statement.next()? {
+      names.push(statement.read::<String>(0)?);
+    }
+
+    Ok(names)
+  }
+
+  pub(crate) fn featured_daily(&self, name: &str) -> Result<bool> {
+    Ok(matches!(
+      self
+        .conn
+        .prepare("SELECT * FROM daily WHERE name = :name")?
+        .bind_by_name(":name", name)?
+        .next()?,
+      State::Row
+    ))
+  }
+
+  pub(crate) fn record_daily(&self, name: &str, tweet_id: u64) -> Result {
+    self
+      .conn
+      .prepare(
+        "INSERT OR REPLACE INTO daily (name, tweet_id, date) \
+         VALUES (:name, :tweet_id, :date)",
+      )?
+      .bind_by_name(":name", name)?
+      .bind_by_name(":tweet_id", i64::try_from(tweet_id)?)?
+      .bind_by_name(":date", Self::timestamp(Utc::now()).as_str())?
+      .next()?;
+
+    Ok(())
+  }
+
+  /// Categories that haven't been compared yet, with up to `limit` of their
+  /// crates, most downloaded first. Categories with a single known crate are
+  /// left out.
+  pub(crate) fn categories(
+    &self,
+    limit: usize,
+  ) -> Result<BTreeMap<String, Vec<String>>> {
+    let mut statement = self.conn.prepare(
+      "SELECT keywords.keyword, keywords.name FROM keywords \
+       JOIN metadata ON metadata.name = keywords.name \
+       WHERE keywords.keyword LIKE 'category:%' \
+       AND SUBSTR(keywords.keyword, 10) NOT IN \
+       (SELECT category FROM comparisons) \
+       AND keywords.name NOT IN \
+       (SELECT name FROM optouts WHERE kind = 'crate') \
+       ORDER BY keywords.keyword, metadata.downloads DESC",
+    )?;
+
+    let mut categories = BTreeMap::<String, Vec<String>>::new();
+
+    while let State::Row = statement.next()? {
+      let keyword = statement.read::<String>(0)?;
+
+      let names = categories
+        .entry(keyword.trim_start_matches("category:").into())
+        .or_default();
+
+      if names.len() < limit {
+        names.push(statement.read::<String>(1)?);
+      }
+    }
+
+    categories.retain(|_, names| names.len() >= 2);
+
+    Ok(categories)
+  }
+
+  /// The most downloaded crates that have never been fetched in full, and so
+  /// have no categories or keywords indexed.
+  pub(crate) fn unindexed(&self, limit: usize) -> Result<Vec<String>> {
+    let mut statement = self
+      .conn
+      .prepare(
+        "SELECT name FROM metadata WHERE name NOT IN (SELECT name FROM yanked) \
+         AND name NOT IN (SELECT name FROM gone) \
+         ORDER BY downloads DESC LIMIT :limit",
+      )?
+      .bind_by_name(":limit", i64::try_from(limit)?)?;
+
+    let mut names = Vec::new();
+
+    while let State::Row = statement.next()? {
+      names.push(statement.read::<String>(0)?);
+    }
+
+    Ok(names)
+  }
+
+  pub(crate) fn record_comparison(
+    &self,
+    category: &str,
+    names: &[&str],
+    tweet_id: u64,
+  ) -> Result {
+    self
+      .conn
+      .prepare(
+        "INSERT OR REPLACE INTO comparisons (category, names, tweet_id, date) \
+         VALUES (:category, :names, :tweet_id, :date)",
+      )?
+      .bind_by_name(":category", category)?
+      .bind_by_name(":names", names.join(",").as_str())?
+      .bind_by_name(":tweet_id", i64::try_from(tweet_id)?)?
+      .bind_by_name(":date", Self::timestamp(Utc::now()).as_str())?
+      .next()?;
+
+    Ok(())
+  }
+
+  pub(crate) fn deep_dived(&self, name: &str) -> Result<bool> {
+    Ok(matches!(
+      self
+        .conn
+        .prepare("SELECT 1 FROM deep_dives WHERE name = :name")?
+        .bind_by_name(":name", name)?
+        .next()?,
+      State::Row
+    ))
+  }
+
+  pub(crate) fn record_deep_dive(&self, name: &str, tweet_id: u64) -> Result {
+    self
+      .conn
+      .prepare(
+        "INSERT OR REPLACE INTO deep_dives (name, tweet_id, date) \
+         VALUES (:name, :tweet_id, :date)",
+      )?
+      .bind_by_name(":name", name)?
+      .bind_by_name(":tweet_id", i64::try_from(tweet_id)?)?
+      .bind_by_name(":date", Self::timestamp(Utc::now()).as_str())?
+      .next()?;
+
+    Ok(())
+  }
+
+  /// Queues `name` to be featured ahead of other candidates.
+  pub(crate) fn enqueue(&self, name: &str) -> Result {
+    self
+      .conn
+      .prepare(
+        "INSERT OR IGNORE INTO queue (name, date) VALUES (:name, :date)",
+      )?
+      .bind_by_name(":name", name)?
+      .bind_by_name(":date", Self::timestamp(Utc::now()).as_str())?
+      .next()?;
+
+    Ok(())
+  }
+
+  pub(crate) fn dequeue(&self, name: &str) -> Result {
+    self
+      .conn
+      .prepare("DELETE FROM queue WHERE name = :name")?
+      .bind_by_name(":name", name)?
+      .next()?;
+
+    Ok(())
+  }
+
+  /// Queued crates, oldest first.
+  pub(crate) fn queued(&self) -> Result<Vec<String>> {
+    let mut statement =
+      self.conn.prepare("SELECT name FROM queue ORDER BY date")?;
+
+    let mut ret = Vec::new();
+
+    while let State::Row = statement.next()? {
+      ret.push(statement.read::<String>(0)?);
+    }
+
+    Ok(ret)
+  }
+
+  pub(crate) fn pending_poll(&self) -> Result<Option<(u64, Vec<String>)>> {
+    let mut statement = self
+      .conn
+      .prepare("SELECT id, candidates FROM polls WHERE status = 'open'")?;
+
+    if let State::Row = statement.next()? {
+      return Ok(Some((
+        statement.read::<i64>(0)?.try_into()?,
+        statement
+          .read::<String>(1)?
+          .split(',')
+          .map(str::to_string)
+          .collect(),
+      )));
+    }
+
+    Ok(None)
+  }
+
+  pub(crate) fn insert_poll(&self, id: u64, candidates: &[String]) -> Result {
+    log::info!("Recording open poll {id} for candidates {:?}", candidates);
+
+    self
+      .conn
+      .prepare(
+        "INSERT INTO polls (id, candidates, status, date) \
+         VALUES (:id, :candidates, 'open', :date)",
+      )?
+      .bind_by_name(":id", i64::try_from(id)?)?
+      .bind_by_name(":candidates", candidates.join(",").as_str())?
+      .bind_by_name(":date", Self::timestamp(Utc::now()).as_str())?
+      .next()?;
+
+    Ok(())
+  }
+
+  /// Closes poll `id`, with no winner if none of its options won.
+  pub(crate) fn close_poll(&self, id: u64, winner: Option<&str>) -> Result {
+    self
+      .conn
+      .prepare(
+        "UPDATE polls SET status = 'closed', winner = :winner WHERE id = :id",
+      )?
+      .bind_by_name(":id", i64::try_from(id)?)?
+      .bind_by_name(":winner", winner)?
+      .next()?;
+
+    Ok(())
+  }
+
+  /// How many distinct crates were featured in posts that weren't deleted,
+  /// and when the first of those posts was published.
+  pub(crate) fn featured(&self) -> Result<(u64, Option<DateTime<Utc>>)> {
+    let mut statement = self.conn.prepare(
+      "SELECT COUNT(DISTINCT name), MIN(date) FROM posts \
+       WHERE deleted IS NULL",
+    )?;
+
+    statement.next()?;
+
+    Ok((
+      statement.read::<i64>(0)?.try_into()?,
+      statement
+        .read::<Option<String>>(1)?
+        .map(|date| date.parse())
+        .transpose()?,
+    ))
+  }
+
+  pub(crate) fn series(&self) -> Result<u64> {
+    Ok(
+      self
+        .state(SERIES)?
+        .map(|series| series.parse())
+        .transpose()?
+        .unwrap_or(0),
+    )
+  }
+
+  /// Adds the columns recording cross-posts of each post to `platform`.
+  pub(crate) fn add_publisher(&self, platform: &str) -> Result {
+    self.table(
+      "posts",
+      &[
+        (&format!("{platform}_id"), "TEXT"),
+        (&format!("{platform}_error"), "TEXT"),
+        (&format!("{platform}_attempts"), "INTEGER"),
+        (&format!("{platform}_text"), "TEXT"),
+      ],
+    )
+  }
+
+  /// The posts column holding IDs on `platform`. Twitter's predates the other
+  /// platforms, and is the `tweet_id` column.
+  fn id_column(platform: &str) -> String {
+    if platform == TWITTER {
+      "tweet_id".into()
+    } else {
+      format!("{platform}_id")
+    }
+  }
+
+  /// The posts expression for the text posted to `platform`. Posts are only
+  /// recorded with their own text on platforms they were translated for.
+  fn text_column(platform: &str) -> String {
+    if platform == TWITTER {
+      "text".into()
+    } else {
+      format!("COALESCE({platform}_text, text)")
+    }
+  }
+
+  /// Records `text`, the translation of post `series` posted to `platform`.
+  pub(crate) fn record_translation(
+    &self,
+    series: u64,
+    platform: &str,
+    text: &str,
+  ) -> Result {
+    self
+      .conn
+      .prepare(format!(
+        "UPDATE posts SET {platform}_text = :text WHERE series = :series"
+      ))?
+      .bind_by_name(":text", text)?
+      .bind_by_name(":series", i64::try_from(series)?)?
+      .next()?;
+
+    Ok(())
+  }
+
+  /// The ID of post `series` on `platform`, unless publishing it there
+  /// failed.
+  pub(crate) fn publication(
+    &self,
+    series: u64,
+    platform: &str,
+  ) -> Result<Option<String>> {
+    let mut statement = self
+      .conn
+      .prepare(format!(
+        "SELECT {} FROM posts WHERE series = :series",
+        Self::id_column(platform),
+      ))?
+      .bind_by_name(":series", i64::try_from(series)?)?;
+
+    Ok(match statement.next()? {
+      State::Row => statement.read::<Option<String>>(0)?,
+      State::Done => None,
+    })
+  }
+
+  /// Records the ID of post `series` on `platform`, or why publishing it
+  /// there failed, counting the attempt.
+  pub(crate) fn record_publication(
+    &self,
+    series: u64,
+    platform: &str,
+    outcome: &Result<String>,
+  ) -> Result {
+    let (id, error) = match outcome {
+      Ok(id) => (Some(id.clone()), None),
+      Err(error) => (None, Some(error.to_string())),
+    };
+
+    self
+      .conn
+      .prepare(format!(
+        "UPDATE posts SET {} = :id, {platform}_error = :error, \
+         {platform}_attempts = COALESCE({platform}_attempts, 0) + 1 \
+         WHERE series = :series",
+        Self::id_column(platform),
+      ))?
+      .bind_by_name(":id", id.as_deref())?
+      .bind_by_name(":error", error.as_deref())?
+      .bind_by_name(":series", i64::try_from(series)?)?
+      .next()?;
+
+    Ok(())
+  }
+
+  /// Posts published after `since` that failed to publish to `platform` in
+  /// fewer than `PUBLISH_RETRY_ATTEMPTS` attempts and weren't deleted since,
+  /// as series, crate name, and text.
+  pub(crate) fn failed_publications(
+    &self,
+    platform: &str,
+    since: DateTime<Utc>,
+  ) -> Result<Vec<(u64, String, String)>> {
+    let mut statement = self
+      .conn
+      .prepare(format!(
+        "SELECT series, name, {} FROM posts \
+         WHERE {} IS NULL AND {platform}_error IS NOT NULL \
+         AND COALESCE({platform}_attempts, 0) < :attempts \
+         AND deleted IS NULL AND text IS NOT NULL AND date > :since \
+         ORDER BY series",
+        Self::text_column(platform),
+        Self::id_column(platform),
+      ))?
+      .bind_by_name(":attempts", i64::try_from(PUBLISH_RETRY_ATTEMPTS)?)?
+      .bind_by_name(":since", Self::timestamp(since).as_str())?;
+
+    let mut ret = Vec::new();
+
+    while let State::Row = statement.next()? {
+      ret.push((
+        statement.read::<i64>(0)?.try_into()?,
+        statement.read::<String>(1)?,
+        statement.read::<String>(2)?,
+      ));
+    }
+
+    Ok(ret)
+  }
+
+  pub(crate) fn record_post(
+    &self,
+    series: u64,
+    full_crate: &FullCrate,
+    tweet_id: Option<u64>,
+    text: &str,
+  ) -> Result {
+    log::info!("Recording post #{series} for crate {}", full_crate.name);
+
+    self.transaction(|| {
+      self
+        .conn
+        .prepare(
+          "INSERT INTO posts \
+           (series, name, tweet_id, date, description, keywords, text, downloads, thread) \
+           VALUES \
+           (:series, :name, :tweet_id, :date, :description, :keywords, :text, :downloads, :thread)",
+        )?
+        .bind_by_name(":series", i64::try_from(series)?)?
+        .bind_by_name(":name", full_crate.name.as_str())?
+        .bind_by_name(":tweet_id", tweet_id.map(i64::try_from).transpose()?)?
+        .bind_by_name(":date", Self::timestamp(Utc::now()).as_str())?
+        .bind_by_name(":description", full_crate.description.as_deref())?
+        .bind_by_name(
+          ":keywords",
+          full_crate
+            .keywords
+            .iter()
+            .map(|keyword| keyword.keyword.as_str())
+            .collect::<Vec<&str>>()
+            .join(",")
+            .as_str(),
+        )?
+        .bind_by_name(":text", text)?
+        .bind_by_name(":downloads", i64::try_from(full_crate.total_downloads)?)?
+        .bind_by_name(":thread", "")?
+        .next()?;
+
+      self.set_state(SERIES, &series.to_string())
+    })
+  }
+
+  /// Records the replies posted under post `series`, once the thread is done.
+  pub(crate) fn set_thread(&self, series: u64, thread: &[u64]) -> Result {
+    self
+      .conn
+      .prepare("UPDATE posts SET thread = :thread WHERE series = :series")?
+      .bind_by_name(
+        ":thread",
+        thread
+          .iter()
+          .map(u64::to_string)
+          .collect::<Vec<String>>()
+          .join(",")
+          .as_str(),
+      )?
+      .bind_by_name(":series", i64::try_from(series)?)?
+      .next()?;
+
+    Ok(())
+  }
+
+  pub(crate) fn record_engagement(
+    &self,
+    tweet_id: u64,
+    engagement: Engagement,
+  ) -> Result {
+    self
+      .conn
+      .prepare(
+        "UPDATE posts SET likes = :likes, quotes = :quotes, \
+         replies = :replies, reposts = :reposts, engagement_date = :date \
+         WHERE tweet_id = :tweet_id",
+      )?
+      .bind_by_name(":likes", i64::try_from(engagement.likes)?)?
+      .bind_by_name(":quotes", i64::try_from(engagement.quotes)?)?
+      .bind_by_name(":replies", i64::try_from(engagement.replies)?)?
+      .bind_by_name(":reposts", i64::try_from(engagement.reposts)?)?
+      .bind_by_name(":date", Self::timestamp(Utc::now()).as_str())?
+      .bind_by_name(":tweet_id", i64::try_from(tweet_id)?)?
+      .next()?;
+
+    Ok(())
+  }
+
+  /// Every post that hasn't been deleted, newest first.
+  pub(crate) fn posts(&self) -> Result<Vec<Post>> {
+    Self::read_posts(self.conn.prepare(format!(
+      "SELECT {POST_COLUMNS} FROM posts WHERE deleted IS NULL \
+       ORDER BY series DESC"
+    ))?)
+  }
+
+  pub(crate) fn post(&self, series: u64) -> Result<Option<Post>> {
+    Ok(
+      Self::read_posts(
+        self
+          .conn
+          .prepare(format!(
+            "SELECT {POST_COLUMNS} FROM posts WHERE series = :series"
+          ))?
+          .bind_by_name(":series", i64::try_from(series)?)?,
+      )?
+      .pop(),
+    )
+  }
+
+  /// Posts featuring `name` that haven't been deleted, newest first.
+  pub(crate) fn posts_featuring(&self, name: &str) -> Result<Vec<Post>> {
+    Self::read_posts(
+      self
+        .conn
+        .prepare(format!(
+          "SELECT {POST_COLUMNS} FROM posts \
+           WHERE name = :name AND deleted IS NULL ORDER BY series DESC"
+        ))?
+        .bind_by_name(":name", name)?,
+    )
+  }
+
+  fn read_posts(mut statement: Statement) -> Result<Vec<Post>> {
+    let mut posts = Vec::new();
+
+    while let State::Row = statement.next()? {
+      posts.push(Post {
+        series: statement.read::<i64>(0)?.try_into()?,
+        name: statement.read::<String>(1)?,
+        tweet_id: statement
+          .read::<Option<i64>>(2)?
+          .map(u64::try_from)
+          .transpose()?,
+        date: statement.read::<String>(3)?.parse()?,
+        description: statement.read::<Option<String>>(4)?,
+        keywords: statement
+          .read::<Option<String>>(5)?
+          .unwrap_or_default()
+          .split(',')
+          .filter(|keyword| !keyword.is_empty())
+          .map(str::to_string)
+          .collect(),
+        text: statement.read::<Option<String>>(6)?,
+        downloads: statement
+          .read::<Option<i64>>(7)?
+          .map(u64::try_from)
+          .transpose()?,
+        engagement: match statement.read::<Option<i64>>(8)? {
+          Some(likes) => Some(Engagement {
+            likes: likes.try_into()?,
+            quotes: statement.read::<i64>(9)?.try_into()?,
+            replies: statement.read::<i64>(10)?.try_into()?,
+            reposts: statement.read::<i64>(11)?.try_into()?,
+          }),
+          None => None,
+        },
+        thread: statement
+          .read::<Option<String>>(12)?
+          .unwrap_or_default()
+          .split(',')
+          .filter(|id| !id.is_empty())
+          .map(str::parse)
+          .collect::<Result<Vec<u64>, _>>()?,
+        deleted: statement
+          .read::<Option<String>>(13)?
+          .map(|date| date.parse())
+          .transpose()?,
+      });
+    }
+
+    Ok(posts)
+  }
+
+  pub(crate) fn mark_deleted(&self, series: u64, reason: &str) -> Result {
+    self
+      .conn
+      .prepare(
+        "UPDATE posts SET deleted = :date, deleted_reason = :reason \
+         WHERE series = :series",
+      )?
+      .bind_by_name(":date", Self::timestamp(Utc::now()).as_str())?
+      .bind_by_name(":reason", reason)?
+      .bind_by_name(":series", i64::try_from(series)?)?
+      .next()?;
+
+    Ok(())
+  }
+
+  /// Featured crates whose metadata was refreshed least recently.
+  pub(crate) fn stale_featured(&self, limit: usize) -> Result<Vec<String>> {
+    let mut statement = self
+      .conn
+      .prepare(
+        "SELECT DISTINCT posts.name FROM posts \
+         LEFT JOIN metadata ON metadata.name = posts.name \
+         WHERE posts.deleted IS NULL \
+         AND posts.name NOT IN (SELECT name FROM gone) \
+         ORDER BY metadata.refreshed IS NOT NULL, metadata.refreshed \
+         LIMIT :limit",
+      )?
+      .bind_by_name(":limit", i64::try_from(limit)?)?;
+
+    let mut names = Vec::new();
+
+    while let State::Row = statement.next()? {
+      names.push(statement.read::<String>(0)?);
+    }
+
+    Ok(names)
+  }
+
+  pub(crate) fn celebrated(&self, name: &str, milestone: &str) -> Result<bool> {
+    Ok(matches!(
+      self
+        .conn
+        .prepare(
+          "SELECT * FROM milestones WHERE name = :name AND milestone = :milestone",
+        )?
+        .bind_by_name(":name", name)?
+        .bind_by_name(":milestone", milestone)?
+        .next()?,
+      State::Row
+    ))
+  }
+
+  pub(crate) fn celebrate(
+    &self,
+    name: &str,
+    milestone: &str,
+    tweet_id: u64,
+  ) -> Result {
+    self
+      .conn
+      .prepare(
+        "INSERT OR REPLACE INTO milestones (name, milestone, tweet_id, date) \
+         VALUES (:name, :milestone, :tweet_id, :date)",
+      )?
+      .bind_by_name(":name", name)?
+      .bind_by_name(":milestone", milestone)?
+      .bind_by_name(":tweet_id", i64::try_from(tweet_id)?)?
+      .bind_by_name(":date", Self::timestamp(Utc::now()).as_str())?
+      .next()?;
+
+    Ok(())
+  }
+
+  /// Snapshot dates are fixed-width RFC 3339 in UTC, like ledger dates, so
+  /// they compare correctly as strings.
+  pub(crate) fn record_snapshot(&self, snapshot: &Snapshot) -> Result {
+    self
+      .conn
+      .prepare(
+        "INSERT OR REPLACE INTO snapshots \
+         (date, crates, downloads, versions, categories) \
+         VALUES (:date, :crates, :downloads, :versions, :categories)",
+      )?
+      .bind_by_name(":date", Self::timestamp(snapshot.date).as_str())?
+      .bind_by_name(":crates", i64::try_from(snapshot.crates)?)?
+      .bind_by_name(":downloads", i64::try_from(snapshot.downloads)?)?
+      .bind_by_name(":versions", i64::try_from(snapshot.versions)?)?
+      .bind_by_name(
+        ":categories",
+        serde_json::to_string(&snapshot.categories)?.as_str(),
+      )?
+      .next()?;
+
+    Ok(())
+  }
+
+  pub(crate) fn latest_snapshot(&self) -> Result<Option<Snapshot>> {
+    Ok(
+      Self::read_snapshots(self.conn.prepare(
+        "SELECT date, crates, downloads, versions, categories FROM snapshots \
+         ORDER BY date DESC LIMIT 1",
+      )?)?
+      .pop(),
+    )
+  }
+
+  /// Snapshots taken after `since`, oldest first, preceded by the last one
+  /// taken before it, if any, to measure against.
+  pub(crate) fn snapshots_since(
+    &self,
+    since: DateTime<Utc>,
+  ) -> Result<Vec<Snapshot>> {
+    Self::read_snapshots(
+      self
+        .conn
+        .prepare(
+          "SELECT date, crates, downloads, versions, categories FROM snapshots \
+           WHERE date >= COALESCE( \
+           (SELECT MAX(date) FROM snapshots WHERE date <= :since), '') \
+           ORDER BY date",
+        )?
+        .bind_by_name(
+          ":since",
+          Self::timestamp(since).as_str(),
+        )?,
+    )
+  }
+
+  fn read_snapshots(mut statement: Statement) -> Result<Vec<Snapshot>> {
+    let mut snapshots = Vec::new();
+
+    while let State::Row = statement.next()? {
+      snapshots.push(Snapshot {
+        date: statement.read::<String>(0)?.parse()?,
+        crates: statement.read::<i64>(1)?.try_into()?,
+        downloads: statement.read::<i64>(2)?.try_into()?,
+        versions: statement.read::<i64>(3)?.try_into()?,
+        categories: serde_json::from_str(&statement.read::<String>(4)?)?,
+      });
+    }
+
+    Ok(snapshots)
+  }
+
+  pub(crate) fn state(&self, key: &str) -> Result<Option<String>> {
+    let mut statement = self
+      .conn
+      .prepare("SELECT value FROM state WHERE key = :key")?
+      .bind_by_name(":key", key)?;
+
+    if let State::Row = statement.next()? {
+      return Ok(Some(statement.read::<String>(0)?));
+    }
+
+    Ok(None)
+  }
+
+  pub(crate) fn set_state(&self, key: &str, value: &str) -> Result {
+    self
+      .conn
+      .prepare(
+        "INSERT OR REPLACE INTO state (key, value) VALUES (:key, :value)",
+      )?
+      .bind_by_name(":key", key)?
+      .bind_by_name(":value", value)?
+      .next()?;
+
+    Ok(())
+  }
+
+  /// Where the last sync of `source`, or of every crate without one, left
+  /// off.
+  pub(crate) fn sync_cursor(
+    &self,
+    source: Option<&str>,
+  ) -> Result<Option<SyncCursor>> {
+    let mut statement = self
+      .conn
+      .prepare(
+        "SELECT page, seek, sort FROM sync_state WHERE source = :source",
+      )?
+      .bind_by_name(":source", source.unwrap_or_default())?;
+
+    if let State::Row = statement.next()? {
+      return Ok(Some(SyncCursor {
+        page: statement.read::<i64>(0)?.try_into()?,
+        seek: statement.read::<Option<String>>(1)?,
+        sort: statement.read::<String>(2)?,
+      }));
+    }
+
+    Ok(None)
+  }
+
+  pub(crate) fn set_sync_cursor(
+    &self,
+    source: Option<&str>,
+    cursor: &SyncCursor,
+  ) -> Result {
+    self
+      .conn
+      .prepare(
+        "INSERT OR REPLACE INTO sync_state (source, page, seek, sort, date) \
+         VALUES (:source, :page, :seek, :sort, :date)",
+      )?
+      .bind_by_name(":source", source.unwrap_or_default())?
+      .bind_by_name(":page", i64::try_from(cursor.page)?)?
+      .bind_by_name(":seek", cursor.seek.as_deref())?
+      .bind_by_name(":sort", cursor.sort.as_str())?
+      .bind_by_name(":date", Self::timestamp(Utc::now()).as_str())?
+      .next()?;
+
+    Ok(())
+  }
+
+  /// Whether `name` was featured within the last 30 days.
+  pub(crate) fn visited(&self, name: &str) -> Result<bool> {
+    Ok(matches!(
+      self
+        .conn
+        .prepare("SELECT * FROM crates WHERE name = :name AND visited = 1")?
+        .bind_by_name(":name", name)?
+        .next()?,
+      State::Row
+    ))
+  }
+
+  /// Marks `name` as visited, inserting it if it was never synced, which
+  /// happens when an operator posts a crate directly.
+  pub(crate) fn update(&self, name: &str) -> Result {
+    self.transaction(|| {
+      let known = matches!(
+        self
+          .conn
+          .prepare("SELECT * FROM crates WHERE name = :name")?
+          .bind_by_name(":name", name)?
+          .next()?,
+        State::Row
+      );
+
+      self
+        .conn
+        .prepare(if known {
+          "UPDATE crates SET visited = 1, date = :date WHERE name = :name"
+        } else {
+          "INSERT INTO crates (name, visited, date) VALUES (:name, 1, :date)"
+        })?
+        .bind_by_name(":name", name)?
+        .bind_by_name(":date", Self::timestamp(Utc::now()).as_str())?
+        .next()?;
+
+      self.dequeue(name)
+    })
+  }
+
+  pub(crate) fn scan<T: TimeZone>(&self, date: DateTime<T>) -> Result {
+    log::info!(
+      "Scanning db for visited crates that have passed date {:?}",
+      date
+    );
+
+    self.transaction(|| {
+      let mut statement = self
+        .conn
+        .prepare("SELECT * FROM crates WHERE visited = 1")?;
+
+      while let State::Row = statement.next()? {
+        if statement
+          .read::<String>(2)?
+          .parse::<DateTime<Utc>>()?
+          .signed_duration_since(date.clone())
+          .num_days()
+          >= 30
+        {
+          let name = statement.read::<String>(0)?;
+
+          log::info!(
+            "Found visited crate {} that has surpassed {:?}",
+            name,
+            date
+          );
+
+          self
+            .conn
+            .prepare(
+              "UPDATE crates SET visited = 0, date = :date WHERE name = :name",
+            )?
+            .bind_by_name(":date", Self::timestamp(Utc::now()).as_str())?
+            .bind_by_name(":name", name.as_str())?
+            .next()?;
+        }
+      }
+
+      Ok(())
+    })
+  }
+
+  fn upsert_metadata(&self, metadata: &Metadata) -> Result {
+    self
+      .conn
+      .prepare(
+        "INSERT OR REPLACE INTO metadata \
+         (name, description, downloads, max_version, repository, created_at, updated_at, refreshed) \
+         VALUES \
+         (:name, :description, :downloads, :max_version, :repository, :created_at, :updated_at, :refreshed)",
+      )?
+      .bind_by_name(":name", metadata.name.as_str())?
+      .bind_by_name(":description", metadata.description.as_deref())?
+      .bind_by_name(":downloads", i64::try_from(metadata.downloads)?)?
+      .bind_by_name(":max_version", metadata.max_version.as_str())?
+      .bind_by_name(":repository", metadata.repository.as_deref())?
+      .bind_by_name(":created_at", Self::timestamp(metadata.created_at).as_str())?
+      .bind_by_name(":updated_at", Self::timestamp(metadata.updated_at).as_str())?
+      .bind_by_name(":refreshed", Self::timestamp(Utc::now()).as_str())?
+      .next()?;
+
+    Ok(())
+  }
+
+  fn record_yanked(&self, name: &str, yanked: Yanked) -> Result {
+    self
+      .conn
+      .prepare(
+        "INSERT OR REPLACE INTO yanked (name, latest, available, date) \
+         VALUES (:name, :latest, :available, :date)",
+      )?
+      .bind_by_name(":name", name)?
+      .bind_by_name(":latest", i64::from(yanked.latest))?
+      .bind_by_name(":available", i64::from(yanked.available))?
+      .bind_by_name(":date", Self::timestamp(Utc::now()).as_str())?
+      .next()?;
+
+    Ok(())
+  }
+
+  pub(crate) fn upsert_owner(&self, owner: &User, name: &str) -> Result {
+    self
+      .conn
+      .prepare(
+        "INSERT OR REPLACE INTO owners \
+         (login, id, name, kind, url, avatar, date) \
+         VALUES (:login, :id, :name, :kind, :url, :avatar, :date)",
+      )?
+      .bind_by_name(":login", owner.login.as_str())?
+      .bind_by_name(":id", i64::try_from(owner.id)?)?
+      .bind_by_name(":name", owner.name.as_deref())?
+      .bind_by_name(":kind", owner.kind.as_deref())?
+      .bind_by_name(":url", owner.url.as_str())?
+      .bind_by_name(":avatar", owner.avatar.as_deref())?
+      .bind_by_name(":date", Self::timestamp(Utc::now()).as_str())?
+      .next()?;
+
+    self
+      .conn
+      .prepare(
+        "INSERT OR IGNORE INTO ownership (login, name) VALUES (:login, :name)",
+      )?
+      .bind_by_name(":login", owner.login.as_str())?
+      .bind_by_name(":name", name)?
+      .next()?;
+
+    Ok(())
+  }
+
+  /// Users, not teams, owning at least `min_crates` crates seen during
+  /// enrichment who haven't been spotlighted yet.
+  pub(crate) fn prolific_owners(&self, min_crates: usize) -> Result<Vec<User>> {
+    let mut statement = self
+      .conn
+      .prepare(
+        "SELECT owners.login, id, owners.name, kind, url, avatar FROM owners \
+         JOIN ownership ON ownership.login = owners.login \
+         WHERE (kind IS NULL OR kind = 'user') \
+         AND owners.login NOT IN (SELECT login FROM spotlights) \
+         GROUP BY owners.login HAVING COUNT(*) >= :min_crates",
+      )?
+      .bind_by_name(":min_crates", i64::try_from(min_crates)?)?;
+
+    let mut owners = Vec::new();
+
+    while let State::Row = statement.next()? {
+      owners.push(User {
+        login: statement.read::<String>(0)?,
+        id: statement.read::<i64>(1)?.try_into()?,
+        name: statement.read::<Option<String>>(2)?,
+        kind: statement.read::<Option<String>>(3)?,
+        url: statement.read::<String>(4)?,
+        avatar: statement.read::<Option<String>>(5)?,
+        email: None,
+      });
+    }
+
+    Ok(owners)
+  }
+
+  pub(crate) fn record_spotlight(&self, login: &str, tweet_id: u64) -> Result {
+    self
+      .conn
+      .prepare(
+        "INSERT OR REPLACE INTO spotlights (login, tweet_id, date) \
+         VALUES (:login, :tweet_id, :date)",
+      )?
+      .bind_by_name(":login", login)?
+      .bind_by_name(":tweet_id", i64::try_from(tweet_id)?)?
+      .bind_by_name(":date", Self::timestamp(Utc::now()).as_str())?
+      .next()?;
+
+    Ok(())
+  }
+
+  pub(crate) fn index(&self, full_crate: &FullCrate) -> Result {
+    log::info!("Indexing metadata for crate {}", full_crate.name);
+
+    self.transaction(|| {
+      self.upsert_metadata(&full_crate.into())?;
+
+      self.record_yanked(&full_crate.name, full_crate.into())?;
+
+      for owner in &full_crate.owners {
+        self.upsert_owner(owner, &full_crate.name)?;
+      }
+
+      for keyword in full_crate
+        .keywords
+        .iter()
+        .map(|keyword| keyword.keyword.clone())
+        .chain(
+          full_crate
+            .categories
+            .iter()
+            .map(|category| format!("category:{}", category.slug)),
+        )
+      {
+        self
+          .conn
+          .prepare(
+            "INSERT OR IGNORE INTO keywords (name, keyword) VALUES (:name, :keyword)",
+          )?
+          .bind_by_name(":name", full_crate.name.as_str())?
+          .bind_by_name(":keyword", keyword.as_str())?
+          .next()?;
+      }
+
+      Ok(())
+    })
+  }
+
+  pub(crate) fn metadata(&self, name: &str) -> Result<Option<Metadata>> {
+    let mut statement = self
+      .conn
+      .prepare(
+        "SELECT name, description, downloads, max_version, repository, \
+         created_at, updated_at FROM metadata WHERE name = :name",
+      )?
+      .bind_by_name(":name", name)?;
+
+    if let State::Row = statement.next()? {
+      return Ok(Some(Metadata {
+        name: statement.read::<String>(0)?,
+        description: statement.read::<Option<String>>(1)?,
+        downloads: statement.read::<i64>(2)?.try_into()?,
+        max_version: statement.read::<String>(3)?,
+        repository: statement.read::<Option<String>>(4)?,
+        created_at: statement.read::<String>(5)?.parse()?,
+        updated_at: statement.read::<String>(6)?.parse()?,
+      }));
+    }
+
+    Ok(None)
+  }
+
+  /// Crates with at least `min_downloads` downloads first published on the
+  /// month and day of `date` in an earlier year, most downloaded first.
+  pub(crate) fn first_published_on(
+    &self,
+    date: NaiveDate,
+    min_downloads: u64,
+    limit: usize,
+  ) -> Result<Vec<Metadata>> {
+    let mut statement = self
+      .conn
+      .prepare(
+        "SELECT name, description, downloads, max_version, repository, \
+         created_at, updated_at FROM metadata \
+         WHERE substr(created_at, 6, 5) = :month_day \
+         AND substr(created_at, 1, 4) < :year \
+         AND downloads >= :min_downloads \
+         AND name NOT IN (SELECT name FROM optouts WHERE kind = 'crate') \
+         AND name NOT IN (SELECT name FROM gone) \
+         ORDER BY downloads DESC LIMIT :limit",
+      )?
+      .bind_by_name(":month_day", date.format("%m-%d").to_string().as_str())?
+      .bind_by_name(":year", date.format("%Y").to_string().as_str())?
+      .bind_by_name(":min_downloads", i64::try_from(min_downloads)?)?
+      .bind_by_name(":limit", i64::try_from(limit)?)?;
+
+    let mut crates = Vec::new();
+
+    while let State::Row = statement.next()? {
+      crates.push(Metadata {
+        name: statement.read::<String>(0)?,
+        description: statement.read::<Option<String>>(1)?,
+        downloads: statement.read::<i64>(2)?.try_into()?,
+        max_version: statement.read::<String>(3)?,
+        repository: statement.read::<Option<String>>(4)?,
+        created_at: statement.read::<String>(5)?.parse()?,
+        updated_at: statement.read::<String>(6)?.parse()?,
+      });
+    }
+
+    Ok(crates)
+  }
+
+  pub(crate) fn keywords(&self, name: &str) -> Result<Vec<String>> {
+    let mut statement = self
+      .conn
+      .prepare("SELECT keyword FROM keywords WHERE name = :name")?
+      .bind_by_name(":name", name)?;
+
+    let mut keywords = Vec::new();
+
+    while let State::Row = statement.next()? {
+      keywords.push(statement.read::<String>(0)?);
+    }
+
+    Ok(keywords)
+  }
+
+  /// Keywords and categories of every indexed crate.
+  pub(crate) fn all_keywords(&self) -> Result<BTreeMap<String, Vec<String>>> {
+    let mut statement =
+      self.conn.prepare("SELECT name, keyword FROM keywords")?;
+
+    let mut keywords = BTreeMap::<String, Vec<String>>::new();
+
+    while let State::Row = statement.next()? {
+      keywords
+        .entry(statement.read::<String>(0)?)
+        .or_default()
+        .push(statement.read::<String>(1)?);
+    }
+
+    Ok(keywords)
+  }
+
+  pub(crate) fn similar(
+    &self,
+    name: &str,
+    limit: usize,
+  ) -> Result<Vec<String>> {
+    let mut statement = self
+      .conn
+      .prepare(
+        "SELECT keywords.name, COUNT(*) AS shared FROM keywords \
+         LEFT JOIN metadata ON metadata.name = keywords.name \
+         WHERE keywords.keyword IN \
+         (SELECT keyword FROM keywords WHERE name = :name) \
+         AND keywords.name != :name \
+         AND keywords.name NOT IN \
+         (SELECT name FROM optouts WHERE kind = 'crate') \
+         AND keywords.name NOT IN \
+         (SELECT name FROM yanked WHERE available = 0) \
+         GROUP BY keywords.name \
+         ORDER BY shared DESC, metadata.downloads DESC \
+         LIMIT :limit",
+      )?
+      .bind_by_name(":name", name)?
+      .bind_by_name(":limit", i64::try_from(limit)?)?;
+
+    let mut similar = Vec::new();
+
+    while let State::Row = statement.next()? {
+      similar.push(statement.read::<String>(0)?);
+    }
+
+    Ok(similar)
+  }
+
+  pub(crate) fn sync(
+    &self,
+    crates: Vec<Crate>,
+    source: Option<&str>,
+  ) -> Result {
+    log::info!("Syncing db...");
+
+    self.transaction(|| {
+      let mut inserted = 0;
+
+      for krate in &crates {
+        self.upsert_metadata(&krate.into())?;
+
+        // A listed crate exists, even if it was deleted and published again.
+        self
+          .conn
+          .prepare("DELETE FROM gone WHERE name = :name")?
+          .bind_by_name(":name", krate.name.as_str())?
+          .next()?;
+
+        if let State::Done = self
+          .conn
+          .prepare("SELECT * FROM crates WHERE name = :name")?
+          .bind_by_name(":name", krate.name.as_str())?
+          .next()?
+        {
+          self
+            .conn
+            .prepare(
+              "INSERT INTO crates (name, visited, date, source) \
+               VALUES (:name, 0, :date, :source)",
+            )?
+            .bind_by_name(":name", krate.name.as_str())?
+            .bind_by_name(":date", Self::timestamp(Utc::now()).as_str())?
+            .bind_by_name(":source", source)?
+            .next()?;
+
+          inserted += 1;
+        } else if let Some(source) = source {
+          self
+            .conn
+            .prepare(
+              "UPDATE crates SET source = :source \
+               WHERE name = :name AND source IS NULL",
+            )?
+            .bind_by_name(":name", krate.name.as_str())?
+            .bind_by_name(":source", source)?
+            .next()?;
+        }
+      }
+
+      if inserted == 0 {
+        log::info!("Database up to date!");
+      } else {
+        log::info!("Inserted {inserted} crates");
+      }
+
+      Ok(())
+    })
+  }
+}
@@ -0,0 +1,187 @@
+use super::*;
+
+/// Posts a long-form thread about one well-known crate, once per week: an
+/// overview, key features and an example from its README, maintenance stats,
+/// and alternatives. Crates only get one deep dive each.
+pub(crate) async fn tick(bot: &Bot) -> Result<Vec<Published>> {
+  let week = Utc::now().with_timezone(&bot.config.timezone).iso_week();
+
+  let week = format!("{}-W{:02}", week.year(), week.week());
+
+  if bot.db.state(DEEP_DIVE)?.as_deref() == Some(week.as_str()) {
+    log::info!("Already posted a deep dive for {week}, waiting...");
+    return Ok(Vec::new());
+  }
+
+  let mut candidates = bot.db.popular(DEEP_DIVE_CANDIDATES)?;
+
+  candidates.shuffle(&mut rand::thread_rng());
+
+  for name in candidates {
+    if bot.db.deep_dived(&name)? {
+      continue;
+    }
+
+    let Some(full_crate) = bot.candidate(&name).await? else {
+      continue;
+    };
+
+    if let Some(reason) = bot.rejection(&full_crate).await? {
+      log::info!("Skipping deep dive on {name}, {reason}");
+      bot.db.audit(Action::Rejected, Some(&name), &reason)?;
+      continue;
+    }
+
+    let published = post(bot, &full_crate).await?;
+
+    bot.db.set_state(DEEP_DIVE, &week)?;
+
+    return Ok(vec![published]);
+  }
+
+  bail!("Failed to find a crate for a deep dive")
+}
+
+async fn post(bot: &Bot, full_crate: &FullCrate) -> Result<Published> {
+  let FullCrate {
+    name,
+    max_version,
+    total_downloads,
+    reverse_dependencies,
+    created_at,
+    updated_at,
+    repository,
+    ..
+  } = full_crate;
+
+  let mut fields = compose::fields(full_crate, &bot.config);
+
+  bot.attribute(&mut fields, &full_crate.owners).await?;
+
+  let mut overview = format!("Deep dive: {name} {max_version}");
+
+  if !fields["owners"].is_empty() {
+    overview.push_str(&format!(" by {}", fields["owners"]));
+  }
+
+  let url = bot.config.crate_url(name);
+
+  let mut text = format!("{overview}\n{url}");
+
+  let description = &fields["description"];
+
+  let budget = TWEET_LENGTH.saturating_sub(format::length(&text) + 1);
+
+  if !description.is_empty() && budget > 1 {
+    text = format!(
+      "{overview}\n{}\n{url}",
+      format::truncate(description, budget)
+    );
+  }
+
+  let mut thread = Vec::<(String, Option<Vec<u8>>)>::new();
+
+  if let Some(readme) = bot.readme(name, max_version).await? {
+    let mut features = String::from("Key features:");
+
+    for item in readme::list_items(&readme)
+      .iter()
+      .filter(|item| item.chars().count() >= DEEP_DIVE_FEATURE_MIN_LENGTH)
+      .take(DEEP_DIVE_FEATURES)
+    {
+      match compose::append(&features, &format!("- {item}")) {
+        Some(appended) => features = appended,
+        None => break,
+      }
+    }
+
+    if features.contains('\n') {
+      thread.push((features, None));
+    }
+
+    let heading = format!("Example from the {name} README:");
+
+    let code_blocks = readme::code_blocks(&readme);
+
+    match &bot.screenshot {
+      Some(screenshot) => {
+        if let Some(code) =
+          code_blocks.iter().find(|code| Screenshot::fits(code))
+        {
+          thread.push((heading, Some(screenshot.render(code)?)));
+        }
+      }
+      None => {
+        if let Some(example) = code_blocks
+          .iter()
+          .find_map(|code| compose::append(&heading, code))
+        {
+          thread.push((example, None));
+        }
+      }
+    }
+  }
+
+  let mut maintenance = format!(
+    "Maintenance: {} downloads and {} dependents. First published {}, last \
+     updated {}.",
+    bot.config.locale.number(*total_downloads),
+    bot.config.locale.number(reverse_dependencies.meta.total),
+    created_at.format("%Y-%m-%d"),
+    updated_at.format("%Y-%m-%d"),
+  );
+
+  if let Some(repository) = repository {
+    maintenance =
+      compose::append(&maintenance, repository).unwrap_or(maintenance);
+  }
+
+  thread.push((maintenance, None));
+
+  let alternatives = bot.db.similar(name, SEE_ALSO_LENGTH)?;
+
+  if !alternatives.is_empty() {
+    thread.push((
+      compose::fit(&format!(
+        "Alternatives to consider: {}",
+        alternatives.join(", ")
+      )),
+      None,
+    ));
+  }
+
+  let (id, entry) = bot.tweet("deep-dive", name, text.clone()).await?;
+
+  let mut replies = Vec::new();
+
+  for (reply, image) in thread {
+    let parent = replies.last().copied().unwrap_or(id);
+
+    match bot
+      .reply(name, reply, image.as_deref(), parent, entry)
+      .await?
+    {
+      Some(reply) => replies.push(reply),
+      None => break,
+    }
+  }
+
+  bot.db.record_deep_dive(name, id)?;
+
+  bot.db.audit(
+    Action::Published,
+    Some(name),
+    &format!("deep dive tweet {id}"),
+  )?;
+
+  let published = Published {
+    kind: "deep-dive",
+    name: name.clone(),
+    replies,
+    series: None,
+    text,
+    tweet_id: Some(id),
+  };
+
+  Ok(published)
+}
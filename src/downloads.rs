@@ -0,0 +1,55 @@
+use super::*;
+
+/// Daily download counts for a crate, in total and for each of its recently
+/// downloaded versions. crates.io only serves the last 90 days, and counts
+/// the downloads of older versions only towards the total.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub(crate) struct Downloads {
+  pub(crate) daily: BTreeMap<NaiveDate, u64>,
+  pub(crate) versions: BTreeMap<String, BTreeMap<NaiveDate, u64>>,
+}
+
+impl Downloads {
+  /// The downloads in `response` that fall within `range`, with versions
+  /// named by the numbers in `versions` rather than their IDs.
+  pub(crate) fn new(
+    response: CrateDownloads,
+    versions: &[Version],
+    range: &RangeInclusive<NaiveDate>,
+  ) -> Self {
+    let mut downloads = Self::default();
+
+    for entry in response.version_downloads {
+      if !range.contains(&entry.date) {
+        continue;
+      }
+
+      *downloads.daily.entry(entry.date).or_default() += entry.downloads;
+
+      let num = versions
+        .iter()
+        .find(|version| version.id == entry.version)
+        .map(|version| version.num.clone())
+        .unwrap_or_else(|| entry.version.to_string());
+
+      *downloads
+        .versions
+        .entry(num)
+        .or_default()
+        .entry(entry.date)
+        .or_default() += entry.downloads;
+    }
+
+    for entry in response.meta.extra_downloads {
+      if range.contains(&entry.date) {
+        *downloads.daily.entry(entry.date).or_default() += entry.downloads;
+      }
+    }
+
+    downloads
+  }
+
+  pub(crate) fn total(&self) -> u64 {
+    self.daily.values().sum()
+  }
+}
@@ -0,0 +1,40 @@
+use super::*;
+
+/// A post composed for a crate, with the thread of replies under it, that
+/// hasn't been published yet. Platforms posted to in another locale get the
+/// post's translation into theirs.
+#[derive(Debug)]
+pub(crate) struct Draft {
+  pub(crate) series: u64,
+  pub(crate) text: String,
+  pub(crate) thread: Vec<(String, Option<Vec<u8>>)>,
+  pub(crate) translations: Vec<(Locale, String)>,
+}
+
+impl Draft {
+  /// The post in `locale`, or as composed if it wasn't translated into it.
+  pub(crate) fn text(&self, locale: Locale) -> &str {
+    self
+      .translations
+      .iter()
+      .find(|(translation, _)| *translation == locale)
+      .map_or(&self.text, |(_, text)| text)
+  }
+
+  /// Why the draft can't be published as composed, if it can't.
+  pub(crate) fn problem(&self) -> Option<String> {
+    if self.text.trim().is_empty() {
+      return Some("post is empty".into());
+    }
+
+    [&self.text]
+      .into_iter()
+      .chain(self.translations.iter().map(|(_, text)| text))
+      .chain(self.thread.iter().map(|(reply, _)| reply))
+      .map(|text| format::length(text))
+      .find(|length| *length > TWEET_LENGTH)
+      .map(|length| {
+        format!("post is {length} characters, over the {TWEET_LENGTH} limit")
+      })
+  }
+}
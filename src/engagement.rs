@@ -0,0 +1,46 @@
+use super::*;
+
+/// Public like, quote, reply, and repost counts of a tweet, deserialized from
+/// the `public_metrics` field of the Twitter v2 API.
+#[derive(Debug, Default, Clone, Copy, Deserialize, Serialize, PartialEq)]
+pub(crate) struct Engagement {
+  #[serde(alias = "like_count")]
+  pub(crate) likes: u64,
+  #[serde(alias = "quote_count")]
+  pub(crate) quotes: u64,
+  #[serde(alias = "reply_count")]
+  pub(crate) replies: u64,
+  #[serde(alias = "retweet_count")]
+  pub(crate) reposts: u64,
+}
+
+impl Engagement {
+  pub(crate) fn total(self) -> u64 {
+    self.likes + self.quotes + self.replies + self.reposts
+  }
+}
+
+/// Refreshes engagement counts for posts published within the last
+/// `ENGAGEMENT_DAYS` days. Older posts have stopped accumulating engagement
+/// and keep their last recorded counts.
+pub(crate) async fn refresh(bot: &Bot) -> Result {
+  let cutoff = Utc::now() - chrono::Duration::days(ENGAGEMENT_DAYS);
+
+  let ids = bot
+    .db
+    .posts()?
+    .into_iter()
+    .filter(|post| post.date >= cutoff)
+    .filter_map(|post| post.tweet_id)
+    .collect::<Vec<u64>>();
+
+  for chunk in ids.chunks(ENGAGEMENT_BATCH) {
+    for (id, engagement) in bot.client.engagement(chunk).await? {
+      bot.db.record_engagement(id, engagement)?;
+    }
+  }
+
+  log::info!("Refreshed engagement for {} posts", ids.len());
+
+  Ok(())
+}
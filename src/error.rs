@@ -0,0 +1,100 @@
+use super::*;
+
+/// Errors returned by cratebot, classified by what failed so that embedders
+/// can match on the kind of failure.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+  #[error("crates.io API error: {0:#}")]
+  Api(anyhow::Error),
+  #[error("configuration error: {0:#}")]
+  Config(anyhow::Error),
+  #[error("database error: {0:#}")]
+  Db(anyhow::Error),
+  #[error("{0:#}")]
+  Other(anyhow::Error),
+  #[error("publishing error: {0:#}")]
+  Publish(anyhow::Error),
+}
+
+impl Error {
+  fn inner(&self) -> &anyhow::Error {
+    match self {
+      Self::Api(error)
+      | Self::Config(error)
+      | Self::Db(error)
+      | Self::Other(error)
+      | Self::Publish(error) => error,
+    }
+  }
+
+  pub fn is_not_found(&self) -> bool {
+    is_not_found(self.inner())
+  }
+
+  pub fn is_throttled(&self) -> bool {
+    is_throttled(self.inner())
+  }
+}
+
+/// Classifies an error by the first cause in its chain that comes from a
+/// registry request, SQLite, the Twitter client, or config parsing. Errors
+/// that are already classified keep their kind, and failed requests to
+/// anything other than the registry are left unclassified.
+impl From<anyhow::Error> for Error {
+  fn from(error: anyhow::Error) -> Self {
+    let error = match error.downcast::<Self>() {
+      Ok(error) => return error,
+      Err(error) => error,
+    };
+
+    for cause in error.chain() {
+      if cause.is::<RegistryError>() {
+        return Self::Api(error);
+      }
+
+      if cause.is::<sqlite::Error>() {
+        return Self::Db(error);
+      }
+
+      if cause.is::<egg_mode::error::Error>() {
+        return Self::Publish(error);
+      }
+
+      if cause.is::<envy::Error>() {
+        return Self::Config(error);
+      }
+    }
+
+    Self::Other(error)
+  }
+}
+
+/// The failed registry request in `error`'s chain, if any.
+fn registry(error: &anyhow::Error) -> Option<&reqwest::Error> {
+  error
+    .chain()
+    .find_map(|cause| Some(&cause.downcast_ref::<RegistryError>()?.0))
+}
+
+fn status(error: &anyhow::Error) -> Option<reqwest::StatusCode> {
+  registry(error)?.status()
+}
+
+pub(crate) fn is_not_found(error: &anyhow::Error) -> bool {
+  status(error) == Some(reqwest::StatusCode::NOT_FOUND)
+}
+
+/// Whether the registry couldn't be reached, or failed or turned the request
+/// away itself, as opposed to failing for the crate that was requested.
+pub(crate) fn is_unavailable(error: &anyhow::Error) -> bool {
+  is_throttled(error)
+    || status(error).is_some_and(|status| status.is_server_error())
+    || registry(error)
+      .is_some_and(|error| error.is_connect() || error.is_timeout())
+}
+
+/// Whether the registry responded with 429 Too Many Requests, which the
+/// retry layer backs off from.
+pub(crate) fn is_throttled(error: &anyhow::Error) -> bool {
+  status(error) == Some(reqwest::StatusCode::TOO_MANY_REQUESTS)
+}
@@ -0,0 +1,51 @@
+use super::*;
+
+/// Checks a candidate must pass before being featured, in the order they're
+/// applied. Cheap database checks come first so that the filters that hit the
+/// crates.io API only run for crates that would otherwise be picked.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Filter {
+  Optout,
+  Yanked,
+  Moderation,
+  Typosquat,
+  Script,
+  Advisories,
+  Squat,
+  Manifest,
+  Safety,
+}
+
+impl Filter {
+  pub(crate) const ALL: &'static [Self] = &[
+    Self::Optout,
+    Self::Yanked,
+    Self::Moderation,
+    Self::Typosquat,
+    Self::Script,
+    Self::Advisories,
+    Self::Squat,
+    Self::Manifest,
+    Self::Safety,
+  ];
+
+  pub(crate) fn as_str(self) -> &'static str {
+    match self {
+      Self::Optout => "optout",
+      Self::Yanked => "yanked",
+      Self::Moderation => "moderation",
+      Self::Typosquat => "typosquat",
+      Self::Script => "script",
+      Self::Advisories => "advisories",
+      Self::Squat => "squat",
+      Self::Manifest => "manifest",
+      Self::Safety => "safety",
+    }
+  }
+}
+
+impl Display for Filter {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}", self.as_str())
+  }
+}
@@ -0,0 +1,121 @@
+use super::*;
+
+/// The maximum weighted length of a post.
+pub const MAX_LENGTH: usize = TWEET_LENGTH;
+
+pub(crate) const ELLIPSIS: &str = "…";
+
+/// Twitter counts characters outside of Latin scripts and general
+/// punctuation, including CJK and emoji, as two.
+fn weight(c: char) -> usize {
+  match u32::from(c) {
+    0x0..=0x10FF | 0x2000..=0x200D | 0x2010..=0x201F | 0x2032..=0x2037 => 1,
+    _ => 2,
+  }
+}
+
+/// Lowercase hexadecimal encoding of `bytes`.
+pub(crate) fn hex(bytes: &[u8]) -> String {
+  bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// The weighted length of `text`, ignoring links.
+pub(crate) fn width(text: &str) -> usize {
+  text.chars().map(weight).sum()
+}
+
+/// The weighted length of `text` as Twitter counts it. Every link counts as
+/// a fixed-length t.co URL, regardless of how long the original is.
+pub fn length(text: &str) -> usize {
+  text
+    .split_inclusive(char::is_whitespace)
+    .map(|word| {
+      if word.starts_with("https://") {
+        URL_LENGTH + width(&word[word.trim_end().len()..])
+      } else {
+        width(word)
+      }
+    })
+    .sum()
+}
+
+/// Cuts `text` down to at most `budget` weighted characters, ending it with
+/// an ellipsis if anything was removed.
+pub(crate) fn truncate(text: &str, budget: usize) -> String {
+  if width(text) <= budget {
+    return text.to_string();
+  }
+
+  let budget = budget.saturating_sub(width(ELLIPSIS));
+
+  let mut used = 0;
+
+  let kept = text
+    .chars()
+    .take_while(|&c| {
+      used += weight(c);
+      used <= budget
+    })
+    .collect::<String>();
+
+  format!("{}{ELLIPSIS}", kept.trim_end())
+}
+
+/// Collapses whitespace, including newlines, into single spaces, and drops
+/// control characters and bidirectional overrides, which could garble the
+/// rest of the post.
+pub(crate) fn sanitize(text: &str) -> String {
+  text
+    .split_whitespace()
+    .map(|word| {
+      word
+        .chars()
+        .filter(|&c| {
+          !c.is_control()
+            && !matches!(c, '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}')
+        })
+        .collect::<String>()
+    })
+    .filter(|word| !word.is_empty())
+    .collect::<Vec<String>>()
+    .join(" ")
+}
+
+pub(crate) fn fields(
+  name: &str,
+  description: Option<&str>,
+  owners: &[&str],
+  url: String,
+) -> BTreeMap<&'static str, String> {
+  BTreeMap::from([
+    ("description", sanitize(description.unwrap_or_default())),
+    ("name", name.to_string()),
+    (
+      "owners",
+      owners
+        .iter()
+        .map(|owner| sanitize(owner))
+        .collect::<Vec<String>>()
+        .join(", "),
+    ),
+    ("url", url),
+  ])
+}
+
+/// Formats a post for a crate on crates.io with the default template. The
+/// description and owners are shortened as needed so the post never exceeds
+/// `MAX_LENGTH`.
+pub fn post(name: &str, description: Option<&str>, owners: &[&str]) -> String {
+  compose(
+    &Template::default(),
+    fields(
+      name,
+      description,
+      owners,
+      format!("{REGISTRY_URL}/crates/{name}"),
+    ),
+    &[],
+    &[],
+    Locale::default(),
+  )
+}
@@ -0,0 +1,88 @@
+use super::*;
+
+/// The public fields of a GitHub user profile that can carry a handle.
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct Profile {
+  pub(crate) bio: Option<String>,
+  pub(crate) blog: Option<String>,
+  pub(crate) twitter_username: Option<String>,
+}
+
+/// Handles an owner has published on their GitHub profile: the Twitter
+/// username field, and a fediverse address, either written out as
+/// `@user@instance` or as a profile link like `https://instance/@user`.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct Handles {
+  pub(crate) fediverse: Option<String>,
+  pub(crate) twitter: Option<String>,
+}
+
+impl Handles {
+  pub(crate) fn discover(profile: &Profile) -> Self {
+    Self {
+      fediverse: [&profile.blog, &profile.bio]
+        .into_iter()
+        .flatten()
+        .flat_map(|text| text.split_whitespace())
+        .find_map(fediverse),
+      twitter: profile
+        .twitter_username
+        .as_deref()
+        .map(|username| username.trim().trim_start_matches('@'))
+        .filter(|username| is_username(username))
+        .map(str::to_owned),
+    }
+  }
+
+  /// The GitHub login for an owner, taken from their profile URL, since
+  /// that's what crates.io links to from its user pages. Teams and owners
+  /// from registries that don't authenticate through GitHub have none.
+  pub(crate) fn github_login(owner: &User) -> Option<&str> {
+    if owner.kind.as_deref() == Some("team") {
+      return None;
+    }
+
+    let login = owner
+      .url
+      .strip_prefix("https://github.com/")?
+      .trim_end_matches('/');
+
+    (!login.is_empty() && !login.contains('/')).then_some(login)
+  }
+}
+
+fn is_username(text: &str) -> bool {
+  !text.is_empty()
+    && text
+      .chars()
+      .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.' || c == '-')
+}
+
+fn is_host(text: &str) -> bool {
+  text.contains('.')
+    && text
+      .split('.')
+      .all(|label| is_username(label) && !label.contains('_'))
+}
+
+fn fediverse(word: &str) -> Option<String> {
+  let word = word.trim_matches(|c: char| {
+    !(c.is_ascii_alphanumeric() || c == '@' || c == '_')
+  });
+
+  if let Some((user, host)) =
+    word.strip_prefix('@').and_then(|rest| rest.split_once('@'))
+  {
+    return (is_username(user) && is_host(host))
+      .then(|| format!("@{user}@{host}"));
+  }
+
+  let (host, path) = word
+    .strip_prefix("https://")
+    .or_else(|| word.strip_prefix("http://"))?
+    .split_once('/')?;
+
+  let user = path.strip_prefix('@')?.trim_end_matches('/');
+
+  (is_username(user) && is_host(host)).then(|| format!("@{user}@{host}"))
+}
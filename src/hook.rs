@@ -0,0 +1,105 @@
+use {
+  super::*,
+  std::process::Stdio,
+  tokio::{io::AsyncWriteExt, process::Command},
+};
+
+/// Points in the bot's lifecycle where operators can run their own command
+/// or webhook, set with `HOOK_ON_SELECTED`, `HOOK_ON_PUBLISHED`, and
+/// `HOOK_ON_ERROR`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Hook {
+  Error,
+  Published,
+  Selected,
+}
+
+impl Hook {
+  pub(crate) fn as_str(self) -> &'static str {
+    match self {
+      Self::Error => "error",
+      Self::Published => "published",
+      Self::Selected => "selected",
+    }
+  }
+
+  fn target(self, config: &Config) -> Option<&str> {
+    match self {
+      Self::Error => config.hook_on_error.as_deref(),
+      Self::Published => config.hook_on_published.as_deref(),
+      Self::Selected => config.hook_on_selected.as_deref(),
+    }
+  }
+
+  /// Runs the hook with `payload` and an `event` field naming the hook. An
+  /// `http://` or `https://` target gets the payload POSTed as JSON, anything
+  /// else is run with `sh -c`, with the payload on stdin. Hooks are best
+  /// effort, failures are logged and never interrupt the bot.
+  pub(crate) async fn run(
+    self,
+    config: &Config,
+    mut payload: serde_json::Value,
+  ) {
+    let Some(target) = self.target(config) else {
+      return;
+    };
+
+    payload["event"] = self.as_str().into();
+
+    let result =
+      if target.starts_with("http://") || target.starts_with("https://") {
+        Self::post(config, target, &payload).await
+      } else {
+        self.exec(target, &payload).await
+      };
+
+    if let Err(error) = result {
+      log::warn!("Failed to run {} hook: {error:#}", self.as_str());
+    }
+  }
+
+  async fn post(
+    config: &Config,
+    url: &str,
+    payload: &serde_json::Value,
+  ) -> Result {
+    reqwest::Client::builder()
+      .user_agent(config.user_agent())
+      .timeout(HOOK_TIMEOUT)
+      .build()?
+      .post(url)
+      .json(payload)
+      .send()
+      .await?
+      .error_for_status()?;
+
+    Ok(())
+  }
+
+  async fn exec(self, command: &str, payload: &serde_json::Value) -> Result {
+    let mut child = Command::new("sh")
+      .args(["-c", command])
+      .env("CRATEBOT_EVENT", self.as_str())
+      .stdin(Stdio::piped())
+      .kill_on_drop(true)
+      .spawn()?;
+
+    let mut stdin = child.stdin.take().unwrap();
+
+    stdin.write_all(&serde_json::to_vec(payload)?).await?;
+
+    drop(stdin);
+
+    let status = tokio::time::timeout(HOOK_TIMEOUT, child.wait())
+      .await
+      .map_err(|_| {
+        anyhow!("`{command}` timed out after {}s", HOOK_TIMEOUT.as_secs())
+      })??;
+
+    if !status.success() {
+      bail!("`{command}` failed with {status}");
+    }
+
+    Ok(())
+  }
+}
@@ -0,0 +1,59 @@
+use super::*;
+
+/// A range of hours of the day, written `start-end`, e.g. `9-22` for 9:00 up
+/// to but not including 22:00. Ranges where `start` is after `end` wrap past
+/// midnight, so `22-6` covers the night.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(try_from = "String")]
+pub(crate) struct Hours {
+  end: u32,
+  start: u32,
+}
+
+impl Hours {
+  pub(crate) fn contains(self, hour: u32) -> bool {
+    if self.start <= self.end {
+      self.start <= hour && hour < self.end
+    } else {
+      hour >= self.start || hour < self.end
+    }
+  }
+}
+
+impl Default for Hours {
+  fn default() -> Self {
+    Self { end: 24, start: 0 }
+  }
+}
+
+impl Display for Hours {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}-{}", self.start, self.end)
+  }
+}
+
+impl FromStr for Hours {
+  type Err = anyhow::Error;
+
+  fn from_str(s: &str) -> Result<Self> {
+    let (start, end) = s
+      .split_once('-')
+      .ok_or_else(|| anyhow!("Expected hours in the form `start-end`: {s}"))?;
+
+    let (start, end) = (start.trim().parse()?, end.trim().parse()?);
+
+    if start > 24 || end > 24 {
+      bail!("Hours must be between 0 and 24: {s}");
+    }
+
+    Ok(Self { end, start })
+  }
+}
+
+impl TryFrom<String> for Hours {
+  type Error = anyhow::Error;
+
+  fn try_from(s: String) -> Result<Self> {
+    s.parse()
+  }
+}
@@ -0,0 +1,84 @@
+use super::*;
+
+/// A version of a crate, as listed in a Cargo sparse registry index.
+#[derive(Debug, Deserialize)]
+pub(crate) struct IndexEntry {
+  deps: Vec<IndexDependency>,
+  pub(crate) vers: String,
+  pub(crate) yanked: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct IndexDependency {
+  #[serde(default = "IndexDependency::default_default_features")]
+  default_features: bool,
+  #[serde(default)]
+  features: Vec<String>,
+  #[serde(default)]
+  kind: Option<String>,
+  name: String,
+  #[serde(default)]
+  optional: bool,
+  #[serde(default)]
+  package: Option<String>,
+  req: String,
+  #[serde(default)]
+  target: Option<String>,
+}
+
+impl IndexDependency {
+  fn default_default_features() -> bool {
+    true
+  }
+}
+
+impl IndexEntry {
+  /// Path of the index file listing crate `name`, relative to the index root.
+  pub(crate) fn path(name: &str) -> String {
+    let name = name.to_lowercase();
+
+    let prefix = |range: std::ops::Range<usize>| {
+      name
+        .chars()
+        .skip(range.start)
+        .take(range.len())
+        .collect::<String>()
+    };
+
+    match name.chars().count() {
+      1 => format!("1/{name}"),
+      2 => format!("2/{name}"),
+      3 => format!("3/{}/{name}", prefix(0..1)),
+      _ => format!("{}/{}/{name}", prefix(0..2), prefix(2..4)),
+    }
+  }
+
+  /// Parses an index file, which has one JSON object per line.
+  pub(crate) fn parse(text: &str) -> Result<Vec<Self>> {
+    text
+      .lines()
+      .filter(|line| !line.trim().is_empty())
+      .map(|line| Ok(serde_json::from_str(line)?))
+      .collect()
+  }
+
+  /// This version's dependencies, in the crates.io API's format.
+  pub(crate) fn dependencies(self, version_id: u64) -> Vec<Dependency> {
+    self
+      .deps
+      .into_iter()
+      .map(|dependency| Dependency {
+        crate_id: dependency.package.unwrap_or(dependency.name),
+        default_features: dependency.default_features,
+        downloads: 0,
+        features: dependency.features,
+        id: 0,
+        kind: dependency.kind.unwrap_or_else(|| "normal".into()),
+        optional: dependency.optional,
+        req: dependency.req,
+        target: dependency.target,
+        version_id,
+      })
+      .collect()
+  }
+}
@@ -0,0 +1,261 @@
+use {
+  crate::{
+    action::Action,
+    advisory::Advisory,
+    api::{Api, RegistryError},
+    arguments::Arguments,
+    audit::Audit,
+    budget::{Budget, Exhausted},
+    client::Client,
+    command::Command,
+    compose::compose,
+    db::Db,
+    downloads::Downloads,
+    draft::Draft,
+    engagement::Engagement,
+    error::{is_not_found, is_throttled, is_unavailable},
+    filter::Filter,
+    handle::Handles,
+    hook::Hook,
+    hours::Hours,
+    index::IndexEntry,
+    linkedin::LinkedIn,
+    locale::Locale,
+    log_file::LogFile,
+    manifest::Manifest,
+    metadata::Metadata,
+    metrics::Metrics,
+    mode::Mode,
+    moderation::Moderation,
+    nostr::Nostr,
+    optout_kind::OptoutKind,
+    overrides::Override,
+    pid_file::PidFile,
+    post::Post,
+    publisher::Publisher,
+    quota::Quota,
+    ranking::Ranking,
+    rate_limiter::RateLimiter,
+    readme::Excerpt,
+    rejection::Rejection,
+    reload::Reload,
+    review_status::ReviewStatus,
+    schedule::Schedule,
+    screenshot::Screenshot,
+    script::Script,
+    see_also::SeeAlso,
+    selection::Selection,
+    snapshot::Snapshot,
+    squat::Squat,
+    state_store::StateStore,
+    subcommand::Subcommand,
+    summary::Summary,
+    sync_cursor::SyncCursor,
+    systemd::Systemd,
+    template::Template,
+    template_filter::TemplateFilter,
+    typosquat::Typosquat,
+    weights::Weights,
+    yanked::Yanked,
+  },
+  anyhow::{anyhow, bail},
+  chrono::{
+    offset::TimeZone, DateTime, Datelike, Months, NaiveDate, SecondsFormat,
+    Timelike, Utc, Weekday,
+  },
+  chrono_tz::Tz,
+  clap::{Parser, ValueEnum},
+  crates_io_api::{
+    Category, Crate, CrateDownloads, CrateDownloadsMeta, CrateResponse,
+    CratesPage, Dependencies, Dependency, FullCrate, FullVersion, Meta, Owners,
+    ReverseDependencies, ReverseDependency, User, Version,
+  },
+  dotenv::dotenv,
+  egg_mode::{
+    media::{self, media_types},
+    raw::{self, Headers, ParamList},
+    tweet::{DraftTweet, Tweet},
+    KeyPair, RateLimit, Token,
+  },
+  rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng},
+  serde::{de::DeserializeOwned, Deserialize, Serialize},
+  sqlite::{Connection, State, Statement},
+  std::{
+    collections::{BTreeMap, BTreeSet},
+    env,
+    fmt::{self, Display, Formatter},
+    fs,
+    future::Future,
+    io, iter, mem,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    process,
+    str::FromStr,
+    sync::{
+      atomic::{AtomicU64, Ordering},
+      Mutex,
+    },
+    time::Duration,
+    time::Instant,
+  },
+};
+
+mod action;
+mod advisory;
+mod advisory_db;
+mod api;
+mod arguments;
+mod audit;
+mod bio;
+mod bot;
+mod budget;
+mod client;
+mod command;
+mod comparison;
+mod compose;
+mod config;
+mod cvss;
+mod daily;
+mod db;
+mod deep_dive;
+mod downloads;
+mod draft;
+mod engagement;
+mod error;
+mod filter;
+pub mod format;
+mod handle;
+mod hook;
+mod hours;
+mod index;
+mod linkedin;
+mod locale;
+mod log_file;
+mod manifest;
+mod mentions;
+mod metadata;
+mod metrics;
+mod milestone;
+mod mode;
+mod moderation;
+mod nostr;
+mod on_this_day;
+mod optout_kind;
+mod overrides;
+mod pid_file;
+mod poll;
+mod post;
+mod publisher;
+mod quota;
+mod ranking;
+mod rate_limiter;
+mod readme;
+mod rejection;
+mod reload;
+mod report;
+mod review_status;
+mod safety;
+mod schedule;
+mod screenshot;
+mod script;
+mod see_also;
+mod selection;
+mod site;
+mod snapshot;
+mod spotlight;
+mod squat;
+mod state_store;
+mod subcommand;
+mod summary;
+mod sync_cursor;
+mod systemd;
+mod template;
+mod template_filter;
+#[cfg(feature = "test-support")]
+pub mod testing;
+mod typosquat;
+mod weekly;
+mod weights;
+mod yanked;
+
+const ADVISORIES_SYNCED: &str = "advisories_synced";
+const ADVISORY_DB_URL: &str =
+  "https://github.com/rustsec/advisory-db/archive/refs/heads/main.tar.gz";
+const AGENT: &str = "cratebot";
+const API_RETRIES: u32 = 5;
+const BIO: &str = "bio";
+const BIO_LENGTH: usize = 160;
+const COMPARISON_CANDIDATES: usize = 6;
+const COMPARISON_SIZE: usize = 3;
+const CONFIG_PATH: &str = "cratebot.toml";
+const DAILY: &str = "daily";
+const DAILY_SHORTLIST: usize = 3;
+const DEEP_DIVE: &str = "deep_dive";
+const DEEP_DIVE_CANDIDATES: usize = 100;
+const DEEP_DIVE_FEATURES: usize = 4;
+const DEEP_DIVE_FEATURE_MIN_LENGTH: usize = 10;
+const DB_BUSY_TIMEOUT: Duration = Duration::from_secs(30);
+const DB_PATH: &str = "db.sqlite";
+const ENGAGEMENT_BATCH: usize = 100;
+const ENGAGEMENT_DAYS: i64 = 30;
+const GITHUB_URL: &str = "https://api.github.com";
+const HOOK_TIMEOUT: Duration = Duration::from_secs(30);
+const HANDLE_TTL: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+const IDLE_INTERVAL: Duration = Duration::from_secs(60);
+const DEFAULT_TEMPLATE: &str = "[({owners}) / ]{name}[: {description}]\n{url}";
+const LAST_RUN: &str = "last_run";
+const LINKEDIN_API_URL: &str = "https://api.linkedin.com";
+const LINKEDIN_AUTH_URL: &str = "https://www.linkedin.com";
+const LOG_KEEP: usize = 5;
+const LOG_MAX_SIZE: u64 = 10 * 1024 * 1024;
+const LOG_PATH: &str = "cratebot.log";
+const MENTIONS_PAGE_SIZE: i32 = 50;
+const MENTIONS_SINCE_ID: &str = "mentions_since_id";
+const NEXT_RUN: &str = "next_run";
+const NOSTR_RETRIES: u32 = 3;
+const NOSTR_RETRY_DELAY: Duration = Duration::from_secs(1);
+const NOSTR_TIMEOUT: Duration = Duration::from_secs(10);
+const ON_THIS_DAY: &str = "on_this_day";
+const ON_THIS_DAY_LENGTH: usize = 3;
+const ON_THIS_DAY_MIN_DOWNLOADS: u64 = 100_000;
+const OPTOUT_KEYWORD: &str = "no-cratebot";
+const PAGE_SIZE: u64 = 100;
+const PUBLISH_CONCURRENCY: usize = 4;
+const PUBLISH_RETRY_ATTEMPTS: u64 = 5;
+const PUBLISH_RETRY_WINDOW: Duration = Duration::from_secs(24 * 60 * 60);
+const QUOTA: &str = "quota";
+const REGISTRY_URL: &str = "https://crates.io";
+const QUOTA_MAX_DELAY: Duration = Duration::from_secs(15 * 60);
+const PID_PATH: &str = "cratebot.pid";
+const POPULAR_CRATES: usize = 1000;
+const POLL_OPTIONS: usize = 4;
+const POLL_OPTION_LENGTH: usize = 25;
+const SCRIPT_MAX_OPERATIONS: u64 = 1_000_000;
+const SCRIPT_SHORTLIST: usize = 10;
+const SEE_ALSO_LENGTH: usize = 3;
+const SERIES: &str = "series";
+const SPOTLIGHT_LENGTH: usize = 3;
+const SPOTLIGHT_MIN_CRATES: usize = 5;
+const SPOTLIGHT_TEMPLATE: &str = "{name}[: {description}]\n{url}";
+const SYNC_SORT: &str = "alpha";
+const TICK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const TWEET_LENGTH: usize = 280;
+const TWITTER: &str = "twitter";
+const TWITTER_URL: &str = "https://api.twitter.com";
+const URL_LENGTH: usize = 23;
+const WEEKLY: &str = "weekly";
+const WEEKLY_MIN_HISTORY: Duration = Duration::from_secs(6 * 24 * 60 * 60);
+
+pub use {
+  bot::Bot,
+  config::Config,
+  error::Error,
+  report::{Published, Report, Synced},
+};
+
+type Result<T = (), E = anyhow::Error> = std::result::Result<T, E>;
+
+/// Parses command line arguments and runs the selected subcommand.
+pub async fn run() -> Result<(), Error> {
+  Ok(Arguments::parse().run().await?)
+}
@@ -0,0 +1,191 @@
+use {
+  super::*,
+  publisher::{Delete, Publish},
+};
+
+/// Publishes posts to a LinkedIn organization page through the UGC post API.
+/// Access tokens are short lived, so one is fetched with the configured
+/// OAuth2 refresh token when the bot first posts, and again when it expires
+/// or is revoked.
+pub(crate) struct LinkedIn {
+  api_url: String,
+  auth_url: String,
+  client_id: String,
+  client_secret: String,
+  http: reqwest::Client,
+  locale: Locale,
+  organization: String,
+  refresh_token: String,
+  token: Mutex<Option<(String, Instant)>>,
+}
+
+impl LinkedIn {
+  pub(crate) fn new(config: &Config, refresh_token: &str) -> Result<Self> {
+    let setting = |value: &Option<String>, name: &str| {
+      value.clone().ok_or_else(|| {
+        anyhow!("LINKEDIN_REFRESH_TOKEN is set, but {name} is not")
+      })
+    };
+
+    Ok(Self {
+      api_url: config.linkedin_api_url.trim_end_matches('/').into(),
+      auth_url: config.linkedin_auth_url.trim_end_matches('/').into(),
+      client_id: setting(&config.linkedin_client_id, "LINKEDIN_CLIENT_ID")?,
+      client_secret: setting(
+        &config.linkedin_client_secret,
+        "LINKEDIN_CLIENT_SECRET",
+      )?,
+      http: reqwest::Client::builder()
+        .user_agent(config.user_agent())
+        .build()?,
+      locale: config.linkedin_locale.unwrap_or(config.locale),
+      organization: setting(
+        &config.linkedin_organization,
+        "LINKEDIN_ORGANIZATION",
+      )?,
+      refresh_token: refresh_token.into(),
+      token: Mutex::new(None),
+    })
+  }
+
+  async fn access_token(&self) -> Result<String> {
+    if let Some((token, expires)) = &*self.token.lock().unwrap() {
+      if Instant::now() < *expires {
+        return Ok(token.clone());
+      }
+    }
+
+    self.refresh().await
+  }
+
+  async fn refresh(&self) -> Result<String> {
+    #[derive(Deserialize)]
+    struct Token {
+      access_token: String,
+      expires_in: u64,
+    }
+
+    log::info!("Refreshing LinkedIn access token...");
+
+    let token = self
+      .http
+      .post(format!("{}/oauth/v2/accessToken", self.auth_url))
+      .form(&[
+        ("grant_type", "refresh_token"),
+        ("refresh_token", &self.refresh_token),
+        ("client_id", &self.client_id),
+        ("client_secret", &self.client_secret),
+      ])
+      .send()
+      .await?
+      .error_for_status()?
+      .json::<Token>()
+      .await?;
+
+    // Expire the token a minute early, so it can't expire mid-request.
+    let expires =
+      Instant::now() + Duration::from_secs(token.expires_in.saturating_sub(60));
+
+    *self.token.lock().unwrap() = Some((token.access_token.clone(), expires));
+
+    Ok(token.access_token)
+  }
+
+  async fn post(&self, token: &str, text: &str) -> Result<reqwest::Response> {
+    Ok(
+      self
+        .http
+        .post(format!("{}/v2/ugcPosts", self.api_url))
+        .bearer_auth(token)
+        .header("X-Restli-Protocol-Version", "2.0.0")
+        .json(&serde_json::json!({
+          "author": format!("urn:li:organization:{}", self.organization),
+          "lifecycleState": "PUBLISHED",
+          "specificContent": {
+            "com.linkedin.ugc.ShareContent": {
+              "shareCommentary": { "text": text },
+              "shareMediaCategory": "NONE",
+            },
+          },
+          "visibility": {
+            "com.linkedin.ugc.MemberNetworkVisibility": "PUBLIC",
+          },
+        }))
+        .send()
+        .await?,
+    )
+  }
+
+  async fn remove(&self, token: &str, id: &str) -> Result<reqwest::Response> {
+    Ok(
+      self
+        .http
+        // Post URNs go in the path URL-encoded, and only their colons need
+        // it.
+        .delete(format!(
+          "{}/v2/ugcPosts/{}",
+          self.api_url,
+          id.replace(':', "%3A")
+        ))
+        .bearer_auth(token)
+        .header("X-Restli-Protocol-Version", "2.0.0")
+        .send()
+        .await?,
+    )
+  }
+}
+
+impl Publisher for LinkedIn {
+  fn name(&self) -> &'static str {
+    "linkedin"
+  }
+
+  fn locale(&self) -> Locale {
+    self.locale
+  }
+
+  fn publish<'a>(&'a self, text: &'a str) -> Publish<'a> {
+    Box::pin(async move {
+      let mut response = self.post(&self.access_token().await?, text).await?;
+
+      // Tokens can be revoked before they expire, so an unauthorized post is
+      // retried once with a fresh one.
+      if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+        response = self.post(&self.refresh().await?, text).await?;
+      }
+
+      let response = response.error_for_status()?;
+
+      // The ID of the new post is in a header, and in the body of newer API
+      // versions.
+      if let Some(id) = response
+        .headers()
+        .get("x-restli-id")
+        .and_then(|id| id.to_str().ok())
+      {
+        return Ok(id.to_owned());
+      }
+
+      #[derive(Deserialize)]
+      struct Created {
+        id: String,
+      }
+
+      Ok(response.json::<Created>().await?.id)
+    })
+  }
+
+  fn delete<'a>(&'a self, id: &'a str) -> Delete<'a> {
+    Box::pin(async move {
+      let mut response = self.remove(&self.access_token().await?, id).await?;
+
+      if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+        response = self.remove(&self.refresh().await?, id).await?;
+      }
+
+      response.error_for_status()?;
+
+      Ok(())
+    })
+  }
+}
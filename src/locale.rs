@@ -0,0 +1,194 @@
+use super::*;
+
+/// Language of posts. Each locale has its own default template and number
+/// formatting, so a community bot only needs to set `LOCALE`, and platforms
+/// with their own audience can be posted to in another.
+#[derive(Debug, Default, Clone, Copy, Deserialize, PartialEq, Eq)]
+pub(crate) enum Locale {
+  #[serde(rename = "de")]
+  De,
+  #[default]
+  #[serde(rename = "en")]
+  En,
+  #[serde(rename = "ja")]
+  Ja,
+  #[serde(rename = "pt-BR")]
+  PtBr,
+}
+
+impl Locale {
+  /// The BCP 47 language tag, as in `LOCALE`.
+  pub(crate) fn tag(self) -> &'static str {
+    match self {
+      Self::De => "de",
+      Self::En => "en",
+      Self::Ja => "ja",
+      Self::PtBr => "pt-BR",
+    }
+  }
+
+  pub(crate) fn template(self) -> Template {
+    match self {
+      Self::De => "{name}[ von {owners}][: {description}]\n{url}",
+      Self::En => DEFAULT_TEMPLATE,
+      Self::Ja => "{name}[（作者: {owners}）][: {description}]\n{url}",
+      Self::PtBr => "{name}[ por {owners}][: {description}]\n{url}",
+    }
+    .parse()
+    .unwrap()
+  }
+
+  /// Formats `n` with the locale's digit grouping separator.
+  pub(crate) fn number(self, n: u64) -> String {
+    let separator = match self {
+      Self::De | Self::PtBr => '.',
+      Self::En | Self::Ja => ',',
+    };
+
+    let digits = n.to_string();
+
+    let mut formatted = String::new();
+
+    for (i, digit) in digits.chars().enumerate() {
+      if i > 0 && (digits.len() - i).is_multiple_of(3) {
+        formatted.push(separator);
+      }
+      formatted.push(digit);
+    }
+
+    formatted
+  }
+
+  /// Formats `date` the way it's written in prose, like `12 March 2024`.
+  pub(crate) fn date(self, date: NaiveDate) -> String {
+    let months = match self {
+      Self::De => [
+        "Januar",
+        "Februar",
+        "März",
+        "April",
+        "Mai",
+        "Juni",
+        "Juli",
+        "August",
+        "September",
+        "Oktober",
+        "November",
+        "Dezember",
+      ],
+      Self::En => [
+        "January",
+        "February",
+        "March",
+        "April",
+        "May",
+        "June",
+        "July",
+        "August",
+        "September",
+        "October",
+        "November",
+        "December",
+      ],
+      Self::Ja => {
+        return format!("{}年{}月{}日", date.year(), date.month(), date.day())
+      }
+      Self::PtBr => [
+        "janeiro",
+        "fevereiro",
+        "março",
+        "abril",
+        "maio",
+        "junho",
+        "julho",
+        "agosto",
+        "setembro",
+        "outubro",
+        "novembro",
+        "dezembro",
+      ],
+    };
+
+    let month = months[date.month0() as usize];
+
+    match self {
+      Self::De => format!("{}. {month} {}", date.day(), date.year()),
+      Self::PtBr => format!("{} de {month} de {}", date.day(), date.year()),
+      Self::En | Self::Ja => format!("{} {month} {}", date.day(), date.year()),
+    }
+  }
+
+  /// How long ago something `days` before today was, like `3 weeks ago`.
+  pub(crate) fn ago(self, days: i64) -> String {
+    #[derive(Clone, Copy)]
+    enum Unit {
+      Day,
+      Week,
+      Month,
+      Year,
+    }
+
+    let (count, unit) = match days {
+      i64::MIN..=0 => {
+        return match self {
+          Self::De => "heute",
+          Self::En => "today",
+          Self::Ja => "今日",
+          Self::PtBr => "hoje",
+        }
+        .into()
+      }
+      1 => {
+        return match self {
+          Self::De => "gestern",
+          Self::En => "yesterday",
+          Self::Ja => "昨日",
+          Self::PtBr => "ontem",
+        }
+        .into()
+      }
+      2..=13 => (days, Unit::Day),
+      14..=59 => (days / 7, Unit::Week),
+      60..=729 => (days / 30, Unit::Month),
+      _ => (days / 365, Unit::Year),
+    };
+
+    // Counts are at least two, so units are always plural.
+    match (self, unit) {
+      (Self::De, Unit::Day) => format!("vor {count} Tagen"),
+      (Self::De, Unit::Week) => format!("vor {count} Wochen"),
+      (Self::De, Unit::Month) => format!("vor {count} Monaten"),
+      (Self::De, Unit::Year) => format!("vor {count} Jahren"),
+      (Self::En, Unit::Day) => format!("{count} days ago"),
+      (Self::En, Unit::Week) => format!("{count} weeks ago"),
+      (Self::En, Unit::Month) => format!("{count} months ago"),
+      (Self::En, Unit::Year) => format!("{count} years ago"),
+      (Self::Ja, Unit::Day) => format!("{count}日前"),
+      (Self::Ja, Unit::Week) => format!("{count}週間前"),
+      (Self::Ja, Unit::Month) => format!("{count}か月前"),
+      (Self::Ja, Unit::Year) => format!("{count}年前"),
+      (Self::PtBr, Unit::Day) => format!("há {count} dias"),
+      (Self::PtBr, Unit::Week) => format!("há {count} semanas"),
+      (Self::PtBr, Unit::Month) => format!("há {count} meses"),
+      (Self::PtBr, Unit::Year) => format!("há {count} anos"),
+    }
+  }
+
+  pub(crate) fn not_to_be_confused_with(self, name: &str) -> String {
+    match self {
+      Self::De => format!("Nicht zu verwechseln mit {name}"),
+      Self::En => format!("Not to be confused with {name}"),
+      Self::Ja => format!("{name}とは別のクレートです"),
+      Self::PtBr => format!("Não confundir com {name}"),
+    }
+  }
+
+  pub(crate) fn see_also(self) -> &'static str {
+    match self {
+      Self::De => "siehe auch",
+      Self::En => "see also",
+      Self::Ja => "関連",
+      Self::PtBr => "veja também",
+    }
+  }
+}
@@ -0,0 +1,80 @@
+use {super::*, std::io::Write};
+
+/// A log file that rotates once it grows past `max_size` bytes, renaming
+/// `cratebot.log` to `cratebot.log.1`, `cratebot.log.1` to `cratebot.log.2`,
+/// and so on, and deleting the oldest once there are `keep` rotated files.
+pub(crate) struct LogFile {
+  file: fs::File,
+  keep: usize,
+  max_size: u64,
+  path: PathBuf,
+  size: u64,
+}
+
+impl LogFile {
+  pub(crate) fn open(
+    path: PathBuf,
+    max_size: u64,
+    keep: usize,
+  ) -> Result<Self> {
+    let file = Self::append(&path)?;
+
+    Ok(Self {
+      size: file.metadata()?.len(),
+      file,
+      keep,
+      max_size,
+      path,
+    })
+  }
+
+  fn append(path: &Path) -> io::Result<fs::File> {
+    fs::OpenOptions::new().create(true).append(true).open(path)
+  }
+
+  fn rotated(&self, n: usize) -> PathBuf {
+    let mut path = self.path.clone().into_os_string();
+    path.push(format!(".{n}"));
+    path.into()
+  }
+
+  fn rotate(&mut self) -> io::Result<()> {
+    self.file.flush()?;
+
+    if self.keep == 0 {
+      fs::remove_file(&self.path)?;
+    } else {
+      for n in (1..self.keep).rev() {
+        let from = self.rotated(n);
+        if from.exists() {
+          fs::rename(from, self.rotated(n + 1))?;
+        }
+      }
+
+      fs::rename(&self.path, self.rotated(1))?;
+    }
+
+    self.file = Self::append(&self.path)?;
+    self.size = 0;
+
+    Ok(())
+  }
+}
+
+impl Write for LogFile {
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    if self.size > 0 && self.size + buf.len() as u64 > self.max_size {
+      self.rotate()?;
+    }
+
+    let written = self.file.write(buf)?;
+
+    self.size += written as u64;
+
+    Ok(written)
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    self.file.flush()
+  }
+}
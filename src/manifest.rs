@@ -0,0 +1,82 @@
+use {super::*, flate2::read::GzDecoder, std::io::Read, tar::Archive};
+
+/// The parts of a published `Cargo.toml` that cratebot cares about.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub(crate) struct Manifest {
+  pub(crate) edition: String,
+  pub(crate) rust_version: Option<String>,
+}
+
+impl Manifest {
+  /// Extract and parse `Cargo.toml` from a downloaded `.crate` archive.
+  pub(crate) fn from_crate(
+    archive: &[u8],
+    name: &str,
+    version: &str,
+  ) -> Result<Self> {
+    let path = format!("{name}-{version}/Cargo.toml");
+
+    for entry in Archive::new(GzDecoder::new(archive)).entries()? {
+      let mut entry = entry?;
+
+      if entry.path()?.to_str() != Some(path.as_str()) {
+        continue;
+      }
+
+      let mut manifest = String::new();
+
+      entry.read_to_string(&mut manifest)?;
+
+      return manifest.parse();
+    }
+
+    bail!("Crate archive for {name} {version} has no {path}")
+  }
+
+  pub(crate) fn allowed(
+    &self,
+    editions: &[String],
+    max_rust_version: Option<&str>,
+  ) -> bool {
+    if !editions.is_empty() && !editions.contains(&self.edition) {
+      return false;
+    }
+
+    match (max_rust_version, &self.rust_version) {
+      (Some(max), Some(rust_version)) => version(rust_version) <= version(max),
+      _ => true,
+    }
+  }
+}
+
+impl FromStr for Manifest {
+  type Err = anyhow::Error;
+
+  fn from_str(s: &str) -> Result<Self> {
+    let manifest = s.parse::<toml::Table>()?;
+
+    let package = manifest
+      .get("package")
+      .and_then(toml::Value::as_table)
+      .ok_or_else(|| anyhow!("Manifest has no [package] table"))?;
+
+    let field = |key: &str| {
+      package
+        .get(key)
+        .and_then(toml::Value::as_str)
+        .map(str::to_string)
+    };
+
+    Ok(Self {
+      edition: field("edition").unwrap_or_else(|| "2015".into()),
+      rust_version: field("rust-version"),
+    })
+  }
+}
+
+fn version(version: &str) -> Vec<u64> {
+  version
+    .split('.')
+    .map(|component| component.parse().unwrap_or_default())
+    .collect()
+}
@@ -0,0 +1,46 @@
+use super::*;
+
+pub(crate) async fn poll(bot: &Bot) -> Result {
+  let Bot { client, db, .. } = bot;
+
+  let since_id = db
+    .state(MENTIONS_SINCE_ID)?
+    .map(|id| id.parse::<u64>())
+    .transpose()?;
+
+  let mut mentions = client.mentions(since_id).await?;
+
+  mentions.sort_by_key(|mention| mention.id);
+
+  if since_id.is_none() {
+    if let Some(latest) = mentions.last() {
+      log::info!("Skipping mentions before {}, first poll", latest.id);
+      db.set_state(MENTIONS_SINCE_ID, &latest.id.to_string())?;
+    }
+    return Ok(());
+  }
+
+  for mention in mentions {
+    if let Some(command) = Command::parse(&mention.text) {
+      // Unanswered mentions are picked up again once the budget allows.
+      if let Some(reason) =
+        Budget::new(&bot.config).exhausted(db, Utc::now(), false)?
+      {
+        log::info!("Holding off on mention {}, {reason}", mention.id);
+        break;
+      }
+
+      log::info!("Responding to command {:?} from {}", command, mention.id);
+
+      let id = client
+        .reply(command.respond(bot).await?, mention.id)
+        .await?;
+
+      db.record_sent("mention", None, Some(id), false)?;
+    }
+
+    db.set_state(MENTIONS_SINCE_ID, &mention.id.to_string())?;
+  }
+
+  Ok(())
+}
@@ -0,0 +1,40 @@
+use super::*;
+
+#[derive(Debug, Clone)]
+pub(crate) struct Metadata {
+  pub(crate) created_at: DateTime<Utc>,
+  pub(crate) description: Option<String>,
+  pub(crate) downloads: u64,
+  pub(crate) max_version: String,
+  pub(crate) name: String,
+  pub(crate) repository: Option<String>,
+  pub(crate) updated_at: DateTime<Utc>,
+}
+
+impl From<&Crate> for Metadata {
+  fn from(krate: &Crate) -> Self {
+    Self {
+      created_at: krate.created_at,
+      description: krate.description.clone(),
+      downloads: krate.downloads,
+      max_version: krate.max_version.clone(),
+      name: krate.name.clone(),
+      repository: krate.repository.clone(),
+      updated_at: krate.updated_at,
+    }
+  }
+}
+
+impl From<&FullCrate> for Metadata {
+  fn from(full_crate: &FullCrate) -> Self {
+    Self {
+      created_at: full_crate.created_at,
+      description: full_crate.description.clone(),
+      downloads: full_crate.total_downloads,
+      max_version: full_crate.max_version.clone(),
+      name: full_crate.name.clone(),
+      repository: full_crate.repository.clone(),
+      updated_at: full_crate.updated_at,
+    }
+  }
+}
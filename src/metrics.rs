@@ -0,0 +1,33 @@
+use super::*;
+
+#[derive(Debug, Default)]
+pub(crate) struct Metrics {
+  pub(crate) cache_hits: AtomicU64,
+  pub(crate) cache_misses: AtomicU64,
+  pub(crate) requests: AtomicU64,
+  pub(crate) retries: AtomicU64,
+  pub(crate) throttled: AtomicU64,
+  pub(crate) waited_millis: AtomicU64,
+}
+
+impl Metrics {
+  pub(crate) fn increment(counter: &AtomicU64, amount: u64) {
+    counter.fetch_add(amount, Ordering::Relaxed);
+  }
+}
+
+impl Display for Metrics {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(
+      f,
+      "requests={} throttled={} retries={} waited={:.1}s cache_hits={} \
+       cache_misses={}",
+      self.requests.load(Ordering::Relaxed),
+      self.throttled.load(Ordering::Relaxed),
+      self.retries.load(Ordering::Relaxed),
+      self.waited_millis.load(Ordering::Relaxed) as f64 / 1000.0,
+      self.cache_hits.load(Ordering::Relaxed),
+      self.cache_misses.load(Ordering::Relaxed),
+    )
+  }
+}
@@ -0,0 +1,167 @@
+use super::*;
+
+const ANNIVERSARIES: &[u32] = &[5, 10];
+const ANNIVERSARY_WINDOW_DAYS: i64 = 7;
+const DOWNLOAD_MILESTONES: &[u64] = &[1_000_000, 10_000_000, 100_000_000];
+const REFRESH_BATCH: usize = 10;
+
+/// A milestone reached by a previously featured crate, celebrated with a
+/// reply to the tweet that featured it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Milestone {
+  Anniversary(u32),
+  Downloads(u64),
+}
+
+impl Milestone {
+  /// Milestones reached since `post`, given freshly refreshed `metadata`.
+  /// Download milestones need the download count recorded with the post, so
+  /// posts from before it was recorded only get anniversaries.
+  pub(crate) fn detect(
+    post: &Post,
+    metadata: &Metadata,
+    now: DateTime<Utc>,
+  ) -> Vec<Self> {
+    let mut milestones = Vec::new();
+
+    if let Some(featured) = post.downloads {
+      milestones.extend(
+        DOWNLOAD_MILESTONES
+          .iter()
+          .filter(|&&threshold| {
+            featured < threshold && metadata.downloads >= threshold
+          })
+          .map(|&threshold| Self::Downloads(threshold)),
+      );
+    }
+
+    for &years in ANNIVERSARIES {
+      let Some(anniversary) = metadata
+        .created_at
+        .checked_add_months(Months::new(12 * years))
+      else {
+        continue;
+      };
+
+      if anniversary <= now
+        && (now - anniversary).num_days() < ANNIVERSARY_WINDOW_DAYS
+      {
+        milestones.push(Self::Anniversary(years));
+      }
+    }
+
+    milestones
+  }
+
+  pub(crate) fn key(self) -> String {
+    match self {
+      Self::Anniversary(years) => format!("anniversary:{years}"),
+      Self::Downloads(threshold) => format!("downloads:{threshold}"),
+    }
+  }
+
+  pub(crate) fn text(self, metadata: &Metadata, url: &str) -> String {
+    match self {
+      Self::Anniversary(years) => format!(
+        "Happy {years} year anniversary to {}, first published on {}!\n{url}",
+        metadata.name,
+        metadata.created_at.format("%Y-%m-%d"),
+      ),
+      Self::Downloads(threshold) => format!(
+        "Congratulations to {} on passing {} downloads!\n{url}",
+        metadata.name,
+        abbreviate(threshold),
+      ),
+    }
+  }
+}
+
+fn abbreviate(n: u64) -> String {
+  match n {
+    n if n >= 1_000_000 && n % 1_000_000 == 0 => format!("{}M", n / 1_000_000),
+    n if n >= 1_000 && n % 1_000 == 0 => format!("{}K", n / 1_000),
+    n => n.to_string(),
+  }
+}
+
+/// Refreshes metadata for the least recently refreshed featured crates, then
+/// posts at most one milestone that hasn't been celebrated yet.
+pub(crate) async fn tick(bot: &Bot) -> Result<Option<Published>> {
+  for name in bot.db.stale_featured(REFRESH_BATCH)? {
+    match bot.api.get_crate(&name).await {
+      Ok(full_crate) => bot.db.index(&full_crate)?,
+      Err(error) if is_not_found(&error) => {
+        log::info!("Featured crate {name} no longer exists, marking it gone");
+        bot.db.mark_gone(&name)?;
+        bot
+          .db
+          .audit(Action::Gone, Some(&name), "not found on registry")?;
+      }
+      Err(error) => return Err(error),
+    }
+  }
+
+  let mut seen = Vec::new();
+
+  for post in bot.db.posts()? {
+    // Milestones are replies, so posts that never made it to Twitter are
+    // passed over for the crate's older posts.
+    let Some(tweet_id) = post.tweet_id else {
+      continue;
+    };
+
+    if seen.contains(&post.name) {
+      continue;
+    }
+
+    seen.push(post.name.clone());
+
+    if bot.db.gone(&post.name)? {
+      continue;
+    }
+
+    let Some(metadata) = bot.db.metadata(&post.name)? else {
+      continue;
+    };
+
+    for milestone in Milestone::detect(&post, &metadata, Utc::now()) {
+      if bot.db.celebrated(&post.name, &milestone.key())? {
+        continue;
+      }
+
+      log::info!("Crate {} reached milestone {milestone:?}", post.name);
+
+      let text =
+        milestone.text(&metadata, &bot.config.crate_url(&metadata.name));
+
+      bot.afford(true)?;
+
+      let id = bot.client.reply(text.clone(), tweet_id).await?;
+
+      bot
+        .db
+        .record_sent("milestone", Some(&post.name), Some(id), true)?;
+
+      bot.db.celebrate(&post.name, &milestone.key(), id)?;
+
+      bot.db.audit(
+        Action::Published,
+        Some(&post.name),
+        &format!("milestone {} tweet {id}", milestone.key()),
+      )?;
+
+      let published = Published {
+        kind: "milestone",
+        name: post.name,
+        replies: Vec::new(),
+        series: None,
+        text,
+        tweet_id: Some(id),
+      };
+
+      return Ok(Some(published));
+    }
+  }
+
+  Ok(None)
+}
@@ -0,0 +1,14 @@
+use super::*;
+
+#[derive(Debug, Default, Clone, Copy, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Mode {
+  Comparison,
+  Daily,
+  #[serde(rename = "deep-dive")]
+  DeepDive,
+  Poll,
+  #[default]
+  Random,
+  Spotlight,
+}
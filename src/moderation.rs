@@ -0,0 +1,77 @@
+use super::*;
+
+/// What to do with a crate whose description fails moderation. Descriptions
+/// are arbitrary user content, posted under the bot's name.
+#[derive(Debug, Default, Clone, Copy, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Moderation {
+  Off,
+  /// Post the crate without its description.
+  #[default]
+  Replace,
+  /// Don't feature the crate.
+  Reject,
+}
+
+/// Runs of the same character longer than this, like `!!!!!!`, are spam.
+const MAX_RUN: usize = 5;
+
+impl Moderation {
+  /// Why `description` fails moderation, if it does.
+  pub(crate) fn violation(
+    description: &str,
+    config: &Config,
+  ) -> Option<String> {
+    let lowercase = description.to_lowercase();
+
+    let words = lowercase
+      .split(|c: char| !c.is_alphanumeric())
+      .collect::<Vec<&str>>();
+
+    if let Some(word) = config
+      .moderation_words
+      .iter()
+      .find(|word| words.contains(&word.to_lowercase().as_str()))
+    {
+      return Some(format!("contains blocked word `{word}`"));
+    }
+
+    let urls = description
+      .split_whitespace()
+      .filter(|word| word.contains("://") || word.starts_with("www."))
+      .count();
+
+    if urls > config.moderation_max_urls {
+      return Some(format!("contains {urls} URLs"));
+    }
+
+    let emoji = description.chars().filter(|c| is_emoji(*c)).count();
+
+    if emoji > config.moderation_max_emoji {
+      return Some(format!("contains {emoji} emoji"));
+    }
+
+    let mut chars = description.chars().peekable();
+
+    while let Some(c) = chars.next() {
+      let mut run = 1;
+
+      while chars.next_if_eq(&c).is_some() {
+        run += 1;
+      }
+
+      if run > MAX_RUN && !c.is_whitespace() {
+        return Some(format!("repeats `{c}` {run} times"));
+      }
+    }
+
+    None
+  }
+}
+
+fn is_emoji(c: char) -> bool {
+  matches!(
+    u32::from(c),
+    0x1F000..=0x1FAFF | 0x2600..=0x27BF | 0x2B00..=0x2BFF
+  )
+}
@@ -0,0 +1,213 @@
+use {
+  super::*,
+  futures::{future, SinkExt, StreamExt},
+  k256::schnorr::SigningKey,
+  publisher::{Delete, Publish},
+  serde_json::{json, Value},
+  sha2::{Digest, Sha256},
+  tokio_tungstenite::tungstenite::Message,
+};
+
+/// Publishes posts as NIP-01 text notes, signed with the hex-encoded secret
+/// key in `NOSTR_KEY`, to every relay in `NOSTR_RELAYS` at once. A post
+/// counts as published when any relay accepts it. Relays that can't be
+/// reached, time out, or ask for the note to be sent again later are retried
+/// up to `NOSTR_RETRIES` times, relays that reject the note are not. Deleted
+/// posts are retracted with a NIP-09 deletion event, sent the same way.
+pub(crate) struct Nostr {
+  key: SigningKey,
+  locale: Locale,
+  relays: Vec<String>,
+}
+
+/// How a relay answered an `EVENT` message.
+enum Reply {
+  Accepted,
+  Rejected(String),
+  Retry(String),
+}
+
+impl Nostr {
+  pub(crate) fn new(
+    key: &str,
+    relays: Vec<String>,
+    locale: Locale,
+  ) -> Result<Self> {
+    if relays.is_empty() {
+      bail!("NOSTR_KEY is set, but NOSTR_RELAYS lists no relays");
+    }
+
+    let bytes = (0..key.len())
+      .step_by(2)
+      .map(|i| {
+        key
+          .get(i..i + 2)
+          .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+      })
+      .collect::<Option<Vec<u8>>>()
+      .ok_or_else(|| anyhow!("NOSTR_KEY must be a hex-encoded secret key"))?;
+
+    Ok(Self {
+      key: SigningKey::from_bytes(&bytes)
+        .map_err(|_| anyhow!("NOSTR_KEY is not a valid secret key"))?,
+      locale,
+      relays,
+    })
+  }
+
+  /// An event of `kind` with `tags` and `content`, identified by the hash of
+  /// its serialization and signed with a BIP-340 Schnorr signature.
+  fn event(&self, kind: u64, tags: Value, content: &str) -> Result<Value> {
+    let pubkey = format::hex(&self.key.verifying_key().to_bytes());
+
+    let created_at = Utc::now().timestamp();
+
+    let id = Sha256::digest(serde_json::to_string(&json!([
+      0, pubkey, created_at, kind, tags, content
+    ]))?);
+
+    let sig = self
+      .key
+      .sign_raw(&id, &rand::random())
+      .map_err(|error| anyhow!("Failed to sign note: {error}"))?;
+
+    Ok(json!({
+      "id": format::hex(&id),
+      "pubkey": pubkey,
+      "created_at": created_at,
+      "kind": kind,
+      "tags": tags,
+      "content": content,
+      "sig": format::hex(&sig.to_bytes()),
+    }))
+  }
+
+  async fn send(relay: &str, event: &Value) -> Result<Reply> {
+    let (mut socket, _) = tokio_tungstenite::connect_async(relay).await?;
+
+    socket
+      .send(Message::Text(json!(["EVENT", event]).to_string()))
+      .await?;
+
+    while let Some(message) = socket.next().await {
+      let Message::Text(text) = message? else {
+        continue;
+      };
+
+      // Relays may send notices before answering, only the `OK` for this
+      // event is an answer.
+      let Ok((kind, id, accepted, reason)) =
+        serde_json::from_str::<(String, String, bool, String)>(&text)
+      else {
+        continue;
+      };
+
+      if kind != "OK" || id != event["id"] {
+        continue;
+      }
+
+      socket.close(None).await.ok();
+
+      return Ok(if accepted {
+        Reply::Accepted
+      } else if reason.starts_with("rate-limited:")
+        || reason.starts_with("error:")
+      {
+        Reply::Retry(reason)
+      } else {
+        Reply::Rejected(reason)
+      });
+    }
+
+    bail!("relay closed the connection without answering")
+  }
+
+  async fn publish_to(relay: &str, event: &Value) -> Result {
+    let mut attempt = 0;
+
+    loop {
+      let error =
+        match tokio::time::timeout(NOSTR_TIMEOUT, Self::send(relay, event))
+          .await
+        {
+          Ok(Ok(Reply::Accepted)) => return Ok(()),
+          Ok(Ok(Reply::Rejected(reason))) => {
+            bail!("relay {relay} rejected note: {reason}")
+          }
+          Ok(Ok(Reply::Retry(reason))) => anyhow!(reason),
+          Ok(Err(error)) => error,
+          Err(_) => anyhow!("timed out after {}s", NOSTR_TIMEOUT.as_secs()),
+        };
+
+      if attempt == NOSTR_RETRIES {
+        return Err(error.context(format!("relay {relay} failed")));
+      }
+
+      attempt += 1;
+
+      let backoff = NOSTR_RETRY_DELAY * attempt;
+
+      log::warn!(
+        "Failed to publish to relay {relay}, retrying in {}s: {error:#}",
+        backoff.as_secs()
+      );
+
+      tokio::time::sleep(backoff).await;
+    }
+  }
+
+  /// Sends `event` to every relay at once, succeeding if any accepts it.
+  async fn broadcast(&self, event: &Value) -> Result {
+    let results = future::join_all(
+      self
+        .relays
+        .iter()
+        .map(|relay| Self::publish_to(relay, event)),
+    )
+    .await;
+
+    let errors = results
+      .into_iter()
+      .filter_map(Result::err)
+      .map(|error| format!("{error:#}"))
+      .collect::<Vec<String>>();
+
+    if errors.len() == self.relays.len() {
+      bail!("No relay accepted the note: {}", errors.join("; "));
+    }
+
+    for error in &errors {
+      log::warn!("Published note to some relays, but {error}");
+    }
+
+    Ok(())
+  }
+}
+
+impl Publisher for Nostr {
+  fn name(&self) -> &'static str {
+    "nostr"
+  }
+
+  fn locale(&self) -> Locale {
+    self.locale
+  }
+
+  fn publish<'a>(&'a self, text: &'a str) -> Publish<'a> {
+    Box::pin(async move {
+      let event = self.event(1, json!([]), text)?;
+
+      self.broadcast(&event).await?;
+
+      Ok(event["id"].as_str().unwrap_or_default().to_owned())
+    })
+  }
+
+  fn delete<'a>(&'a self, id: &'a str) -> Delete<'a> {
+    Box::pin(async move {
+      self
+        .broadcast(&self.event(5, json!([["e", id]]), "deleted")?)
+        .await
+    })
+  }
+}
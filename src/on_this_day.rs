@@ -0,0 +1,63 @@
+use super::*;
+
+/// Posts the most downloaded crates first published on today's date in past
+/// years once per day, alongside the regular posts of whichever mode is
+/// active.
+pub(crate) async fn tick(bot: &Bot) -> Result<Option<Published>> {
+  let today = Utc::now().with_timezone(&bot.config.timezone).date_naive();
+
+  let key = today.to_string();
+
+  if bot.db.state(ON_THIS_DAY)?.as_deref() == Some(key.as_str()) {
+    return Ok(None);
+  }
+
+  let crates = bot.db.first_published_on(
+    today,
+    ON_THIS_DAY_MIN_DOWNLOADS,
+    ON_THIS_DAY_LENGTH,
+  )?;
+
+  if crates.is_empty() {
+    log::info!("No notable crates were first published on this day");
+    bot.db.set_state(ON_THIS_DAY, &key)?;
+    return Ok(None);
+  }
+
+  let mut text = "On this day in Rust history:".to_owned();
+
+  for metadata in &crates {
+    let line = format!(
+      "{}: {} was first published, {} downloads since",
+      metadata.created_at.year(),
+      metadata.name,
+      bot.config.locale.number(metadata.downloads),
+    );
+
+    match compose::append(&text, &line) {
+      Some(appended) => text = appended,
+      None => break,
+    }
+  }
+
+  let text = compose::tag(&text, &[], &bot.config.hashtags);
+
+  let (id, _) = bot.tweet("on-this-day", &key, text.clone()).await?;
+
+  bot.db.set_state(ON_THIS_DAY, &key)?;
+
+  bot
+    .db
+    .audit(Action::Published, None, &format!("on this day tweet {id}"))?;
+
+  let published = Published {
+    kind: "on-this-day",
+    name: key,
+    replies: Vec::new(),
+    series: None,
+    text,
+    tweet_id: Some(id),
+  };
+
+  Ok(Some(published))
+}
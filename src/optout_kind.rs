@@ -0,0 +1,22 @@
+use super::*;
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub(crate) enum OptoutKind {
+  Owner,
+  Crate,
+}
+
+impl OptoutKind {
+  pub(crate) fn as_str(self) -> &'static str {
+    match self {
+      Self::Owner => "owner",
+      Self::Crate => "crate",
+    }
+  }
+}
+
+impl Display for OptoutKind {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}", self.as_str())
+  }
+}
@@ -0,0 +1,31 @@
+use super::*;
+
+/// Operator-supplied metadata for a crate, used in place of what the registry
+/// has when the crate is posted.
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct Override {
+  pub(crate) description: Option<String>,
+  pub(crate) hashtags: Option<Vec<String>>,
+  pub(crate) link: Option<String>,
+  /// Context for other operators, never posted.
+  pub(crate) note: Option<String>,
+}
+
+impl Override {
+  /// Replace the description in `fields`, and the crate's URL with the
+  /// preferred link.
+  pub(crate) fn apply(&self, fields: &mut BTreeMap<&'static str, String>) {
+    if let Some(description) = &self.description {
+      fields.insert("description", format::sanitize(description));
+    }
+
+    if let Some(link) = &self.link {
+      fields.insert("url", link.clone());
+    }
+  }
+
+  /// The hashtags to post with, these or `default`.
+  pub(crate) fn hashtags<'a>(&'a self, default: &'a [String]) -> &'a [String] {
+    self.hashtags.as_deref().unwrap_or(default)
+  }
+}
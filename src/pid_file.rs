@@ -0,0 +1,42 @@
+use {super::*, fs2::FileExt, std::io::Write};
+
+/// An exclusive lock on a PID file, held for as long as this value is alive,
+/// so that two instances can never run against the same database. Taken by
+/// every command that publishes, so a manual post can't race the daemon.
+pub(crate) struct PidFile {
+  _file: fs::File,
+}
+
+impl PidFile {
+  pub(crate) fn acquire(path: &Path) -> Result<Self> {
+    let mut file = fs::OpenOptions::new()
+      .read(true)
+      .write(true)
+      .create(true)
+      .truncate(false)
+      .open(path)?;
+
+    if file.try_lock_exclusive().is_err() {
+      bail!(
+        "Another instance holds the lock on {}{}",
+        path.display(),
+        fs::read_to_string(path)
+          .ok()
+          .map(|pid| pid.trim().to_owned())
+          .filter(|pid| !pid.is_empty())
+          .map(|pid| format!(" (PID {pid})"))
+          .unwrap_or_default()
+      );
+    }
+
+    file.set_len(0)?;
+
+    writeln!(file, "{}", process::id())?;
+
+    file.flush()?;
+
+    log::info!("Acquired lock on {}", path.display());
+
+    Ok(Self { _file: file })
+  }
+}
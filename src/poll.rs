@@ -0,0 +1,89 @@
+use super::*;
+
+pub(crate) async fn tick(bot: &Bot) -> Result<Vec<Published>> {
+  let mut published = Vec::new();
+
+  if let Some((id, candidates)) = bot.db.pending_poll()? {
+    let Some(results) = bot.client.poll_results(id).await? else {
+      log::info!("Poll {id} is still open, waiting for it to close...");
+      return Ok(published);
+    };
+
+    let winner = results
+      .into_iter()
+      .max_by_key(|(_, votes)| *votes)
+      .map(|(label, _)| label)
+      .filter(|label| candidates.contains(label));
+
+    match winner {
+      Some(winner) => {
+        log::info!("Poll {id} closed, featuring winner {winner}");
+
+        match bot.candidate(&winner).await? {
+          Some(full_crate) => {
+            bot.db.index(&full_crate)?;
+
+            match bot.rejection(&full_crate).await? {
+              Some(reason) => {
+                log::info!("Skipping poll winner {winner}, {reason}");
+                bot.db.audit(Action::Rejected, Some(&winner), &reason)?;
+              }
+              None => published.push(bot.feature(&full_crate).await?),
+            }
+          }
+          None => {
+            log::info!("Skipping poll winner {winner}, failed to fetch it")
+          }
+        }
+
+        bot.db.close_poll(id, Some(&winner))?;
+      }
+      None => {
+        log::warn!("Poll {id} closed without a known winner");
+        bot.db.audit(
+          Action::Rejected,
+          None,
+          &format!("poll {id} closed without a known winner"),
+        )?;
+        bot.db.close_poll(id, None)?;
+      }
+    }
+  }
+
+  let candidates = bot
+    .select(POLL_OPTIONS, |name| {
+      name.chars().count() <= POLL_OPTION_LENGTH
+    })
+    .await?
+    .into_iter()
+    .map(|(full_crate, _)| full_crate.name)
+    .collect::<Vec<String>>();
+
+  let text = "Which crate should we feature next?";
+
+  bot.afford(true)?;
+
+  let id = bot
+    .client
+    .poll(text.into(), &candidates, bot.config.poll_duration)
+    .await?;
+
+  let name = candidates.join(",");
+
+  bot.db.record_sent("poll", Some(&name), Some(id), true)?;
+
+  bot.db.insert_poll(id, &candidates)?;
+
+  let poll = Published {
+    kind: "poll",
+    name,
+    replies: Vec::new(),
+    series: None,
+    text: text.into(),
+    tweet_id: Some(id),
+  };
+
+  published.push(poll);
+
+  Ok(published)
+}
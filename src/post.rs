@@ -0,0 +1,29 @@
+use super::*;
+
+#[derive(Debug, Clone)]
+pub(crate) struct Post {
+  pub(crate) date: DateTime<Utc>,
+  pub(crate) deleted: Option<DateTime<Utc>>,
+  pub(crate) description: Option<String>,
+  pub(crate) downloads: Option<u64>,
+  pub(crate) engagement: Option<Engagement>,
+  pub(crate) keywords: Vec<String>,
+  pub(crate) name: String,
+  pub(crate) series: u64,
+  pub(crate) text: Option<String>,
+  pub(crate) thread: Vec<u64>,
+  pub(crate) tweet_id: Option<u64>,
+}
+
+impl Post {
+  pub(crate) fn crate_url(&self) -> String {
+    format!("https://crates.io/crates/{}", self.name)
+  }
+
+  /// The tweet, unless posting to Twitter failed.
+  pub(crate) fn tweet_url(&self) -> Option<String> {
+    self
+      .tweet_id
+      .map(|id| format!("https://twitter.com/i/web/status/{id}"))
+  }
+}
@@ -0,0 +1,129 @@
+use {
+  super::*,
+  futures::stream::{self, StreamExt},
+  std::pin::Pin,
+};
+
+pub(crate) type Publish<'a> =
+  Pin<Box<dyn Future<Output = Result<String>> + Send + 'a>>;
+
+pub(crate) type Delete<'a> = Pin<Box<dyn Future<Output = Result> + Send + 'a>>;
+
+/// A platform featured crates are cross-posted to. Twitter isn't one, since
+/// threads and engagement are tracked on it, but it's posted to alongside
+/// them, its outcome is recorded and retried the same way, and posts are
+/// deleted from all of them together.
+pub(crate) trait Publisher: Send + Sync {
+  /// Lowercase name, used in the `{name}_id` and `{name}_error` columns of
+  /// the posts table.
+  fn name(&self) -> &'static str;
+
+  /// The locale posts to the platform are written in.
+  fn locale(&self) -> Locale;
+
+  /// Publishes `text`, returning the ID of the post on the platform.
+  fn publish<'a>(&'a self, text: &'a str) -> Publish<'a>;
+
+  /// Deletes the post with ID `id` on the platform.
+  fn delete<'a>(&'a self, id: &'a str) -> Delete<'a>;
+}
+
+/// Publishes each post to its platform concurrently, at most
+/// `PUBLISH_CONCURRENCY` at a time, so a slow platform can't hold up the
+/// rest. Every platform's outcome is returned, successful or not.
+pub(crate) async fn fan_out<'a>(
+  posts: impl IntoIterator<Item = (&'a dyn Publisher, &'a str)>,
+) -> Vec<(&'static str, Result<String>)> {
+  stream::iter(posts)
+    .map(|(publisher, text)| async move {
+      (publisher.name(), publisher.publish(text).await)
+    })
+    .buffer_unordered(PUBLISH_CONCURRENCY)
+    .collect()
+    .await
+}
+
+/// Records the outcome of cross-posting post `series` on each platform.
+pub(crate) fn record(
+  bot: &Bot,
+  series: u64,
+  name: &str,
+  outcomes: &[(&'static str, Result<String>)],
+) -> Result {
+  for (platform, outcome) in outcomes {
+    bot.db.record_publication(series, platform, outcome)?;
+  }
+
+  audit(bot, &format!("post #{series}"), name, outcomes)
+}
+
+/// Audits the outcome of cross-posting `post` about `name` on each platform.
+pub(crate) fn audit(
+  bot: &Bot,
+  post: &str,
+  name: &str,
+  outcomes: &[(&'static str, Result<String>)],
+) -> Result {
+  for (platform, outcome) in outcomes {
+    match outcome {
+      Ok(id) => bot.db.audit(
+        Action::Published,
+        Some(name),
+        &format!("{platform} post {id}"),
+      )?,
+      Err(error) => {
+        log::warn!("Failed to publish {post} to {platform}: {error}");
+        bot.db.audit(
+          Action::Error,
+          Some(name),
+          &format!("{platform} post failed: {error}"),
+        )?;
+      }
+    }
+  }
+
+  Ok(())
+}
+
+/// Publishes posts from the last `PUBLISH_RETRY_WINDOW` again, only to the
+/// platforms they failed on, up to `PUBLISH_RETRY_ATTEMPTS` times. The crate
+/// stays visited, since it was posted to at least one platform. A tweet
+/// posted late goes out without its thread.
+pub(crate) async fn retry(bot: &Bot) -> Result {
+  let since = Utc::now() - chrono::Duration::from_std(PUBLISH_RETRY_WINDOW)?;
+
+  for (series, name, text) in bot.db.failed_publications(TWITTER, since)? {
+    bot.afford(false)?;
+
+    log::info!("Retrying post #{series} on {TWITTER}");
+
+    let tweet = bot.client.tweet(text).await;
+
+    // The post was counted when it first went out, the tweet only counts
+    // against the monthly tweet cap.
+    if let Ok(id) = &tweet {
+      bot.db.record_sent("retry", Some(&name), Some(*id), false)?;
+    }
+
+    record(
+      bot,
+      series,
+      &name,
+      &[(TWITTER, tweet.map(|id| id.to_string()))],
+    )?;
+  }
+
+  for publisher in &bot.publishers {
+    for (series, name, text) in
+      bot.db.failed_publications(publisher.name(), since)?
+    {
+      log::info!("Retrying post #{series} on {}", publisher.name());
+
+      let outcome = (publisher.name(), publisher.publish(&text).await);
+
+      record(bot, series, &name, &[outcome])?;
+    }
+  }
+
+  Ok(())
+}
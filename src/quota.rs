@@ -0,0 +1,49 @@
+use super::*;
+
+/// Posting quota reported by the rate limit headers of the Twitter API.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq)]
+pub(crate) struct Quota {
+  pub(crate) limit: u64,
+  pub(crate) remaining: u64,
+  pub(crate) reset: DateTime<Utc>,
+}
+
+impl Quota {
+  /// egg-mode reports missing headers as -1, which most v1.1 posting
+  /// endpoints omit.
+  pub(crate) fn from_rate_limit(rate_limit: &RateLimit) -> Option<Self> {
+    Some(Self {
+      limit: rate_limit.limit.try_into().ok()?,
+      remaining: rate_limit.remaining.try_into().ok()?,
+      reset: Utc.timestamp_opt(rate_limit.reset.into(), 0).single()?,
+    })
+  }
+
+  /// The most restrictive of the per-user and per-app 24 hour limits and the
+  /// 15 minute window limit reported by the v2 API.
+  pub(crate) fn from_headers(headers: &Headers) -> Option<Self> {
+    ["x-user-limit-24hour", "x-app-limit-24hour", "x-rate-limit"]
+      .into_iter()
+      .filter_map(|prefix| {
+        let header = |suffix| -> Option<i64> {
+          headers
+            .get(format!("{prefix}-{suffix}"))?
+            .to_str()
+            .ok()?
+            .parse()
+            .ok()
+        };
+
+        Some(Self {
+          limit: header("limit")?.try_into().ok()?,
+          remaining: header("remaining")?.try_into().ok()?,
+          reset: Utc.timestamp_opt(header("reset")?, 0).single()?,
+        })
+      })
+      .min_by_key(|quota| quota.remaining)
+  }
+
+  pub(crate) fn is_low(&self, reserve: u64, now: DateTime<Utc>) -> bool {
+    self.remaining <= reserve && self.reset > now
+  }
+}
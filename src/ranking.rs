@@ -0,0 +1,50 @@
+use super::*;
+
+/// The order candidates are tried in for selection. With engagement
+/// selection, candidates are weighted by past engagement, except for an
+/// `EXPLORATION` fraction of picks, which are uniformly random like random
+/// selection. Either way, crates queued by an operator go first, in the order
+/// they were queued.
+pub(crate) struct Ranking {
+  exploration: f64,
+  queued: Vec<String>,
+  weights: Option<(Weights, BTreeMap<String, Vec<String>>)>,
+}
+
+impl Ranking {
+  pub(crate) fn load(db: &Db, config: &Config) -> Result<Self> {
+    Ok(Self {
+      exploration: config.exploration,
+      queued: db.queued()?,
+      weights: if config.selection == Selection::Engagement {
+        Some((Weights::load(db)?, db.all_keywords()?))
+      } else {
+        None
+      },
+    })
+  }
+
+  pub(crate) fn order(
+    &self,
+    mut candidates: Vec<String>,
+    rng: &mut impl Rng,
+  ) -> Vec<String> {
+    match &self.weights {
+      Some((weights, keywords)) if rng.gen::<f64>() >= self.exploration => {
+        log::info!("Weighting candidates by past engagement");
+        candidates = weights.order(candidates, keywords, rng);
+      }
+      _ => candidates.shuffle(rng),
+    }
+
+    candidates.sort_by_key(|name| {
+      self
+        .queued
+        .iter()
+        .position(|queued| queued == name)
+        .unwrap_or(self.queued.len())
+    });
+
+    candidates
+  }
+}
@@ -0,0 +1,64 @@
+use super::*;
+
+/// Token bucket limiting requests to `rate` per second, allowing bursts of up
+/// to `burst` requests after a period of inactivity.
+pub(crate) struct RateLimiter {
+  bucket: Mutex<Bucket>,
+  burst: f64,
+  rate: f64,
+}
+
+struct Bucket {
+  tokens: f64,
+  updated: Instant,
+}
+
+impl RateLimiter {
+  pub(crate) fn new(rate: f64, burst: u32) -> Result<Self> {
+    if !rate.is_finite() || rate <= 0.0 {
+      bail!("API rate must be a positive number of requests per second");
+    }
+
+    let burst = f64::from(burst.max(1));
+
+    Ok(Self {
+      bucket: Mutex::new(Bucket {
+        tokens: burst,
+        updated: Instant::now(),
+      }),
+      burst,
+      rate,
+    })
+  }
+
+  /// Waits until a token is available and takes it, returning how long the
+  /// caller was held back.
+  pub(crate) async fn acquire(&self) -> Duration {
+    let mut waited = Duration::ZERO;
+
+    loop {
+      let wait = {
+        let mut bucket = self.bucket.lock().unwrap();
+
+        let now = Instant::now();
+
+        bucket.tokens = (bucket.tokens
+          + now.duration_since(bucket.updated).as_secs_f64() * self.rate)
+          .min(self.burst);
+
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+          bucket.tokens -= 1.0;
+          return waited;
+        }
+
+        Duration::from_secs_f64((1.0 - bucket.tokens) / self.rate)
+      };
+
+      tokio::time::sleep(wait).await;
+
+      waited += wait;
+    }
+  }
+}
@@ -0,0 +1,221 @@
+use super::*;
+
+const EXCERPT_MIN_LENGTH: usize = 40;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Excerpt {
+  Code(String),
+  Paragraph(String),
+}
+
+impl Excerpt {
+  /// Extract a short usage snippet from a rendered README, falling back to
+  /// the first paragraph with real prose in it.
+  pub(crate) fn extract(readme: &str, budget: usize) -> Option<Self> {
+    let code = code_blocks(readme)
+      .into_iter()
+      .find(|code| code.chars().count() <= budget)
+      .map(Self::Code);
+
+    code.or_else(|| {
+      elements(readme, "p")
+        .into_iter()
+        .map(|paragraph| {
+          strip_markdown(&decode(&strip_tags(&paragraph)))
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ")
+        })
+        .find(|paragraph| paragraph.chars().count() >= EXCERPT_MIN_LENGTH)
+        .map(Self::Paragraph)
+    })
+  }
+}
+
+impl Display for Excerpt {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::Code(code) => write!(f, "{code}"),
+      Self::Paragraph(paragraph) => write!(f, "{paragraph}"),
+    }
+  }
+}
+
+/// Every non-empty Rust code block in a rendered README.
+pub(crate) fn code_blocks(readme: &str) -> Vec<String> {
+  elements(readme, "pre")
+    .into_iter()
+    .filter(|block| block.contains("language-rust"))
+    .map(|block| decode(&strip_tags(&block)).trim().to_string())
+    .filter(|code| !code.is_empty())
+    .collect()
+}
+
+/// Text of every list item in a rendered README, such as a feature list.
+pub(crate) fn list_items(readme: &str) -> Vec<String> {
+  elements(readme, "li")
+    .into_iter()
+    .map(|item| {
+      strip_markdown(&decode(&strip_tags(&item)))
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+    })
+    .filter(|item| !item.is_empty())
+    .collect()
+}
+
+/// Inner HTML of every top-level `<tag>` element, in document order.
+fn elements(html: &str, tag: &str) -> Vec<String> {
+  let open = format!("<{tag}");
+  let close = format!("</{tag}>");
+
+  let mut elements = Vec::new();
+
+  let mut rest = html;
+
+  while let Some(start) = rest.find(&open) {
+    rest = &rest[start + open.len()..];
+
+    if !rest.starts_with(['>', ' ']) {
+      continue;
+    }
+
+    let Some(content) = rest.find('>') else {
+      break;
+    };
+
+    rest = &rest[content + 1..];
+
+    let Some(end) = rest.find(&close) else {
+      break;
+    };
+
+    elements.push(rest[..end].to_string());
+
+    rest = &rest[end + close.len()..];
+  }
+
+  elements
+}
+
+fn strip_tags(html: &str) -> String {
+  let mut text = String::new();
+
+  let mut in_tag = false;
+
+  for c in html.chars() {
+    match c {
+      '<' => in_tag = true,
+      '>' if in_tag => in_tag = false,
+      c if !in_tag => text.push(c),
+      _ => {}
+    }
+  }
+
+  text
+}
+
+fn decode(text: &str) -> String {
+  let mut decoded = String::new();
+
+  let mut rest = text;
+
+  while let Some(start) = rest.find('&') {
+    decoded.push_str(&rest[..start]);
+
+    rest = &rest[start..];
+
+    let Some(end) = rest.find(';').filter(|end| *end <= 10) else {
+      decoded.push('&');
+      rest = &rest[1..];
+      continue;
+    };
+
+    let entity = &rest[1..end];
+
+    let c = match entity {
+      "amp" => Some('&'),
+      "lt" => Some('<'),
+      "gt" => Some('>'),
+      "quot" => Some('"'),
+      "apos" => Some('\''),
+      "nbsp" => Some(' '),
+      _ => entity
+        .strip_prefix("#x")
+        .or_else(|| entity.strip_prefix("#X"))
+        .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+        .or_else(|| entity.strip_prefix('#').and_then(|n| n.parse().ok()))
+        .and_then(char::from_u32),
+    };
+
+    match c {
+      Some(c) => {
+        decoded.push(c);
+        rest = &rest[end + 1..];
+      }
+      None => {
+        decoded.push('&');
+        rest = &rest[1..];
+      }
+    }
+  }
+
+  decoded.push_str(rest);
+
+  decoded
+}
+
+/// Remove inline Markdown syntax left over in rendered text, keeping link
+/// text and dropping images entirely.
+fn strip_markdown(text: &str) -> String {
+  let text = text.trim_start().trim_start_matches('#').replace("__", "");
+
+  let mut stripped = String::new();
+
+  let mut chars = text.chars().peekable();
+
+  while let Some(c) = chars.next() {
+    match c {
+      '!' if chars.peek() == Some(&'[') => {
+        for c in chars.by_ref() {
+          if c == ']' {
+            break;
+          }
+        }
+
+        if chars.peek() == Some(&'(') {
+          for c in chars.by_ref() {
+            if c == ')' {
+              break;
+            }
+          }
+        }
+      }
+      '[' => {
+        let mut label = String::new();
+
+        for c in chars.by_ref() {
+          if c == ']' {
+            break;
+          }
+          label.push(c);
+        }
+
+        stripped.push_str(&label);
+
+        if chars.peek() == Some(&'(') {
+          for c in chars.by_ref() {
+            if c == ')' {
+              break;
+            }
+          }
+        }
+      }
+      '*' | '`' => {}
+      c => stripped.push(c),
+    }
+  }
+
+  stripped
+}
@@ -0,0 +1,35 @@
+use super::*;
+
+/// Why a candidate was rejected. Lookups and filters only decide this,
+/// without writing anything, so that `explain` and `simulate` can run them
+/// without marking crates gone or flagging them for review. Paths that post
+/// record the rejection.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Rejection {
+  /// Deleted from the registry, so it's marked gone and never tried again.
+  Gone,
+  /// Held back for `reason` until an operator approves it. Crates that were
+  /// never flagged for `reason` are flagged when a post rejects them.
+  Review {
+    reason: String,
+    status: Option<ReviewStatus>,
+  },
+  Skip(String),
+}
+
+impl Display for Rejection {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::Gone => write!(f, "not found on registry"),
+      Self::Review {
+        reason,
+        status: Some(status),
+      } => write!(f, "{status} review, {reason}"),
+      Self::Review {
+        reason,
+        status: None,
+      } => write!(f, "needs review, {reason}"),
+      Self::Skip(reason) => write!(f, "{reason}"),
+    }
+  }
+}
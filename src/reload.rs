@@ -0,0 +1,165 @@
+use {
+  super::*,
+  notify::{Event, RecommendedWatcher, RecursiveMode, Watcher},
+  std::sync::mpsc::{self, Receiver},
+};
+
+/// Settings read once when the bot starts, by the registry and Twitter
+/// clients and the publishers, which only take effect after a restart.
+const RESTART: &[&str] = &[
+  "access_token_key",
+  "access_token_secret",
+  "advisory_db_url",
+  "api_burst",
+  "api_rate",
+  "api_url",
+  "consumer_key",
+  "consumer_secret",
+  "contact",
+  "github_token",
+  "github_url",
+  "index_url",
+  "linkedin_api_url",
+  "linkedin_auth_url",
+  "linkedin_client_id",
+  "linkedin_client_secret",
+  "linkedin_organization",
+  "linkedin_refresh_token",
+  "nostr_key",
+  "nostr_relays",
+  "registry_token",
+  "registry_url",
+  "screenshot_font",
+  "screenshots",
+  "twitter_url",
+];
+
+/// Watches the config file, reloading the config when it changes.
+pub(crate) struct Reload {
+  events: Receiver<notify::Result<Event>>,
+  path: PathBuf,
+  settings: toml::Table,
+  _watcher: RecommendedWatcher,
+}
+
+impl Reload {
+  pub(crate) fn watch(path: &Path) -> Result<Self> {
+    let (sender, events) = mpsc::channel();
+
+    let mut watcher = notify::recommended_watcher(sender)?;
+
+    // Watch the directory rather than the file, since the file might not
+    // exist yet, and editors often save by replacing it.
+    watcher.watch(
+      path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(Path::new(".")),
+      RecursiveMode::NonRecursive,
+    )?;
+
+    log::info!("Watching {} for config changes", path.display());
+
+    Ok(Self {
+      events,
+      path: path.into(),
+      settings: Config::file(path)?,
+      _watcher: watcher,
+    })
+  }
+
+  /// The new config, if the file changed since the last call. A change that
+  /// fails to load is logged and the running config is kept.
+  pub(crate) fn poll(&mut self) -> Option<Config> {
+    let mut changed = false;
+
+    while let Ok(event) = self.events.try_recv() {
+      match event {
+        Ok(event) => {
+          changed |= event
+            .paths
+            .iter()
+            .any(|path| path.file_name() == self.path.file_name());
+        }
+        Err(error) => {
+          log::warn!("Failed watching {}: {error}", self.path.display());
+        }
+      }
+    }
+
+    if !changed {
+      return None;
+    }
+
+    match self.reload() {
+      Ok(config) => config,
+      Err(error) => {
+        log::warn!(
+          "Keeping running config, failed to reload {}: {error:#}",
+          self.path.display()
+        );
+        None
+      }
+    }
+  }
+
+  fn reload(&mut self) -> Result<Option<Config>> {
+    let mut settings = Config::file(&self.path)?;
+
+    let changes = diff(&self.settings, &settings);
+
+    if changes.is_empty() {
+      return Ok(None);
+    }
+
+    for key in RESTART {
+      match self.settings.get(*key) {
+        Some(value) => settings.insert((*key).into(), value.clone()),
+        None => settings.remove(*key),
+      };
+    }
+
+    let config = Config::with_file(&settings)?;
+
+    for (key, change) in changes {
+      if RESTART.contains(&key.as_str()) {
+        log::warn!("Changed {change}, restart to apply it");
+      } else {
+        log::info!("Reloaded {change}");
+      }
+    }
+
+    self.settings = settings;
+
+    Ok(Some(config))
+  }
+}
+
+/// The settings that differ between `old` and `new`, with a description of
+/// each change. Values of credentials are redacted.
+fn diff(old: &toml::Table, new: &toml::Table) -> Vec<(String, String)> {
+  let keys = old.keys().chain(new.keys()).collect::<BTreeSet<&String>>();
+
+  keys
+    .into_iter()
+    .filter(|key| old.get(*key) != new.get(*key))
+    .map(|key| {
+      let show = |value: Option<&toml::Value>| match value {
+        None => "unset".to_owned(),
+        Some(_)
+          if ["_key", "_secret", "_token"]
+            .iter()
+            .any(|suffix| key.ends_with(suffix)) =>
+        {
+          "<redacted>".to_owned()
+        }
+        Some(value) => value.to_string(),
+      };
+
+      (
+        key.clone(),
+        format!("{key}: {} -> {}", show(old.get(key)), show(new.get(key))),
+      )
+    })
+    .collect()
+}
@@ -0,0 +1,26 @@
+use super::*;
+
+/// What a single tick of the bot did, printed as a JSON line per tick by
+/// `cratebot --json run`.
+#[derive(Debug, Default, Serialize)]
+pub struct Report {
+  pub published: Vec<Published>,
+  pub synced: Vec<Synced>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Published {
+  pub kind: &'static str,
+  pub name: String,
+  pub replies: Vec<u64>,
+  pub series: Option<u64>,
+  pub text: String,
+  pub tweet_id: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Synced {
+  pub crates: usize,
+  pub page: u64,
+  pub source: Option<String>,
+}
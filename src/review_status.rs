@@ -0,0 +1,32 @@
+use super::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub(crate) enum ReviewStatus {
+  Approved,
+  Pending,
+  Rejected,
+}
+
+impl ReviewStatus {
+  pub(crate) fn as_str(self) -> &'static str {
+    match self {
+      Self::Approved => "approved",
+      Self::Pending => "pending",
+      Self::Rejected => "rejected",
+    }
+  }
+}
+
+impl Display for ReviewStatus {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}", self.as_str())
+  }
+}
+
+impl FromStr for ReviewStatus {
+  type Err = anyhow::Error;
+
+  fn from_str(s: &str) -> Result<Self> {
+    ValueEnum::from_str(s, false).map_err(|_| anyhow!("Invalid status {s}"))
+  }
+}
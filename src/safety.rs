@@ -0,0 +1,87 @@
+use {super::*, flate2::read::GzDecoder, std::io::Read, tar::Archive};
+
+/// Patterns that have no business in a build script and show up in malicious
+/// ones: network access, shelling out to downloaders, and obfuscated payloads.
+const SUSPICIOUS_PATTERNS: &[&str] = &[
+  "TcpStream",
+  "UdpSocket",
+  "reqwest",
+  "ureq",
+  "curl ",
+  "wget ",
+  "powershell",
+  "base64",
+  ".ssh",
+  "/etc/passwd",
+];
+
+/// Extract `build.rs` from a downloaded `.crate` archive, if it has one.
+pub(crate) fn build_script(
+  archive: &[u8],
+  name: &str,
+  version: &str,
+) -> Result<Option<String>> {
+  let path = format!("{name}-{version}/build.rs");
+
+  for entry in Archive::new(GzDecoder::new(archive)).entries()? {
+    let mut entry = entry?;
+
+    if entry.path()?.to_str() != Some(path.as_str()) {
+      continue;
+    }
+
+    let mut source = String::new();
+
+    entry.read_to_string(&mut source)?;
+
+    return Ok(Some(source));
+  }
+
+  Ok(None)
+}
+
+pub(crate) fn suspicious_pattern(build_script: &str) -> Option<&'static str> {
+  SUSPICIOUS_PATTERNS
+    .iter()
+    .find(|pattern| build_script.contains(*pattern))
+    .copied()
+}
+
+/// Returns the popular crate `name` is dressed up as, if `name` only differs
+/// from it by separators, a `rust`/`rs` affix, or digits standing in for
+/// letters.
+pub(crate) fn lookalike<'a>(
+  name: &str,
+  popular: &'a [String],
+) -> Option<&'a str> {
+  let normalized = normalize(name);
+
+  popular
+    .iter()
+    .find(|popular| *popular != name && normalize(popular) == normalized)
+    .map(String::as_str)
+}
+
+/// `name` with the differences `lookalike` ignores taken out.
+pub(crate) fn normalize(name: &str) -> String {
+  let name = name.to_lowercase().replace('_', "-");
+
+  let name = name.strip_prefix("rust-").unwrap_or(&name);
+
+  let name = name
+    .strip_suffix("-rs")
+    .or_else(|| name.strip_suffix("-rust"))
+    .unwrap_or(name);
+
+  name
+    .chars()
+    .filter(|c| *c != '-')
+    .map(|c| match c {
+      '0' => 'o',
+      '1' => 'l',
+      '3' => 'e',
+      '5' => 's',
+      c => c,
+    })
+    .collect()
+}
@@ -0,0 +1,70 @@
+use super::*;
+
+/// Decides when the bot may post. Ticks that come due outside of the posting
+/// window are deferred until it next opens. With jitter, each post lands at a
+/// random offset within its hourly slot, while still averaging one per hour.
+pub(crate) struct Schedule {
+  days: Vec<Weekday>,
+  hours: Hours,
+  jitter: Duration,
+  offset: Duration,
+  timezone: Tz,
+}
+
+impl Schedule {
+  pub(crate) fn new(config: &Config) -> Self {
+    Self {
+      days: config.posting_days.clone(),
+      hours: config.posting_hours,
+      jitter: Duration::from_secs(config.jitter)
+        .min(TICK_INTERVAL - Duration::from_secs(1)),
+      offset: Duration::ZERO,
+      timezone: config.timezone,
+    }
+  }
+
+  /// The delay between the previous post and the next one: the rest of the
+  /// previous slot, plus a fresh random offset into the next.
+  pub(crate) fn delay(&mut self) -> Duration {
+    let offset = if self.jitter.is_zero() {
+      Duration::ZERO
+    } else {
+      rand::thread_rng().gen_range(Duration::ZERO..=self.jitter)
+    };
+
+    let delay = TICK_INTERVAL - self.offset + offset;
+
+    self.offset = offset;
+
+    delay
+  }
+
+  /// Number of slots, starting with the one due at `next`, that have come due
+  /// by `now`.
+  pub(crate) fn missed(next: DateTime<Utc>, now: DateTime<Utc>) -> u64 {
+    match (now - next).to_std() {
+      Ok(overdue) => overdue.as_secs() / TICK_INTERVAL.as_secs() + 1,
+      Err(_) => 0,
+    }
+  }
+
+  /// Moves `next` forward by whole slots until it's after `now`, keeping posts
+  /// aligned to the existing schedule.
+  pub(crate) fn advance(
+    next: DateTime<Utc>,
+    now: DateTime<Utc>,
+  ) -> Result<DateTime<Utc>> {
+    Ok(
+      next
+        + chrono::Duration::from_std(TICK_INTERVAL)?
+          * Self::missed(next, now).try_into()?,
+    )
+  }
+
+  pub(crate) fn is_open(&self, now: DateTime<Utc>) -> bool {
+    let local = now.with_timezone(&self.timezone);
+
+    self.hours.contains(local.hour())
+      && (self.days.is_empty() || self.days.contains(&local.weekday()))
+  }
+}
@@ -0,0 +1,144 @@
+use {
+  super::*,
+  ab_glyph::{point, Font, FontVec, PxScale, ScaleFont},
+  image::{ImageOutputFormat, Rgb, RgbImage},
+  syntect::{
+    easy::HighlightLines,
+    highlighting::ThemeSet,
+    parsing::{SyntaxReference, SyntaxSet},
+    util::LinesWithEndings,
+  },
+};
+
+const COLUMNS: usize = 80;
+const FONT_SIZE: f32 = 28.0;
+const LINES: usize = 20;
+const PADDING: u32 = 32;
+const THEME: &str = "base16-ocean.dark";
+
+pub(crate) struct Screenshot {
+  font: FontVec,
+  syntaxes: SyntaxSet,
+  themes: ThemeSet,
+}
+
+impl Screenshot {
+  pub(crate) fn new(font: &Path) -> Result<Self> {
+    Ok(Self {
+      font: FontVec::try_from_vec(fs::read(font)?).map_err(|error| {
+        anyhow!("Failed to load font {}: {error}", font.display())
+      })?,
+      syntaxes: SyntaxSet::load_defaults_newlines(),
+      themes: ThemeSet::load_defaults(),
+    })
+  }
+
+  /// Whether `code` is short and narrow enough to be readable as an image.
+  pub(crate) fn fits(code: &str) -> bool {
+    let lines = code.lines().collect::<Vec<&str>>();
+
+    !lines.is_empty()
+      && lines.len() <= LINES
+      && lines.iter().all(|line| line.chars().count() <= COLUMNS)
+  }
+
+  /// Render `code` as a syntax-highlighted PNG.
+  pub(crate) fn render(&self, code: &str) -> Result<Vec<u8>> {
+    self.draw(
+      code,
+      self
+        .syntaxes
+        .find_syntax_by_extension("rs")
+        .unwrap_or_else(|| self.syntaxes.find_syntax_plain_text()),
+    )
+  }
+
+  /// Render `text`, such as a table, as a PNG without highlighting.
+  pub(crate) fn render_text(&self, text: &str) -> Result<Vec<u8>> {
+    self.draw(text, self.syntaxes.find_syntax_plain_text())
+  }
+
+  fn draw(&self, code: &str, syntax: &SyntaxReference) -> Result<Vec<u8>> {
+    let code = code.replace('\t', "    ");
+
+    let theme = &self.themes.themes[THEME];
+
+    let scale = PxScale::from(FONT_SIZE);
+
+    let font = self.font.as_scaled(scale);
+
+    let advance = font.h_advance(self.font.glyph_id('M'));
+
+    let line_height = font.height() + font.line_gap();
+
+    let columns = code
+      .lines()
+      .map(|line| line.chars().count())
+      .max()
+      .unwrap_or_default();
+
+    let width = PADDING * 2 + (columns as f32 * advance).ceil() as u32;
+
+    let height =
+      PADDING * 2 + (code.lines().count() as f32 * line_height).ceil() as u32;
+
+    let background = theme
+      .settings
+      .background
+      .map(|color| Rgb([color.r, color.g, color.b]))
+      .unwrap_or(Rgb([0x2b, 0x30, 0x3b]));
+
+    let mut image = RgbImage::from_pixel(width, height, background);
+
+    let mut highlighter = HighlightLines::new(syntax, theme);
+
+    for (row, line) in LinesWithEndings::from(&code).enumerate() {
+      let baseline = PADDING as f32 + row as f32 * line_height + font.ascent();
+
+      let mut column = 0;
+
+      for (style, text) in highlighter.highlight_line(line, &self.syntaxes)? {
+        let color =
+          [style.foreground.r, style.foreground.g, style.foreground.b];
+
+        for c in text.chars().filter(|c| *c != '\n') {
+          let glyph = self.font.glyph_id(c).with_scale_and_position(
+            scale,
+            point(PADDING as f32 + column as f32 * advance, baseline),
+          );
+
+          column += 1;
+
+          let Some(outline) = self.font.outline_glyph(glyph) else {
+            continue;
+          };
+
+          let bounds = outline.px_bounds();
+
+          outline.draw(|x, y, coverage| {
+            let x = bounds.min.x as i64 + i64::from(x);
+            let y = bounds.min.y as i64 + i64::from(y);
+
+            if x < 0 || y < 0 || x >= width.into() || y >= height.into() {
+              return;
+            }
+
+            let pixel = image.get_pixel_mut(x as u32, y as u32);
+
+            for (channel, foreground) in pixel.0.iter_mut().zip(color) {
+              *channel = (f32::from(*channel) * (1.0 - coverage)
+                + f32::from(foreground) * coverage)
+                .round() as u8;
+            }
+          });
+        }
+      }
+    }
+
+    let mut png = Vec::new();
+
+    image.write_to(&mut io::Cursor::new(&mut png), ImageOutputFormat::Png)?;
+
+    Ok(png)
+  }
+}
@@ -0,0 +1,179 @@
+use {
+  super::*,
+  rhai::{Array, Dynamic, Engine, Map, Scope, AST},
+};
+
+/// A Rhai script at `SCRIPT` with custom curation logic. Both functions are
+/// optional and take a crate record, a map with `name`, `description`,
+/// `version`, `downloads`, `dependents`, `repository`, `license`, `created`,
+/// `updated`, `keywords`, `categories`, and `owners`:
+///
+/// - `filter(krate)` returns `true` to allow the crate, and `false` or a
+///   reason to reject it.
+/// - `score(krate)` returns a number. Candidates are shortlisted and the
+///   highest scoring ones are featured first.
+pub(crate) struct Script {
+  ast: AST,
+  engine: Engine,
+  path: PathBuf,
+}
+
+impl Script {
+  pub(crate) fn load(path: &Path) -> Result<Self> {
+    let mut engine = Engine::new();
+
+    // Scripts run for every candidate, so a runaway loop shouldn't hang the
+    // bot.
+    engine.set_max_operations(SCRIPT_MAX_OPERATIONS);
+
+    let ast = engine
+      .compile(fs::read_to_string(path)?)
+      .map_err(|error| anyhow!("Invalid script {}: {error}", path.display()))?;
+
+    Ok(Self {
+      ast,
+      engine,
+      path: path.into(),
+    })
+  }
+
+  fn defines(&self, function: &str) -> bool {
+    self
+      .ast
+      .iter_functions()
+      .any(|f| f.name == function && f.params.len() == 1)
+  }
+
+  pub(crate) fn scores(&self) -> bool {
+    self.defines("score")
+  }
+
+  fn call(&self, function: &str, full_crate: &FullCrate) -> Result<Dynamic> {
+    self
+      .engine
+      .call_fn::<Dynamic>(
+        &mut Scope::new(),
+        &self.ast,
+        function,
+        (Self::record(full_crate),),
+      )
+      .map_err(|error| {
+        anyhow!(
+          "Script {} failed in `{function}` for crate {}: {error}",
+          self.path.display(),
+          full_crate.name
+        )
+      })
+  }
+
+  /// Why the script's `filter` rejects `full_crate`, if it does.
+  pub(crate) fn rejection(
+    &self,
+    full_crate: &FullCrate,
+  ) -> Result<Option<String>> {
+    if !self.defines("filter") {
+      return Ok(None);
+    }
+
+    let result = self.call("filter", full_crate)?;
+
+    if let Some(allowed) = result.clone().try_cast::<bool>() {
+      return Ok((!allowed).then(|| "rejected by script".into()));
+    }
+
+    if let Some(reason) = result.clone().try_cast::<String>() {
+      return Ok(Some(format!("rejected by script: {reason}")));
+    }
+
+    bail!(
+      "Script {} `filter` returned {}, expected a bool or a string",
+      self.path.display(),
+      result.type_name()
+    )
+  }
+
+  pub(crate) fn score(&self, full_crate: &FullCrate) -> Result<f64> {
+    let result = self.call("score", full_crate)?;
+
+    if let Some(score) = result.clone().try_cast::<f64>() {
+      return Ok(score);
+    }
+
+    if let Some(score) = result.clone().try_cast::<i64>() {
+      return Ok(score as f64);
+    }
+
+    bail!(
+      "Script {} `score` returned {}, expected a number",
+      self.path.display(),
+      result.type_name()
+    )
+  }
+
+  fn record(full_crate: &FullCrate) -> Map {
+    let strings = |values: Vec<String>| {
+      values.into_iter().map(Dynamic::from).collect::<Array>()
+    };
+
+    let optional = |value: &Option<String>| match value {
+      Some(value) => Dynamic::from(value.clone()),
+      None => Dynamic::UNIT,
+    };
+
+    let count = |n: u64| Dynamic::from(i64::try_from(n).unwrap_or(i64::MAX));
+
+    Map::from_iter([
+      ("name".into(), Dynamic::from(full_crate.name.clone())),
+      ("description".into(), optional(&full_crate.description)),
+      (
+        "version".into(),
+        Dynamic::from(full_crate.max_version.clone()),
+      ),
+      ("downloads".into(), count(full_crate.total_downloads)),
+      (
+        "dependents".into(),
+        count(full_crate.reverse_dependencies.meta.total),
+      ),
+      ("repository".into(), optional(&full_crate.repository)),
+      ("license".into(), optional(&full_crate.license)),
+      (
+        "created".into(),
+        Dynamic::from(full_crate.created_at.to_rfc3339()),
+      ),
+      (
+        "updated".into(),
+        Dynamic::from(full_crate.updated_at.to_rfc3339()),
+      ),
+      (
+        "keywords".into(),
+        Dynamic::from(strings(
+          full_crate
+            .keywords
+            .iter()
+            .map(|keyword| keyword.keyword.clone())
+            .collect(),
+        )),
+      ),
+      (
+        "categories".into(),
+        Dynamic::from(strings(
+          full_crate
+            .categories
+            .iter()
+            .map(|category| category.slug.clone())
+            .collect(),
+        )),
+      ),
+      (
+        "owners".into(),
+        Dynamic::from(strings(
+          full_crate
+            .owners
+            .iter()
+            .map(|owner| owner.login.clone())
+            .collect(),
+        )),
+      ),
+    ])
+  }
+}
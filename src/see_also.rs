@@ -0,0 +1,10 @@
+use super::*;
+
+#[derive(Debug, Default, Clone, Copy, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum SeeAlso {
+  #[default]
+  Off,
+  Post,
+  Reply,
+}
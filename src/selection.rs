@@ -0,0 +1,9 @@
+use super::*;
+
+#[derive(Debug, Default, Clone, Copy, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Selection {
+  Engagement,
+  #[default]
+  Uniform,
+}
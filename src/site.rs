@@ -0,0 +1,293 @@
+use super::*;
+
+const FEED_ITEMS: usize = 50;
+
+pub(crate) fn escape(text: &str) -> String {
+  text
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+    .replace('\'', "&#39;")
+}
+
+fn page(title: &str, root: Option<&str>, body: &str, locale: Locale) -> String {
+  let (head, nav) = match root {
+    Some(root) => (
+      format!(
+        "<link rel=\"alternate\" type=\"application/rss+xml\" \
+         href=\"{root}feed.xml\">\n"
+      ),
+      format!(
+        "<nav><a href=\"{root}index.html\">cratebot</a> \
+         <a href=\"{root}tags/index.html\">tags</a></nav>\n"
+      ),
+    ),
+    None => (String::new(), String::new()),
+  };
+
+  format!(
+    "<!doctype html>\n\
+     <html lang=\"{}\">\n\
+     <head>\n\
+     <meta charset=\"utf-8\">\n\
+     <title>{}</title>\n\
+     {head}\
+     </head>\n\
+     <body>\n\
+     {nav}\
+     <h1>{}</h1>\n\
+     {body}\
+     </body>\n\
+     </html>\n",
+    locale.tag(),
+    escape(title),
+    escape(title),
+  )
+}
+
+fn list(
+  posts: &[&Post],
+  link: impl Fn(&Post) -> String,
+  timezone: Tz,
+  locale: Locale,
+) -> String {
+  let mut html = String::from("<ol reversed>\n");
+
+  for post in posts {
+    let date = post.date.with_timezone(&timezone);
+
+    html.push_str(&format!(
+      "<li value=\"{}\"><a href=\"{}\">{}</a> \
+       <time datetime=\"{}\">{}</time>{}</li>\n",
+      post.series,
+      escape(&link(post)),
+      escape(&post.name),
+      date.to_rfc3339(),
+      locale.date(date.date_naive()),
+      post
+        .tweet_url()
+        .map(|url| format!(" <a href=\"{url}\">tweet</a>"))
+        .unwrap_or_default(),
+    ));
+  }
+
+  html.push_str("</ol>\n");
+
+  html
+}
+
+pub(crate) fn archive(posts: &[Post], timezone: Tz, locale: Locale) -> String {
+  let posts = posts.iter().collect::<Vec<&Post>>();
+
+  page(
+    "cratebot archive",
+    None,
+    &list(&posts, Post::crate_url, timezone, locale),
+    locale,
+  )
+}
+
+pub(crate) fn write_archive(
+  posts: &[Post],
+  path: &Path,
+  timezone: Tz,
+  locale: Locale,
+) -> Result {
+  log::info!("Writing archive to {}", path.display());
+
+  fs::write(path, archive(posts, timezone, locale))?;
+
+  Ok(())
+}
+
+fn crate_page(
+  name: &str,
+  posts: &[&Post],
+  timezone: Tz,
+  locale: Locale,
+) -> String {
+  let latest = posts[0];
+
+  let mut body = String::new();
+
+  if let Some(description) = &latest.description {
+    body.push_str(&format!("<p>{}</p>\n", escape(description)));
+  }
+
+  body.push_str(&format!(
+    "<p><a href=\"{}\">{}</a></p>\n",
+    escape(&latest.crate_url()),
+    escape(&latest.crate_url()),
+  ));
+
+  if !latest.keywords.is_empty() {
+    body.push_str("<ul>\n");
+
+    for keyword in &latest.keywords {
+      body.push_str(&format!(
+        "<li><a href=\"../tags/{}.html\">#{}</a></li>\n",
+        escape(keyword),
+        escape(keyword),
+      ));
+    }
+
+    body.push_str("</ul>\n");
+  }
+
+  if let Some(text) = &latest.text {
+    body.push_str(&format!("<blockquote>{}</blockquote>\n", escape(text)));
+  }
+
+  body.push_str("<h2>Featured</h2>\n");
+  body.push_str(&list(posts, Post::crate_url, timezone, locale));
+
+  page(name, Some("../"), &body, locale)
+}
+
+fn feed(posts: &[Post], base_url: Option<&str>) -> String {
+  let link = |post: &Post| match base_url {
+    Some(base_url) => format!(
+      "{}/crates/{}.html",
+      base_url.trim_end_matches('/'),
+      post.name
+    ),
+    None => post.crate_url(),
+  };
+
+  let mut xml = format!(
+    "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n\
+     <rss version=\"2.0\">\n\
+     <channel>\n\
+     <title>cratebot</title>\n\
+     <link>{}</link>\n\
+     <description>A random crate from crates.io, every hour</description>\n",
+    escape(base_url.unwrap_or("https://crates.io")),
+  );
+
+  for post in posts.iter().take(FEED_ITEMS) {
+    xml.push_str(&format!(
+      "<item>\n\
+       <title>#{} {}</title>\n\
+       <link>{}</link>\n\
+       <guid isPermaLink=\"false\">cratebot-{}</guid>\n\
+       <pubDate>{}</pubDate>\n\
+       <description>{}</description>\n\
+       </item>\n",
+      post.series,
+      escape(&post.name),
+      escape(&link(post)),
+      post.series,
+      post.date.to_rfc2822(),
+      escape(post.description.as_deref().unwrap_or_default()),
+    ));
+  }
+
+  xml.push_str("</channel>\n</rss>\n");
+
+  xml
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct Built {
+  pub(crate) crates: usize,
+  pub(crate) posts: usize,
+  pub(crate) tags: usize,
+}
+
+pub(crate) fn build(
+  posts: &[Post],
+  out: &Path,
+  base_url: Option<&str>,
+  timezone: Tz,
+  locale: Locale,
+) -> Result<Built> {
+  log::info!("Building site in {}", out.display());
+
+  fs::create_dir_all(out.join("crates"))?;
+  fs::create_dir_all(out.join("tags"))?;
+
+  let mut crates = BTreeMap::<&str, Vec<&Post>>::new();
+
+  let mut tags = BTreeMap::<&str, Vec<&Post>>::new();
+
+  for post in posts {
+    crates.entry(&post.name).or_default().push(post);
+
+    for keyword in &post.keywords {
+      tags.entry(keyword).or_default().push(post);
+    }
+  }
+
+  let crate_link = |post: &Post| format!("crates/{}.html", post.name);
+
+  fs::write(
+    out.join("index.html"),
+    page(
+      "cratebot",
+      Some(""),
+      &list(
+        &posts.iter().collect::<Vec<&Post>>(),
+        crate_link,
+        timezone,
+        locale,
+      ),
+      locale,
+    ),
+  )?;
+
+  for (name, posts) in &crates {
+    fs::write(
+      out.join("crates").join(format!("{name}.html")),
+      crate_page(name, posts, timezone, locale),
+    )?;
+  }
+
+  let mut index = String::from("<ul>\n");
+
+  for (tag, posts) in &tags {
+    index.push_str(&format!(
+      "<li><a href=\"{}.html\">#{}</a> ({})</li>\n",
+      escape(tag),
+      escape(tag),
+      posts.len(),
+    ));
+
+    fs::write(
+      out.join("tags").join(format!("{tag}.html")),
+      page(
+        &format!("#{tag}"),
+        Some("../"),
+        &list(
+          posts,
+          |post| format!("../crates/{}.html", post.name),
+          timezone,
+          locale,
+        ),
+        locale,
+      ),
+    )?;
+  }
+
+  index.push_str("</ul>\n");
+
+  fs::write(
+    out.join("tags/index.html"),
+    page("Tags", Some("../"), &index, locale),
+  )?;
+
+  fs::write(out.join("feed.xml"), feed(posts, base_url))?;
+
+  log::info!(
+    "Built site with {} posts, {} crates, and {} tags",
+    posts.len(),
+    crates.len(),
+    tags.len()
+  );
+
+  Ok(Built {
+    crates: crates.len(),
+    posts: posts.len(),
+    tags: tags.len(),
+  })
+}
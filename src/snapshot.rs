@@ -0,0 +1,35 @@
+use super::*;
+
+/// Registry-wide totals, recorded after every sync when `WEEKLY_STATS` is
+/// set. Snapshots are kept rather than overwritten, so weekly statistics can
+/// be computed from the difference between any two of them.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Snapshot {
+  pub(crate) categories: BTreeMap<String, u64>,
+  pub(crate) crates: u64,
+  pub(crate) date: DateTime<Utc>,
+  pub(crate) downloads: u64,
+  /// Crates with a new version since the previous snapshot.
+  pub(crate) versions: u64,
+}
+
+impl Snapshot {
+  pub(crate) async fn take(bot: &Bot) -> Result<Self> {
+    let date = Utc::now();
+
+    let versions = match bot.db.latest_snapshot()? {
+      Some(previous) => bot.api.updated_since(previous.date).await?,
+      None => 0,
+    };
+
+    let (crates, downloads) = bot.api.summary().await?;
+
+    Ok(Self {
+      categories: bot.api.categories().await?,
+      crates,
+      date,
+      downloads,
+      versions,
+    })
+  }
+}
@@ -0,0 +1,122 @@
+use super::*;
+
+/// Features a prolific crate author: one of the owners with the most crates
+/// seen during enrichment, who hasn't been spotlighted before, in a short
+/// thread of their most downloaded crates.
+pub(crate) async fn tick(bot: &Bot) -> Result<Published> {
+  let mut candidates = bot.db.prolific_owners(SPOTLIGHT_MIN_CRATES)?;
+
+  candidates.shuffle(&mut rand::thread_rng());
+
+  for owner in candidates {
+    if bot.db.opted_out(OptoutKind::Owner, &owner.login)? {
+      log::info!("Skipping spotlight on {}, owner opted out", owner.login);
+      continue;
+    }
+
+    let mut crates = Vec::new();
+
+    for krate in bot.owner_crates(owner.id).await? {
+      if !bot.db.opted_out(OptoutKind::Crate, &krate.name)?
+        && !Squat::estimate(&(&krate).into())
+          .is_squat(bot.config.squat_threshold)
+      {
+        crates.push(krate);
+      }
+    }
+
+    if crates.len() < SPOTLIGHT_MIN_CRATES {
+      log::info!(
+        "Skipping spotlight on {}, only {} eligible crates",
+        owner.login,
+        crates.len()
+      );
+      continue;
+    }
+
+    let downloads = crates.iter().map(|krate| krate.downloads).sum::<u64>();
+
+    crates.sort_by_key(|krate| std::cmp::Reverse(krate.downloads));
+
+    let mentions = bot
+      .db
+      .mentions(std::slice::from_ref(&owner), &bot.config.mention_optout)?;
+
+    let mut headline = format!(
+      "Author spotlight: {}{} has published {} crates with {downloads} \
+       downloads in total. A few highlights:",
+      owner.name.as_deref().unwrap_or(&owner.login),
+      mentions
+        .first()
+        .map(|handle| format!(" (@{})", handle.trim_start_matches('@')))
+        .unwrap_or_default(),
+      crates.len(),
+    );
+
+    headline = compose::append(&headline, &owner.url).unwrap_or(headline);
+
+    let text = compose::fit(&headline);
+
+    let (id, entry) =
+      bot.tweet("spotlight", &owner.login, text.clone()).await?;
+
+    let mut replies = Vec::new();
+
+    let template = SPOTLIGHT_TEMPLATE.parse::<Template>()?;
+
+    for krate in crates.iter().take(SPOTLIGHT_LENGTH) {
+      let text = template.render(
+        &BTreeMap::from([
+          (
+            "description",
+            krate
+              .description
+              .as_deref()
+              .unwrap_or_default()
+              .trim()
+              .to_string(),
+          ),
+          ("name", krate.name.clone()),
+          ("url", bot.config.crate_url(&krate.name)),
+        ]),
+        bot.config.locale,
+      );
+
+      let Some(reply) = bot
+        .reply(
+          &owner.login,
+          compose::fit(&text),
+          None,
+          replies.last().copied().unwrap_or(id),
+          entry,
+        )
+        .await?
+      else {
+        break;
+      };
+
+      replies.push(reply);
+    }
+
+    bot.db.record_spotlight(&owner.login, id)?;
+
+    bot.db.audit(
+      Action::Published,
+      Some(&owner.login),
+      &format!("spotlight tweet {id}"),
+    )?;
+
+    let published = Published {
+      kind: "spotlight",
+      name: owner.login,
+      replies,
+      series: None,
+      text,
+      tweet_id: Some(id),
+    };
+
+    return Ok(published);
+  }
+
+  bail!("Failed to find an owner to spotlight")
+}
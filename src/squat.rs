@@ -0,0 +1,115 @@
+use super::*;
+
+const PLACEHOLDER_DESCRIPTIONS: &[&str] = &[
+  "coming soon",
+  "name reserved",
+  "placeholder",
+  "reserved",
+  "reserved name",
+  "reserved for future use",
+];
+
+const PLACEHOLDER_VERSIONS: &[&str] = &["0.0.0", "0.0.1", "0.1.0"];
+
+/// Heuristic scores between 0 and 1 for how likely a crate is to be a name
+/// squat or placeholder. A crate is skipped when the scores sum to at least
+/// the configured threshold.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize)]
+pub(crate) struct Squat {
+  pub(crate) duplicate_description: f64,
+  pub(crate) placeholder_description: f64,
+  pub(crate) placeholder_version: f64,
+}
+
+impl Squat {
+  pub(crate) async fn score(
+    bot: &Bot,
+    full_crate: &FullCrate,
+    duplicate_limit: usize,
+  ) -> Result<Self> {
+    let description = normalize(full_crate.description.as_deref());
+
+    let mut squat = Self::placeholders(
+      &description,
+      &full_crate.max_version,
+      full_crate.repository.is_some(),
+    );
+
+    let mut duplicates = 0;
+
+    if !description.is_empty() {
+      for owner in &full_crate.owners {
+        duplicates = bot
+          .owner_crates(owner.id)
+          .await?
+          .iter()
+          .filter(|krate| {
+            krate.name != full_crate.name
+              && normalize(krate.description.as_deref()) == description
+          })
+          .count()
+          .max(duplicates);
+      }
+    }
+
+    squat.duplicate_description =
+      (duplicates as f64 / duplicate_limit.max(1) as f64).min(1.0);
+
+    Ok(squat)
+  }
+
+  /// Scores a crate using only locally stored metadata. The duplicate
+  /// description heuristic needs the crates.io API, so it's always zero.
+  pub(crate) fn estimate(metadata: &Metadata) -> Self {
+    Self::placeholders(
+      &normalize(metadata.description.as_deref()),
+      &metadata.max_version,
+      metadata.repository.is_some(),
+    )
+  }
+
+  fn placeholders(
+    description: &str,
+    max_version: &str,
+    repository: bool,
+  ) -> Self {
+    let placeholder_description = if description.is_empty() {
+      0.5
+    } else if PLACEHOLDER_DESCRIPTIONS.contains(&description) {
+      1.0
+    } else {
+      0.0
+    };
+
+    let placeholder_version =
+      match (PLACEHOLDER_VERSIONS.contains(&max_version), repository) {
+        (true, false) => 1.0,
+        (true, true) => 0.25,
+        (false, _) => 0.0,
+      };
+
+    Self {
+      duplicate_description: 0.0,
+      placeholder_description,
+      placeholder_version,
+    }
+  }
+
+  pub(crate) fn total(&self) -> f64 {
+    self.duplicate_description
+      + self.placeholder_description
+      + self.placeholder_version
+  }
+
+  pub(crate) fn is_squat(&self, threshold: f64) -> bool {
+    self.total() >= threshold
+  }
+}
+
+fn normalize(description: Option<&str>) -> String {
+  description
+    .unwrap_or_default()
+    .trim()
+    .trim_end_matches(['.', '!'])
+    .to_lowercase()
+}
@@ -0,0 +1,257 @@
+use {
+  super::*,
+  hmac::{Hmac, Mac},
+  sha2::{Digest, Sha256},
+};
+
+/// A copy of the database kept in S3 or Google Cloud Storage at `STATE_URL`,
+/// downloaded before and uploaded after `cratebot once`, so the bot can run as
+/// a scheduled job without a persistent disk.
+pub(crate) struct StateStore {
+  auth: Auth,
+  http: reqwest::Client,
+  url: reqwest::Url,
+}
+
+enum Auth {
+  Bearer(String),
+  Sigv4 {
+    access_key_id: String,
+    region: String,
+    secret_access_key: String,
+    session_token: Option<String>,
+  },
+}
+
+impl StateStore {
+  pub(crate) fn new(config: &Config) -> Result<Option<Self>> {
+    let Some(state_url) = &config.state_url else {
+      return Ok(None);
+    };
+
+    let (scheme, bucket, object) = state_url
+      .split_once("://")
+      .and_then(|(scheme, rest)| {
+        let (bucket, object) = rest.split_once('/')?;
+        Some((scheme, bucket, object))
+      })
+      .filter(|(_, bucket, object)| !bucket.is_empty() && !object.is_empty())
+      .ok_or_else(|| {
+        anyhow!(
+          "Invalid state URL `{state_url}`, expected `s3://bucket/key` or \
+           `gs://bucket/object`"
+        )
+      })?;
+
+    let (url, auth) = match scheme {
+      "gs" => (
+        format!("https://storage.googleapis.com/{bucket}/{object}"),
+        Auth::Bearer(config.state_token.clone().ok_or_else(|| {
+          anyhow!("STATE_TOKEN must be set to use a `gs://` state URL")
+        })?),
+      ),
+      "s3" => {
+        let (Some(access_key_id), Some(secret_access_key)) =
+          (&config.aws_access_key_id, &config.aws_secret_access_key)
+        else {
+          bail!(
+            "AWS_ACCESS_KEY_ID and AWS_SECRET_ACCESS_KEY must be set to use \
+             an `s3://` state URL"
+          );
+        };
+
+        (
+          match &config.aws_endpoint_url {
+            Some(endpoint) => {
+              format!("{}/{bucket}/{object}", endpoint.trim_end_matches('/'))
+            }
+            None => format!(
+              "https://{bucket}.s3.{}.amazonaws.com/{object}",
+              config.aws_region
+            ),
+          },
+          Auth::Sigv4 {
+            access_key_id: access_key_id.clone(),
+            region: config.aws_region.clone(),
+            secret_access_key: secret_access_key.clone(),
+            session_token: config.aws_session_token.clone(),
+          },
+        )
+      }
+      _ => {
+        bail!("Unsupported state URL scheme `{scheme}`, expected `s3` or `gs`")
+      }
+    };
+
+    Ok(Some(Self {
+      auth,
+      http: reqwest::Client::builder()
+        .user_agent(config.user_agent())
+        .build()?,
+      url: reqwest::Url::parse(&url)
+        .map_err(|error| anyhow!("Invalid state URL `{url}`: {error}"))?,
+    }))
+  }
+
+  /// Downloads the saved database to `path`, returning false if nothing has
+  /// been saved yet, in which case the bot starts with a fresh database. Any
+  /// WAL left next to `path` belongs to the old file, so it is removed.
+  pub(crate) async fn download(&self, path: &Path) -> Result<bool> {
+    log::info!("Downloading state from {}", self.url);
+
+    let response = self
+      .request(reqwest::Method::GET, Vec::new(), Utc::now())
+      .send()
+      .await?;
+
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+      return Ok(false);
+    }
+
+    let bytes = response.error_for_status()?.bytes().await?;
+
+    let partial = path.with_extension("download");
+
+    fs::write(&partial, &bytes)?;
+    fs::rename(&partial, path)?;
+
+    for suffix in ["-shm", "-wal"] {
+      let mut sidecar = path.as_os_str().to_owned();
+      sidecar.push(suffix);
+      if let Err(error) = fs::remove_file(&sidecar) {
+        if error.kind() != io::ErrorKind::NotFound {
+          return Err(error.into());
+        }
+      }
+    }
+
+    log::info!("Downloaded {} bytes of state", bytes.len());
+
+    Ok(true)
+  }
+
+  pub(crate) async fn upload(&self, path: &Path) -> Result {
+    log::info!("Uploading state to {}", self.url);
+
+    self
+      .request(reqwest::Method::PUT, fs::read(path)?, Utc::now())
+      .send()
+      .await?
+      .error_for_status()?;
+
+    Ok(())
+  }
+
+  fn request(
+    &self,
+    method: reqwest::Method,
+    body: Vec<u8>,
+    now: DateTime<Utc>,
+  ) -> reqwest::RequestBuilder {
+    let request = self.http.request(method.clone(), self.url.clone());
+
+    let request = match &self.auth {
+      Auth::Bearer(token) => request.bearer_auth(token),
+      Auth::Sigv4 {
+        access_key_id,
+        region,
+        secret_access_key,
+        session_token,
+      } => {
+        let mut headers = BTreeMap::new();
+
+        headers.insert("host", host(&self.url));
+        headers
+          .insert("x-amz-content-sha256", format::hex(&Sha256::digest(&body)));
+        headers.insert("x-amz-date", now.format("%Y%m%dT%H%M%SZ").to_string());
+
+        if let Some(session_token) = session_token {
+          headers.insert("x-amz-security-token", session_token.clone());
+        }
+
+        let authorization = sigv4(
+          method.as_str(),
+          &self.url,
+          &headers,
+          access_key_id,
+          secret_access_key,
+          region,
+          now,
+        );
+
+        headers
+          .into_iter()
+          .filter(|(name, _)| *name != "host")
+          .fold(request, |request, (name, value)| {
+            request.header(name, value)
+          })
+          .header("authorization", authorization)
+      }
+    };
+
+    request.body(body)
+  }
+}
+
+fn host(url: &reqwest::Url) -> String {
+  match url.port() {
+    Some(port) => format!("{}:{port}", url.host_str().unwrap_or_default()),
+    None => url.host_str().unwrap_or_default().into(),
+  }
+}
+
+fn hmac(key: &[u8], data: &str) -> Vec<u8> {
+  let mut mac =
+    Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any size");
+  mac.update(data.as_bytes());
+  mac.finalize().into_bytes().to_vec()
+}
+
+/// The `Authorization` header for an AWS Signature Version 4 signed S3
+/// request without a query string. `headers` must have lowercase names and include `host` and
+/// `x-amz-content-sha256`, and every one of them is signed.
+fn sigv4(
+  method: &str,
+  url: &reqwest::Url,
+  headers: &BTreeMap<&str, String>,
+  access_key_id: &str,
+  secret_access_key: &str,
+  region: &str,
+  now: DateTime<Utc>,
+) -> String {
+  let date = now.format("%Y%m%d").to_string();
+
+  let scope = format!("{date}/{region}/s3/aws4_request");
+
+  let signed_headers = headers.keys().copied().collect::<Vec<&str>>().join(";");
+
+  let canonical_request = format!(
+    "{method}\n{}\n\n{}\n{signed_headers}\n{}",
+    url.path(),
+    headers
+      .iter()
+      .map(|(name, value)| format!("{name}:{}\n", value.trim()))
+      .collect::<String>(),
+    headers
+      .get("x-amz-content-sha256")
+      .map(String::as_str)
+      .unwrap_or_default(),
+  );
+
+  let string_to_sign = format!(
+    "AWS4-HMAC-SHA256\n{}\n{scope}\n{}",
+    now.format("%Y%m%dT%H%M%SZ"),
+    format::hex(&Sha256::digest(canonical_request.as_bytes())),
+  );
+
+  let key = [region, "s3", "aws4_request"].into_iter().fold(
+    hmac(format!("AWS4{secret_access_key}").as_bytes(), &date),
+    |key, data| hmac(&key, data),
+  );
+
+  format!(
+    "AWS4-HMAC-SHA256 Credential={access_key_id}/{scope}, \
+     SignedHeaders={signed_headers}, Signature={}",
+    format::hex(&hmac(&key, &string_to_sign)),
+  )
+}
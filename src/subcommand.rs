@@ -0,0 +1,139 @@
+use super::*;
+
+mod archive;
+mod audit_log;
+mod cache;
+mod correct;
+mod delete_post;
+mod downloads;
+mod explain;
+mod once;
+mod optout;
+mod overrides;
+mod post;
+mod review;
+mod run;
+mod score;
+mod simulate;
+mod site;
+mod stats;
+mod templates;
+mod tui;
+
+#[derive(Debug, Parser)]
+pub(crate) enum Subcommand {
+  #[clap(about = "Render an HTML archive of every featured crate")]
+  Archive(archive::Archive),
+  #[clap(subcommand, about = "Manage the cache of crates.io responses")]
+  Cache(cache::Cache),
+  #[clap(about = "Retract every post featuring a crate and opt it out")]
+  Correct(correct::Correct),
+  #[clap(about = "Delete a published post and its thread")]
+  DeletePost(delete_post::DeletePost),
+  #[clap(about = "Show daily downloads of a crate, in total or per version")]
+  Downloads(downloads::Downloads),
+  #[clap(about = "Show which selection filters a crate passes or fails")]
+  Explain(explain::Explain),
+  #[clap(about = "Query the audit log of bot actions")]
+  Log(audit_log::AuditLog),
+  #[clap(about = "Run one sync, select, and post cycle, then exit")]
+  Once(once::Once),
+  #[clap(
+    subcommand,
+    about = "Manage owners and crates that must never be featured"
+  )]
+  Optout(optout::Optout),
+  #[clap(
+    subcommand,
+    about = "Manage per-crate descriptions, hashtags, and links for posts"
+  )]
+  Overrides(overrides::Overrides),
+  #[clap(about = "Post specific crates now, outside of the schedule")]
+  Post(post::Post),
+  #[clap(subcommand, about = "Review crates flagged by the safety gate")]
+  Review(review::Review),
+  #[clap(about = "Run the bot, tweeting a random crate every hour (default)")]
+  Run(run::Run),
+  #[clap(about = "Print the squatting heuristic scores for a crate")]
+  Score(score::Score),
+  #[clap(about = "Replay crate selection over the local database, running \
+                  the real filters, without posting")]
+  Simulate(simulate::Simulate),
+  #[clap(subcommand, about = "Build a static website from post history")]
+  Site(site::Site),
+  #[clap(about = "Report totals over past posts, or rank them by engagement")]
+  Stats(stats::Stats),
+  #[clap(subcommand, about = "Check post templates before deploying them")]
+  Template(templates::Templates),
+  #[clap(about = "Browse, preview, and queue unvisited crates interactively")]
+  Tui(tui::Tui),
+}
+
+impl Default for Subcommand {
+  fn default() -> Self {
+    Self::Run(run::Run::default())
+  }
+}
+
+fn print_json(value: &impl Serialize) -> Result {
+  println!("{}", serde_json::to_string(value)?);
+  Ok(())
+}
+
+fn deleted(post: &crate::post::Post) -> serde_json::Value {
+  serde_json::json!({
+    "series": post.series,
+    "name": post.name,
+    "tweet_id": post.tweet_id,
+    "thread": post.thread,
+  })
+}
+
+async fn correct(bot: &Bot, name: &str, text: String) -> Result<u64> {
+  bot.afford(false)?;
+
+  let id = bot.client.tweet(text).await?;
+
+  bot
+    .db
+    .record_sent("correction", Some(name), Some(id), false)?;
+
+  bot
+    .db
+    .audit(Action::Corrected, Some(name), &format!("tweet {id}"))?;
+
+  Ok(id)
+}
+
+impl Subcommand {
+  pub(crate) async fn run(self, json: bool) -> Result {
+    match &self {
+      Self::Run(run) => run.init_logger()?,
+      // Logging to stderr would draw over the interface.
+      Self::Tui(_) => {}
+      _ => env_logger::init(),
+    }
+
+    match self {
+      Self::Archive(archive) => archive.run(json),
+      Self::Cache(cache) => cache.run(json),
+      Self::Correct(correct) => correct.run(json).await,
+      Self::DeletePost(delete_post) => delete_post.run(json).await,
+      Self::Downloads(downloads) => downloads.run(json).await,
+      Self::Explain(explain) => explain.run(json).await,
+      Self::Log(audit_log) => audit_log.run(json),
+      Self::Once(once) => once.run(json).await,
+      Self::Optout(optout) => optout.run(json),
+      Self::Overrides(overrides) => overrides.run(json),
+      Self::Post(post) => post.run(json).await,
+      Self::Review(review) => review.run(json),
+      Self::Run(run) => run.run(json).await,
+      Self::Score(score) => score.run(json).await,
+      Self::Simulate(simulate) => simulate.run(json).await,
+      Self::Site(site) => site.run(json),
+      Self::Stats(stats) => stats.run(json),
+      Self::Template(templates) => templates.run(json),
+      Self::Tui(tui) => tui.run(json).await,
+    }
+  }
+}
@@ -0,0 +1,41 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Archive {
+  #[clap(
+    long,
+    default_value = "archive.html",
+    help = "Write archive to <OUT>"
+  )]
+  out: PathBuf,
+  #[clap(
+    long,
+    help = "Show dates in this timezone [default: the configured display \
+              timezone]"
+  )]
+  timezone: Option<Tz>,
+}
+
+impl Archive {
+  pub(crate) fn run(self, json: bool) -> Result {
+    let posts = Db::open(Some(PathBuf::from(DB_PATH)))?.posts()?;
+
+    let config = Config::from_env()?;
+
+    crate::site::write_archive(
+      &posts,
+      &self.out,
+      self.timezone.unwrap_or_else(|| config.display_timezone()),
+      config.locale,
+    )?;
+
+    if json {
+      print_json(&serde_json::json!({
+        "out": self.out,
+        "posts": posts.len(),
+      }))?;
+    }
+
+    Ok(())
+  }
+}
@@ -0,0 +1,29 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct AuditLog {
+  #[clap(long, help = "Only show entries for this action")]
+  action: Option<Action>,
+  #[clap(long = "crate", help = "Only show entries for this crate")]
+  name: Option<String>,
+  #[clap(long, default_value = "50", help = "Show at most this many entries")]
+  limit: usize,
+}
+
+impl AuditLog {
+  pub(crate) fn run(self, json: bool) -> Result {
+    let db = Db::open(Some(PathBuf::from(DB_PATH)))?;
+
+    let entries = db.audits(self.action, self.name.as_deref(), self.limit)?;
+
+    if json {
+      return print_json(&entries);
+    }
+
+    for entry in entries {
+      println!("{entry}");
+    }
+
+    Ok(())
+  }
+}
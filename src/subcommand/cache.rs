@@ -0,0 +1,27 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) enum Cache {
+  #[clap(about = "Delete every cached crates.io response")]
+  Clear,
+}
+
+impl Cache {
+  pub(crate) fn run(self, json: bool) -> Result {
+    let db = Db::open(Some(PathBuf::from(DB_PATH)))?;
+
+    match self {
+      Self::Clear => {
+        let cleared = db.clear_cache()?;
+
+        if json {
+          print_json(&serde_json::json!({ "cleared": cleared }))?;
+        } else {
+          println!("Cleared {cleared} cached responses");
+        }
+
+        Ok(())
+      }
+    }
+  }
+}
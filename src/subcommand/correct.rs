@@ -0,0 +1,70 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Correct {
+  #[clap(help = "Crate whose posts should be retracted")]
+  name: String,
+  #[clap(long, help = "Delete the posts without tweeting a correction")]
+  quiet: bool,
+  #[clap(
+    long,
+    default_value = "retracted",
+    help = "Record <REASON> in post history and the correction"
+  )]
+  reason: String,
+}
+
+impl Correct {
+  pub(crate) async fn run(self, json: bool) -> Result {
+    let _lock = PidFile::acquire(PID_PATH.as_ref())?;
+
+    let bot = Bot::new(Config::from_env()?).await?;
+
+    let posts = bot.db.posts_featuring(&self.name)?;
+
+    if posts.is_empty() {
+      bail!("No published posts featuring {}", self.name);
+    }
+
+    for post in &posts {
+      bot.delete(post, &self.reason).await?;
+    }
+
+    bot.db.optout(OptoutKind::Crate, &self.name)?;
+
+    let correction = if self.quiet {
+      None
+    } else {
+      Some(
+        correct(
+          &bot,
+          &self.name,
+          format!(
+            "Correction: we've removed our post featuring {} ({}).",
+            self.name, self.reason
+          ),
+        )
+        .await?,
+      )
+    };
+
+    if json {
+      return print_json(&serde_json::json!({
+        "deleted": posts.iter().map(deleted).collect::<Vec<serde_json::Value>>(),
+        "correction": correction,
+      }));
+    }
+
+    for post in &posts {
+      println!("Deleted post #{} featuring {}", post.series, post.name);
+    }
+
+    println!("Opted out crate {}", self.name);
+
+    if let Some(id) = correction {
+      println!("Posted correction: tweet {id}");
+    }
+
+    Ok(())
+  }
+}
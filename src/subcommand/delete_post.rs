@@ -0,0 +1,53 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct DeletePost {
+  #[clap(help = "Series number of the post to delete")]
+  series: u64,
+  #[clap(long, help = "Tweet <CORRECTION> after deleting the post")]
+  correction: Option<String>,
+  #[clap(
+    long,
+    default_value = "deleted by operator",
+    help = "Record <REASON> in post history"
+  )]
+  reason: String,
+}
+
+impl DeletePost {
+  pub(crate) async fn run(self, json: bool) -> Result {
+    let _lock = PidFile::acquire(PID_PATH.as_ref())?;
+
+    let bot = Bot::new(Config::from_env()?).await?;
+
+    let Some(post) = bot.db.post(self.series)? else {
+      bail!("No post #{} in history", self.series);
+    };
+
+    if let Some(deleted) = post.deleted {
+      bail!("Post #{} was already deleted on {deleted}", post.series);
+    }
+
+    bot.delete(&post, &self.reason).await?;
+
+    let correction = match self.correction {
+      Some(text) => Some(correct(&bot, &post.name, text).await?),
+      None => None,
+    };
+
+    if json {
+      return print_json(&serde_json::json!({
+        "deleted": [deleted(&post)],
+        "correction": correction,
+      }));
+    }
+
+    println!("Deleted post #{} featuring {}", post.series, post.name);
+
+    if let Some(id) = correction {
+      println!("Posted correction: tweet {id}");
+    }
+
+    Ok(())
+  }
+}
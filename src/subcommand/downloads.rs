@@ -0,0 +1,47 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Downloads {
+  #[clap(help = "Crate to show downloads of")]
+  name: String,
+  #[clap(
+    long,
+    default_value = "30",
+    help = "Show this many days, crates.io keeps up to 90"
+  )]
+  days: u32,
+  #[clap(long, help = "Break downloads down by version")]
+  versions: bool,
+}
+
+impl Downloads {
+  pub(crate) async fn run(self, json: bool) -> Result {
+    let bot = Bot::new(Config::from_env()?).await?;
+
+    let end = Utc::now().date_naive();
+
+    let start = end - chrono::Duration::days(i64::from(self.days) - 1);
+
+    let downloads = bot.downloads(&self.name, start..=end).await?;
+
+    if json {
+      return print_json(&downloads);
+    }
+
+    if self.versions {
+      for (version, daily) in &downloads.versions {
+        for (date, count) in daily {
+          println!("{date}\t{version}\t{count}");
+        }
+      }
+    } else {
+      for (date, count) in &downloads.daily {
+        println!("{date}\t{count}");
+      }
+    }
+
+    println!("total\t{}", bot.config.locale.number(downloads.total()));
+
+    Ok(())
+  }
+}
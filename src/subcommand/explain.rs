@@ -0,0 +1,84 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Explain {
+  #[clap(help = "Crate to run through the selection pipeline")]
+  name: String,
+}
+
+impl Explain {
+  pub(crate) async fn run(self, json: bool) -> Result {
+    let bot = Bot::new(Config::from_env()?).await?;
+
+    let full_crate = bot.full_crate(&self.name).await?;
+
+    let mut checks = Vec::new();
+
+    for filter in Filter::ALL {
+      checks.push((*filter, bot.check(*filter, &full_crate).await?));
+    }
+
+    let eligible = checks.iter().all(|(_, reason)| reason.is_none());
+
+    let candidates = bot.db.crates(&bot.config.focus)?;
+
+    let candidate = candidates.contains(&full_crate.name);
+
+    let affinity = if bot.config.selection == Selection::Engagement {
+      bot.db.index(&full_crate)?;
+      Some(Weights::load(&bot.db)?.weight(&bot.db.keywords(&full_crate.name)?))
+    } else {
+      None
+    };
+
+    if json {
+      return print_json(&serde_json::json!({
+        "name": full_crate.name,
+        "filters": checks
+          .iter()
+          .map(|(filter, reason)| serde_json::json!({
+            "filter": filter.to_string(),
+            "pass": reason.is_none(),
+            "reason": reason.as_ref().map(Rejection::to_string),
+          }))
+          .collect::<Vec<serde_json::Value>>(),
+        "candidate": candidate,
+        "candidates": candidates.len(),
+        "weight": if candidate && eligible {
+          1.0 / candidates.len() as f64
+        } else {
+          0.0
+        },
+        "affinity": affinity,
+      }));
+    }
+
+    for (filter, reason) in checks {
+      match reason {
+        Some(reason) => println!("{filter}\tfail: {reason}"),
+        None => println!("{filter}\tpass"),
+      }
+    }
+
+    if !candidate {
+      println!("weight\t0 (not an unvisited candidate in the database)");
+    } else if !eligible {
+      println!("weight\t0 (rejected by filters)");
+    } else {
+      println!(
+        "weight\t{:.6} (1 of {} candidates)",
+        1.0 / candidates.len() as f64,
+        candidates.len()
+      );
+    }
+
+    if let Some(affinity) = affinity {
+      println!(
+        "affinity\t{affinity:.2} (engagement weighting, {:.0}% exploration)",
+        bot.config.exploration * 100.0
+      );
+    }
+
+    Ok(())
+  }
+}
@@ -0,0 +1,35 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Once {}
+
+impl Once {
+  pub(crate) async fn run(self, json: bool) -> Result {
+    // Locked before the saved state is downloaded over the database.
+    let _lock = PidFile::acquire(PID_PATH.as_ref())?;
+
+    let config = Config::from_env()?;
+
+    let store = StateStore::new(&config)?;
+
+    if let Some(store) = &store {
+      if !store.download(Path::new(DB_PATH)).await? {
+        log::info!("No saved state found, starting with a fresh database");
+      }
+    }
+
+    let bot = Bot::new(config).await?;
+
+    let result = run::tick(&bot, json).await;
+
+    // Closing the last connection checkpoints the WAL into `db.sqlite`, so
+    // the uploaded file is complete on its own.
+    drop(bot);
+
+    if let Some(store) = &store {
+      store.upload(Path::new(DB_PATH)).await?;
+    }
+
+    result
+  }
+}
@@ -0,0 +1,42 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) enum Optout {
+  #[clap(about = "Never feature an owner's crates, or a single crate")]
+  Add { kind: OptoutKind, name: String },
+  #[clap(about = "Remove an opt-out")]
+  Remove { kind: OptoutKind, name: String },
+  #[clap(about = "List all opt-outs")]
+  List,
+}
+
+impl Optout {
+  pub(crate) fn run(self, json: bool) -> Result {
+    let db = Db::open(Some(PathBuf::from(DB_PATH)))?;
+
+    match self {
+      Self::Add { kind, name } => db.optout(kind, &name),
+      Self::Remove { kind, name } => db.remove_optout(kind, &name),
+      Self::List => {
+        let optouts = db.optouts()?;
+
+        if json {
+          return print_json(
+            &optouts
+              .into_iter()
+              .map(|(kind, name, date)| {
+                serde_json::json!({ "kind": kind, "name": name, "date": date })
+              })
+              .collect::<Vec<serde_json::Value>>(),
+          );
+        }
+
+        for (kind, name, date) in optouts {
+          println!("{kind}\t{name}\t{date}");
+        }
+
+        Ok(())
+      }
+    }
+  }
+}
@@ -0,0 +1,79 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) enum Overrides {
+  #[clap(about = "Set overrides for a crate, keeping any that aren't given")]
+  Set {
+    name: String,
+    #[clap(long, help = "Description to post instead of the crate's own")]
+    description: Option<String>,
+    #[clap(
+      long,
+      value_delimiter = ',',
+      help = "Comma-separated hashtags to post instead of HASHTAGS"
+    )]
+    hashtags: Option<Vec<String>>,
+    #[clap(long, help = "Link to post instead of the crate's page")]
+    link: Option<String>,
+    #[clap(long, help = "Note for operators, never posted")]
+    note: Option<String>,
+  },
+  #[clap(about = "Remove all overrides for a crate")]
+  Remove { name: String },
+  #[clap(about = "List all overrides")]
+  List,
+}
+
+impl Overrides {
+  pub(crate) fn run(self, json: bool) -> Result {
+    let db = Db::open(Some(PathBuf::from(DB_PATH)))?;
+
+    match self {
+      Self::Set {
+        name,
+        description,
+        hashtags,
+        link,
+        note,
+      } => {
+        let mut crate_override = db.crate_override(&name)?.unwrap_or_default();
+
+        crate_override.description = description.or(crate_override.description);
+        crate_override.hashtags = hashtags.or(crate_override.hashtags);
+        crate_override.link = link.or(crate_override.link);
+        crate_override.note = note.or(crate_override.note);
+
+        db.set_override(&name, &crate_override)
+      }
+      Self::Remove { name } => db.remove_override(&name),
+      Self::List => {
+        let overrides = db.overrides()?;
+
+        if json {
+          return print_json(
+            &overrides
+              .into_iter()
+              .map(|(name, crate_override)| {
+                let mut value = serde_json::to_value(crate_override)?;
+                value["name"] = name.into();
+                Ok(value)
+              })
+              .collect::<Result<Vec<serde_json::Value>>>()?,
+          );
+        }
+
+        for (name, crate_override) in overrides {
+          println!(
+            "{name}\t{}\t{}\t{}\t{}",
+            crate_override.description.unwrap_or_default(),
+            crate_override.hashtags.unwrap_or_default().join(","),
+            crate_override.link.unwrap_or_default(),
+            crate_override.note.unwrap_or_default(),
+          );
+        }
+
+        Ok(())
+      }
+    }
+  }
+}
@@ -0,0 +1,109 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Post {
+  #[clap(long = "crate", help = "Post this crate, may be repeated")]
+  names: Vec<String>,
+  #[clap(
+    long,
+    help = "Post crates named one per line in <FROM_FILE>, or `-` for stdin"
+  )]
+  from_file: Option<PathBuf>,
+  #[clap(long, help = "Post crates even if they were featured recently")]
+  force: bool,
+}
+
+impl Post {
+  fn names(&self) -> Result<Vec<String>> {
+    let mut names = self.names.clone();
+
+    if let Some(path) = &self.from_file {
+      let contents = if path == Path::new("-") {
+        io::read_to_string(io::stdin())?
+      } else {
+        fs::read_to_string(path)?
+      };
+
+      names.extend(
+        contents
+          .lines()
+          .map(str::trim)
+          .filter(|line| !line.is_empty() && !line.starts_with('#'))
+          .map(str::to_owned),
+      );
+    }
+
+    if names.is_empty() {
+      bail!("No crates to post, pass `--crate` or `--from-file`");
+    }
+
+    Ok(names)
+  }
+
+  pub(crate) async fn run(self, json: bool) -> Result {
+    let names = self.names()?;
+
+    let _lock = PidFile::acquire(PID_PATH.as_ref())?;
+
+    let bot = Bot::new(Config::from_env()?).await?;
+
+    let mut published = Vec::new();
+
+    let mut skipped = Vec::new();
+
+    let budget = Budget::new(&bot.config);
+
+    for name in names {
+      if let Some(reason) = budget.exhausted(&bot.db, Utc::now(), true)? {
+        bot
+          .db
+          .audit(Action::BudgetExhausted, Some(&name), &reason)?;
+        skipped.push((name, reason));
+        continue;
+      }
+
+      if !self.force && bot.db.visited(&name)? {
+        skipped.push((name, "featured recently".to_owned()));
+        continue;
+      }
+
+      let full_crate = bot.full_crate(&name).await?;
+
+      if let Some(reason) = bot.db.exclusion(&full_crate)? {
+        skipped.push((name, reason));
+        continue;
+      }
+
+      let post = bot.feature(&full_crate).await?;
+
+      bot.save_quota()?;
+
+      published.push(post);
+    }
+
+    if json {
+      return print_json(&serde_json::json!({
+        "published": published,
+        "skipped": skipped
+          .iter()
+          .map(|(name, reason)| {
+            serde_json::json!({ "name": name, "reason": reason })
+          })
+          .collect::<Vec<serde_json::Value>>(),
+      }));
+    }
+
+    for published in published {
+      match published.tweet_id {
+        Some(id) => println!("{}\ttweet {id}", published.name),
+        None => println!("{}\tnot tweeted", published.name),
+      }
+    }
+
+    for (name, reason) in skipped {
+      println!("{name}\tskipped: {reason}");
+    }
+
+    Ok(())
+  }
+}
@@ -0,0 +1,50 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) enum Review {
+  #[clap(about = "Let a flagged crate be featured")]
+  Approve { name: String },
+  #[clap(about = "List crates flagged by the safety gate")]
+  List {
+    #[clap(long, help = "Only list crates with this status")]
+    status: Option<ReviewStatus>,
+  },
+  #[clap(about = "Keep a flagged crate from ever being featured")]
+  Reject { name: String },
+}
+
+impl Review {
+  pub(crate) fn run(self, json: bool) -> Result {
+    let db = Db::open(Some(PathBuf::from(DB_PATH)))?;
+
+    match self {
+      Self::Approve { name } => db.set_review(&name, ReviewStatus::Approved),
+      Self::List { status } => {
+        let reviews = db.reviews(status)?;
+
+        if json {
+          return print_json(
+            &reviews
+              .into_iter()
+              .map(|(name, status, reason, date)| {
+                serde_json::json!({
+                  "name": name,
+                  "status": status.as_str(),
+                  "reason": reason,
+                  "date": date,
+                })
+              })
+              .collect::<Vec<serde_json::Value>>(),
+          );
+        }
+
+        for (name, status, reason, date) in reviews {
+          println!("{name}\t{status}\t{reason}\t{date}");
+        }
+
+        Ok(())
+      }
+      Self::Reject { name } => db.set_review(&name, ReviewStatus::Rejected),
+    }
+  }
+}
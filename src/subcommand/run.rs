@@ -0,0 +1,278 @@
+use super::*;
+
+#[derive(Debug, Default, Parser)]
+pub(crate) struct Run {
+  #[clap(long, help = "Log to a rotating file")]
+  daemon: bool,
+  #[clap(long, help = "Log file used in daemon mode [default: cratebot.log]")]
+  log_file: Option<PathBuf>,
+  #[clap(long, help = "Rotated log files to keep in daemon mode [default: 5]")]
+  log_keep: Option<usize>,
+  #[clap(
+    long,
+    help = "Rotate the log file after this many bytes [default: 10485760]"
+  )]
+  log_max_size: Option<u64>,
+  #[clap(long, help = "PID file locked while running [default: cratebot.pid]")]
+  pid_file: Option<PathBuf>,
+}
+
+impl Run {
+  pub(crate) fn init_logger(&self) -> Result {
+    if !self.daemon {
+      env_logger::init();
+      return Ok(());
+    }
+
+    let log_file = LogFile::open(
+      self.log_file.clone().unwrap_or(LOG_PATH.into()),
+      self.log_max_size.unwrap_or(LOG_MAX_SIZE),
+      self.log_keep.unwrap_or(LOG_KEEP),
+    )?;
+
+    env_logger::Builder::from_env(
+      env_logger::Env::default().default_filter_or("info"),
+    )
+    .target(env_logger::Target::Pipe(Box::new(log_file)))
+    .init();
+
+    Ok(())
+  }
+
+  pub(crate) async fn run(self, json: bool) -> Result {
+    let _lock = PidFile::acquire(&self.pid_file.unwrap_or(PID_PATH.into()))?;
+
+    let mut bot = Bot::new(Config::from_env()?).await?;
+
+    let mut reload = Reload::watch(CONFIG_PATH.as_ref())?;
+
+    bot.systemd.ready()?;
+
+    let mut schedule = Schedule::new(&bot.config);
+
+    let delay = chrono::Duration::from_std(schedule.delay())?;
+
+    let mut next = match bot.db.state(NEXT_RUN)? {
+      Some(next) => next.parse()?,
+      None => match bot.db.state(LAST_RUN)? {
+        Some(last_run) => last_run.parse::<DateTime<Utc>>()? + delay,
+        None => Utc::now() + delay,
+      },
+    };
+
+    let missed = Schedule::missed(next, Utc::now());
+
+    if missed > 0 {
+      let catch_up = missed.min(bot.config.catch_up_limit);
+
+      log::info!("Missed {missed} slots while down, catching up on {catch_up}");
+
+      for _ in 0..catch_up {
+        if !schedule.is_open(Utc::now()) {
+          log::info!("Outside of posting window, skipping catch-up...");
+          break;
+        }
+
+        tick(&bot, json).await?;
+      }
+
+      next = Schedule::advance(next, Utc::now())?;
+    }
+
+    bot.db.set_state(NEXT_RUN, &Db::timestamp(next))?;
+
+    log::info!("Next tweet scheduled for {next}");
+
+    let mut deferred = false;
+
+    let mut engagement_instant = None::<Instant>;
+
+    let mut mentions_instant = Instant::now();
+
+    let mut bio_instant = None::<Instant>;
+
+    loop {
+      bot.systemd.ping()?;
+
+      // Credentials and clients stay as they were at startup, everything
+      // read from the config while ticking picks up the change, including
+      // the scoring script, which is loaded again from its path.
+      if let Some(config) = reload.poll() {
+        match config.script.as_deref().map(Script::load).transpose() {
+          Ok(script) => {
+            bot.config = config;
+            bot.script = script;
+            schedule = Schedule::new(&bot.config);
+          }
+          Err(error) => {
+            log::warn!(
+              "Failed to load script, keeping running config: {error:#}"
+            );
+          }
+        }
+      }
+
+      if bot.config.reply_to_mentions
+        && mentions_instant.elapsed()
+          >= Duration::from_secs(bot.config.mention_poll_interval)
+      {
+        background(&bot, "poll mentions", mentions::poll(&bot).await)?;
+        mentions_instant = Instant::now();
+      }
+
+      if bot.config.engagement
+        && engagement_instant.is_none_or(|instant| {
+          instant.elapsed()
+            >= Duration::from_secs(bot.config.engagement_interval)
+        })
+      {
+        background(
+          &bot,
+          "refresh engagement",
+          engagement::refresh(&bot).await,
+        )?;
+        engagement_instant = Some(Instant::now());
+      }
+
+      if bot.config.bio
+        && bio_instant.is_none_or(|instant| {
+          instant.elapsed() >= Duration::from_secs(bot.config.bio_interval)
+        })
+      {
+        background(&bot, "update bio", bio::update(&bot, next).await)?;
+        bio_instant = Some(Instant::now());
+      }
+
+      let now = Utc::now();
+
+      if now >= next && !schedule.is_open(now) {
+        if !deferred {
+          log::info!("Outside of posting window, deferring tweet...");
+          deferred = true;
+        }
+      } else if now >= next {
+        deferred = false;
+
+        log::info!("Time elapsed, sending tweet...");
+
+        tick(&bot, json).await?;
+
+        next = Schedule::advance(
+          next + chrono::Duration::from_std(schedule.delay())?,
+          Utc::now(),
+        )?;
+
+        bot.db.set_state(NEXT_RUN, &Db::timestamp(next))?;
+
+        log::info!("Next tweet scheduled for {next}");
+
+        // The bio shows the next pick, so refresh it right away.
+        bio_instant = None;
+        continue;
+      }
+
+      // Sleep until the next tweet or background task is due, waking up
+      // regularly to pick up config changes and, while deferred, to see if
+      // the posting window opened.
+      let mut wait = if deferred {
+        IDLE_INTERVAL
+      } else {
+        (next - Utc::now())
+          .to_std()
+          .unwrap_or_default()
+          .min(IDLE_INTERVAL)
+      };
+
+      for (enabled, instant, interval) in [
+        (
+          bot.config.reply_to_mentions,
+          Some(mentions_instant),
+          bot.config.mention_poll_interval,
+        ),
+        (
+          bot.config.engagement,
+          engagement_instant,
+          bot.config.engagement_interval,
+        ),
+        (bot.config.bio, bio_instant, bot.config.bio_interval),
+      ] {
+        if enabled {
+          wait = wait.min(instant.map_or(Duration::ZERO, |instant| {
+            Duration::from_secs(interval).saturating_sub(instant.elapsed())
+          }));
+        }
+      }
+
+      bot.systemd.sleep(wait).await?;
+    }
+  }
+}
+
+/// Logs and audits a failed background task between ticks. It's tried again
+/// at its next interval, rather than taking the daemon down.
+fn background(bot: &Bot, task: &str, result: Result) -> Result {
+  if let Err(error) = result {
+    log::warn!("Failed to {task}: {error:#}");
+    bot.db.audit(
+      Action::Error,
+      None,
+      &format!("failed to {task}: {error:#}"),
+    )?;
+  }
+
+  Ok(())
+}
+
+pub(crate) async fn tick(bot: &Bot, json: bool) -> Result {
+  // Catch-up ticks run back to back, before the scheduler loop starts.
+  bot.systemd.ping()?;
+
+  if let Some(quota) = bot.client.quota() {
+    let now = Utc::now();
+
+    if quota.is_low(bot.config.quota_reserve, now) {
+      let wait = (quota.reset - now).to_std()?;
+
+      let detail = format!(
+        "{} of {} posts left until {}",
+        quota.remaining, quota.limit, quota.reset
+      );
+
+      if wait > QUOTA_MAX_DELAY {
+        log::warn!("Skipping tick, {detail}");
+        bot.db.audit(Action::QuotaExhausted, None, &detail)?;
+        return Ok(());
+      }
+
+      log::info!("Delaying tick {}s, {detail}", wait.as_secs());
+
+      bot.systemd.sleep(wait).await?;
+    }
+  }
+
+  if let Some(reason) =
+    Budget::new(&bot.config).exhausted(&bot.db, Utc::now(), true)?
+  {
+    log::warn!("Skipping tick, {reason}");
+    bot.db.audit(Action::BudgetExhausted, None, &reason)?;
+    return Ok(());
+  }
+
+  let result = bot.tick().await;
+
+  bot.save_quota()?;
+
+  let report = match result {
+    Ok(report) => report,
+    Err(error) => {
+      bot.db.audit(Action::Error, None, &error.to_string())?;
+      return Err(error.into());
+    }
+  };
+
+  if json {
+    print_json(&report)?;
+  }
+
+  bot.db.set_state(LAST_RUN, &Db::timestamp(Utc::now()))
+}
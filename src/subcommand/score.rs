@@ -0,0 +1,44 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Score {
+  #[clap(help = "Crate to score")]
+  name: String,
+}
+
+impl Score {
+  pub(crate) async fn run(self, json: bool) -> Result {
+    let bot = Bot::new(Config::from_env()?).await?;
+
+    let squat = bot.squat(&bot.full_crate(&self.name).await?).await?;
+
+    if json {
+      return print_json(&serde_json::json!({
+        "name": self.name,
+        "scores": squat,
+        "total": squat.total(),
+        "threshold": bot.config.squat_threshold,
+        "squat": squat.is_squat(bot.config.squat_threshold),
+      }));
+    }
+
+    println!("duplicate_description\t{:.2}", squat.duplicate_description);
+    println!(
+      "placeholder_description\t{:.2}",
+      squat.placeholder_description
+    );
+    println!("placeholder_version\t{:.2}", squat.placeholder_version);
+    println!(
+      "total\t{:.2} (threshold {:.2}, {})",
+      squat.total(),
+      bot.config.squat_threshold,
+      if squat.is_squat(bot.config.squat_threshold) {
+        "skipped"
+      } else {
+        "eligible"
+      }
+    );
+
+    Ok(())
+  }
+}
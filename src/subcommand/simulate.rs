@@ -0,0 +1,122 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Simulate {
+  #[clap(long, default_value = "30", help = "Number of days to simulate")]
+  days: u64,
+  #[clap(long, default_value = "24", help = "Posts per simulated day")]
+  per_day: usize,
+  #[clap(long, help = "Seed the random number generator for reproducibility")]
+  seed: Option<u64>,
+}
+
+impl Simulate {
+  /// Replays selection `per_day` times a day, trying candidates in the order
+  /// a tick would and running them through the same lookups and filters,
+  /// which are cached, so simulating again is cheap. Unlike a tick, nothing
+  /// is marked gone or flagged for review.
+  pub(crate) async fn run(self, json: bool) -> Result {
+    let bot = Bot::new(Config::from_env()?).await?;
+
+    let mut rng = match self.seed {
+      Some(seed) => StdRng::seed_from_u64(seed),
+      None => StdRng::from_entropy(),
+    };
+
+    let ranking = Ranking::load(&bot.db, &bot.config)?;
+
+    let mut candidates = bot.db.crates(&bot.config.focus)?;
+
+    let mut rejected = BTreeMap::<String, usize>::new();
+
+    let start = Utc::now().date_naive();
+
+    let mut days = Vec::new();
+
+    for day in 0..self.days {
+      let mut picks = Vec::new();
+
+      while picks.len() < self.per_day {
+        let mut tried = BTreeSet::new();
+
+        let mut pick = None;
+
+        for name in ranking.order(candidates.clone(), &mut rng) {
+          tried.insert(name.clone());
+
+          let Ok(full_crate) = bot.lookup(&name).await? else {
+            *rejected.entry("fetch".into()).or_default() += 1;
+            continue;
+          };
+
+          let mut failed = None;
+
+          for filter in Filter::ALL {
+            if bot.check(*filter, &full_crate).await?.is_some() {
+              failed = Some(filter);
+              break;
+            }
+          }
+
+          match failed {
+            Some(filter) => {
+              *rejected.entry(filter.to_string()).or_default() += 1;
+            }
+            None => {
+              pick = Some(name);
+              break;
+            }
+          }
+        }
+
+        // Rejected crates would be rejected again, and picked crates are
+        // visited, so neither is tried on later picks.
+        candidates.retain(|name| !tried.contains(name));
+
+        let Some(name) = pick else {
+          break;
+        };
+
+        picks.push(name);
+      }
+
+      let date = start + chrono::Duration::days(day.try_into()?);
+
+      let exhausted = picks.len() < self.per_day;
+
+      if !json {
+        println!(
+          "{date}\t{}",
+          if picks.is_empty() {
+            "-".into()
+          } else {
+            picks.join(", ")
+          }
+        );
+
+        if exhausted {
+          println!("Ran out of candidates after {} days", day + 1);
+        }
+      }
+
+      days.push(serde_json::json!({ "date": date, "picks": picks }));
+
+      if exhausted {
+        break;
+      }
+    }
+
+    if json {
+      return print_json(&serde_json::json!({
+        "days": days,
+        "rejected": rejected,
+      }));
+    }
+
+    for (filter, count) in rejected {
+      println!("{filter}\t{count} rejected");
+    }
+
+    Ok(())
+  }
+}
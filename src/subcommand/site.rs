@@ -0,0 +1,46 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) enum Site {
+  #[clap(about = "Render post history into a static website")]
+  Build {
+    #[clap(long, default_value = "public", help = "Write site to <OUT>")]
+    out: PathBuf,
+    #[clap(long, help = "Absolute URL the site is served from, used in feeds")]
+    base_url: Option<String>,
+    #[clap(
+      long,
+      help = "Show dates in this timezone [default: the configured display \
+              timezone]"
+    )]
+    timezone: Option<Tz>,
+  },
+}
+
+impl Site {
+  pub(crate) fn run(self, json: bool) -> Result {
+    match self {
+      Self::Build {
+        out,
+        base_url,
+        timezone,
+      } => {
+        let config = Config::from_env()?;
+
+        let built = crate::site::build(
+          &Db::open(Some(PathBuf::from(DB_PATH)))?.posts()?,
+          &out,
+          base_url.as_deref(),
+          timezone.unwrap_or_else(|| config.display_timezone()),
+          config.locale,
+        )?;
+
+        if json {
+          print_json(&built)?;
+        }
+
+        Ok(())
+      }
+    }
+  }
+}
@@ -0,0 +1,311 @@
+use {
+  super::*,
+  image::{ImageOutputFormat, Rgb, RgbImage},
+};
+
+const ASCII_WIDTH: u64 = 40;
+const PNG_BAR: u32 = 8;
+const PNG_HEIGHT: u32 = 200;
+const PNG_PADDING: u32 = 10;
+
+#[derive(Debug, Parser)]
+#[clap(args_conflicts_with_subcommands = true)]
+pub(crate) struct Stats {
+  #[clap(subcommand)]
+  command: Option<Command>,
+  #[clap(long, value_enum, help = "Also chart tweets published per day")]
+  chart: Option<Chart>,
+  #[clap(
+    long,
+    default_value = "stats.png",
+    help = "Write the PNG chart to this file"
+  )]
+  output: PathBuf,
+  #[clap(long, help = "Only count activity on or after this date, in UTC")]
+  since: Option<NaiveDate>,
+}
+
+#[derive(Debug, Parser)]
+enum Command {
+  #[clap(about = "Rank posts and keywords by likes, reposts, and replies")]
+  Engagement {
+    #[clap(long, default_value = "10", help = "Show at most this many rows")]
+    limit: usize,
+    #[clap(
+      long,
+      help = "Show dates in this timezone [default: the configured display \
+              timezone]"
+    )]
+    timezone: Option<Tz>,
+  },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+enum Chart {
+  Ascii,
+  Png,
+}
+
+impl Stats {
+  pub(crate) fn run(self, json: bool) -> Result {
+    let db = Db::open(Some(PathBuf::from(DB_PATH)))?;
+
+    match self.command {
+      Some(Command::Engagement { limit, timezone }) => {
+        let timezone = match timezone {
+          Some(timezone) => timezone,
+          None => Config::from_env()?.display_timezone(),
+        };
+
+        engagement(&db, limit, timezone, json)
+      }
+      None => summary(
+        &db,
+        self
+          .since
+          .map(|since| since.and_time(chrono::NaiveTime::MIN).and_utc()),
+        self.chart,
+        &self.output,
+        json,
+      ),
+    }
+  }
+}
+
+fn summary(
+  db: &Db,
+  since: Option<DateTime<Utc>>,
+  chart: Option<Chart>,
+  output: &Path,
+  json: bool,
+) -> Result {
+  let summary = db.summary(since)?;
+
+  let days = match chart {
+    Some(_) => days(&db.published_per_day(since)?, since),
+    None => Vec::new(),
+  };
+
+  if chart == Some(Chart::Png) {
+    fs::write(output, png(&days)?)?;
+  }
+
+  if json {
+    return print_json(&serde_json::json!({
+      "summary": summary,
+      "days": chart.map(|_| days
+        .iter()
+        .map(|(date, published)| serde_json::json!({
+          "date": date.to_string(),
+          "published": published,
+        }))
+        .collect::<Vec<serde_json::Value>>()),
+    }));
+  }
+
+  println!("crates tracked\t{}", summary.tracked);
+  println!("crates visited\t{}", summary.visited);
+  println!("posted\t{}", summary.posted);
+  println!("skipped\t{}", summary.skipped);
+
+  for (platform, posts) in &summary.platforms {
+    println!("posts on {platform}\t{posts}");
+  }
+
+  match summary.engagement {
+    Some(engagement) => println!("average engagement\t{engagement:.1}"),
+    None => println!("average engagement\t-"),
+  }
+
+  match summary.last_sync {
+    Some(date) => println!(
+      "last sync\t{} ({} hours ago)",
+      date.format("%Y-%m-%d %H:%M:%S"),
+      (Utc::now() - date).num_hours()
+    ),
+    None => println!("last sync\tnever"),
+  }
+
+  println!("database size\t{} bytes", Locale::En.number(summary.bytes));
+
+  match chart {
+    Some(Chart::Ascii) => {
+      println!();
+      print!("{}", ascii(&days));
+    }
+    Some(Chart::Png) => println!("Wrote chart to {}", output.display()),
+    None => {}
+  }
+
+  Ok(())
+}
+
+/// Every day from `since`, or the first day with a tweet, through today,
+/// including days without any.
+fn days(
+  published: &[(NaiveDate, u64)],
+  since: Option<DateTime<Utc>>,
+) -> Vec<(NaiveDate, u64)> {
+  let today = Utc::now().date_naive();
+
+  let Some(start) = since
+    .map(|since| since.date_naive())
+    .or_else(|| published.first().map(|(date, _)| *date))
+  else {
+    return Vec::new();
+  };
+
+  let published = published.iter().copied().collect::<BTreeMap<_, _>>();
+
+  start
+    .iter_days()
+    .take_while(|date| *date <= today)
+    .map(|date| (date, published.get(&date).copied().unwrap_or_default()))
+    .collect()
+}
+
+/// A horizontal bar per day, scaled to the busiest one.
+fn ascii(days: &[(NaiveDate, u64)]) -> String {
+  let max = days
+    .iter()
+    .map(|(_, n)| *n)
+    .max()
+    .unwrap_or_default()
+    .max(1);
+
+  days
+    .iter()
+    .map(|(date, n)| {
+      format!(
+        "{date} {:<width$} {n}\n",
+        "#".repeat((n * ASCII_WIDTH / max) as usize),
+        width = ASCII_WIDTH as usize,
+      )
+    })
+    .collect()
+}
+
+/// A vertical bar per day, scaled to the busiest one, as a PNG.
+fn png(days: &[(NaiveDate, u64)]) -> Result<Vec<u8>> {
+  let max = days
+    .iter()
+    .map(|(_, n)| *n)
+    .max()
+    .unwrap_or_default()
+    .max(1);
+
+  let width = PNG_PADDING * 2 + PNG_BAR * u32::try_from(days.len())?.max(1);
+
+  let mut image = RgbImage::from_pixel(
+    width,
+    PNG_HEIGHT + PNG_PADDING * 2,
+    Rgb([0xff, 0xff, 0xff]),
+  );
+
+  for (i, (_, n)) in days.iter().enumerate() {
+    let height = u32::try_from(n * u64::from(PNG_HEIGHT) / max)?;
+
+    let left = PNG_PADDING + u32::try_from(i)? * PNG_BAR;
+
+    // Leave a pixel between bars so adjacent days stay distinguishable.
+    for x in left..left + PNG_BAR - 1 {
+      for y in PNG_PADDING + PNG_HEIGHT - height..PNG_PADDING + PNG_HEIGHT {
+        image.put_pixel(x, y, Rgb([0xde, 0xa5, 0x84]));
+      }
+    }
+  }
+
+  let mut png = Vec::new();
+
+  image.write_to(&mut io::Cursor::new(&mut png), ImageOutputFormat::Png)?;
+
+  Ok(png)
+}
+
+fn engagement(db: &Db, limit: usize, timezone: Tz, json: bool) -> Result {
+  let mut posts = db
+    .posts()?
+    .into_iter()
+    .filter_map(|post| post.engagement.map(|engagement| (post, engagement)))
+    .collect::<Vec<(crate::post::Post, Engagement)>>();
+
+  posts.sort_by_key(|(post, engagement)| {
+    (std::cmp::Reverse(engagement.total()), post.series)
+  });
+
+  let mut keywords = BTreeMap::<&str, (usize, u64)>::new();
+
+  for (post, engagement) in &posts {
+    for keyword in &post.keywords {
+      let (count, total) = keywords.entry(keyword).or_default();
+      *count += 1;
+      *total += engagement.total();
+    }
+  }
+
+  let mut keywords = keywords
+    .into_iter()
+    .map(|(keyword, (count, total))| {
+      (keyword, count, total as f64 / count as f64)
+    })
+    .collect::<Vec<(&str, usize, f64)>>();
+
+  keywords.sort_by(|a, b| b.2.total_cmp(&a.2).then(a.0.cmp(b.0)));
+
+  if json {
+    return print_json(&serde_json::json!({
+      "posts": posts
+        .iter()
+        .take(limit)
+        .map(|(post, engagement)| serde_json::json!({
+          "series": post.series,
+          "name": post.name,
+          "tweet_id": post.tweet_id,
+          "date": post.date.with_timezone(&timezone).to_rfc3339(),
+          "engagement": engagement,
+          "total": engagement.total(),
+        }))
+        .collect::<Vec<serde_json::Value>>(),
+      "keywords": keywords
+        .iter()
+        .take(limit)
+        .map(|(keyword, posts, average)| serde_json::json!({
+          "keyword": keyword,
+          "posts": posts,
+          "average": average,
+        }))
+        .collect::<Vec<serde_json::Value>>(),
+    }));
+  }
+
+  if posts.is_empty() {
+    println!("No engagement recorded yet, set ENGAGEMENT=true");
+    return Ok(());
+  }
+
+  println!("series\tcrate\tfeatured\tlikes\treposts\tquotes\treplies\ttotal");
+
+  for (post, engagement) in posts.iter().take(limit) {
+    println!(
+      "#{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+      post.series,
+      post.name,
+      Locale::En.date(post.date.with_timezone(&timezone).date_naive()),
+      engagement.likes,
+      engagement.reposts,
+      engagement.quotes,
+      engagement.replies,
+      engagement.total()
+    );
+  }
+
+  println!();
+
+  println!("keyword\tposts\taverage");
+
+  for (keyword, posts, average) in keywords.iter().take(limit) {
+    println!("{keyword}\t{posts}\t{average:.1}");
+  }
+
+  Ok(())
+}
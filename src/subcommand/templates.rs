@@ -0,0 +1,194 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) enum Templates {
+  #[clap(about = "Render templates against edge-case crates and report \
+                  posts that are too long or fail to render")]
+  Check {
+    #[clap(
+      long = "template",
+      help = "Also check this template, may be repeated [default: only the \
+              configured template]"
+    )]
+    templates: Vec<String>,
+  },
+}
+
+/// A made-up crate that stresses some part of a template.
+struct Case {
+  description: Option<String>,
+  name: &'static str,
+  owners: Vec<String>,
+  title: &'static str,
+}
+
+impl Case {
+  fn corpus() -> Vec<Self> {
+    vec![
+      Self {
+        description: None,
+        name: "frobnicate",
+        owners: vec!["alice".into()],
+        title: "no description",
+      },
+      Self {
+        description: Some("Frobnicates widgets at scale. ".repeat(100)),
+        name: "frobnicate",
+        owners: vec!["alice".into()],
+        title: "huge description",
+      },
+      Self {
+        description: Some("ウィジェットを処理する 🦀 Ünïcödé wïdgëts".into()),
+        name: "ウィジェット",
+        owners: vec!["田中太郎".into(), "Ólafur".into()],
+        title: "unicode name",
+      },
+      Self {
+        description: Some("Frobnicates widgets".into()),
+        name: "frobnicate",
+        owners: (1..=20).map(|i| format!("maintainer-{i:02}")).collect(),
+        title: "20 owners",
+      },
+      Self {
+        description: Some("Frobnicates widgets".into()),
+        name:
+          "frobnicate-widgets-with-a-crate-name-right-at-the-64-char-limits",
+        owners: vec!["alice".into()],
+        title: "long name",
+      },
+    ]
+  }
+
+  /// Every template field filled in, as `Bot::draft` would for a crate like
+  /// this one.
+  fn fields(&self, config: &Config) -> BTreeMap<&'static str, String> {
+    let mut fields = format::fields(
+      self.name,
+      self.description.as_deref(),
+      &self
+        .owners
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<&str>>(),
+      config.crate_url(self.name),
+    );
+
+    fields.extend([
+      ("advisories", "No known advisories".into()),
+      (
+        "archive",
+        config
+          .archive_url
+          .clone()
+          .unwrap_or_else(|| "https://example.com/archive".into()),
+      ),
+      ("created", "2015-05-15".into()),
+      ("downloads", config.locale.number(123_456_789)),
+      ("edition", "2021".into()),
+      ("msrv", "1.70.0".into()),
+      ("series", "12345".into()),
+      ("updated", "2024-12-31".into()),
+      ("version", "10.20.30-beta.4".into()),
+    ]);
+
+    fields
+  }
+}
+
+impl Templates {
+  pub(crate) fn run(self, json: bool) -> Result {
+    let config = Config::from_env()?;
+
+    match self {
+      Self::Check { templates } => check(&config, &templates, json),
+    }
+  }
+}
+
+fn check(config: &Config, templates: &[String], json: bool) -> Result {
+  let templates = [("configured", Ok(config.template.clone()))]
+    .into_iter()
+    .chain(
+      templates
+        .iter()
+        .map(|template| (template.as_str(), template.parse::<Template>())),
+    )
+    .collect::<Vec<(&str, Result<Template>)>>();
+
+  let corpus = Case::corpus();
+
+  let mut results = Vec::new();
+
+  for (source, template) in &templates {
+    let template = match template {
+      Ok(template) => template,
+      Err(error) => {
+        results.push((*source, "parse", Err(format!("{error:#}"))));
+        continue;
+      }
+    };
+
+    for case in &corpus {
+      let draft = Draft {
+        series: 0,
+        text: compose(
+          template,
+          case.fields(config),
+          &[],
+          &config.hashtags,
+          config.locale,
+        ),
+        thread: Vec::new(),
+        translations: Vec::new(),
+      };
+
+      results.push((
+        *source,
+        case.title,
+        match draft.problem() {
+          Some(problem) => Err(problem),
+          None => Ok(draft.text),
+        },
+      ));
+    }
+  }
+
+  let failures = results
+    .iter()
+    .filter(|(.., result)| result.is_err())
+    .count();
+
+  if json {
+    print_json(&serde_json::json!({
+      "checks": results
+        .iter()
+        .map(|(template, case, result)| serde_json::json!({
+          "template": template,
+          "case": case,
+          "pass": result.is_ok(),
+          "length": result.as_ref().ok().map(|text| format::length(text)),
+          "problem": result.as_ref().err(),
+        }))
+        .collect::<Vec<serde_json::Value>>(),
+      "failures": failures,
+    }))?;
+  } else {
+    for (template, case, result) in &results {
+      match result {
+        Ok(text) => {
+          println!(
+            "{template}\t{case}\tpass: {} characters",
+            format::length(text)
+          );
+        }
+        Err(problem) => println!("{template}\t{case}\tfail: {problem}"),
+      }
+    }
+  }
+
+  if failures > 0 {
+    bail!("{failures} template checks failed");
+  }
+
+  Ok(())
+}
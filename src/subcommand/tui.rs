@@ -0,0 +1,249 @@
+use {
+  super::*,
+  ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    layout::{Constraint, Layout},
+    style::{Modifier, Style},
+    widgets::{Block, List, ListItem, Paragraph, Wrap},
+    DefaultTerminal, Frame,
+  },
+  std::io::{Read, Seek, SeekFrom},
+};
+
+const HELP: &str = "↑/↓ move  enter preview  b ban  q queue  s skip  esc quit";
+const LOG_TAIL: u64 = 16 * 1024;
+const REFRESH: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Parser)]
+pub(crate) struct Tui {
+  #[clap(long, help = "Log file to watch [default: cratebot.log]")]
+  log_file: Option<PathBuf>,
+}
+
+impl Tui {
+  pub(crate) async fn run(self, _json: bool) -> Result {
+    let bot = Bot::new(Config::from_env()?).await?;
+
+    let mut app = App {
+      crates: bot.db.crates(&bot.config.focus)?,
+      log_file: self.log_file.unwrap_or(LOG_PATH.into()),
+      previews: BTreeMap::new(),
+      queued: bot.db.queued()?,
+      selected: 0,
+      status: HELP.into(),
+    };
+
+    let mut terminal = ratatui::init();
+
+    let result = app.run(&bot, &mut terminal).await;
+
+    ratatui::restore();
+
+    result
+  }
+}
+
+struct App {
+  crates: Vec<String>,
+  log_file: PathBuf,
+  previews: BTreeMap<String, String>,
+  queued: Vec<String>,
+  selected: usize,
+  status: String,
+}
+
+impl App {
+  async fn run(&mut self, bot: &Bot, terminal: &mut DefaultTerminal) -> Result {
+    loop {
+      let log = tail(&self.log_file).unwrap_or_else(|error| {
+        format!("{}: {error}", self.log_file.display())
+      });
+
+      terminal.draw(|frame| self.draw(frame, &log))?;
+
+      if !event::poll(REFRESH)? {
+        continue;
+      }
+
+      let Event::Key(key) = event::read()? else {
+        continue;
+      };
+
+      if key.kind != KeyEventKind::Press {
+        continue;
+      }
+
+      let last = self.crates.len().saturating_sub(1);
+
+      match key.code {
+        KeyCode::Esc => return Ok(()),
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+          return Ok(())
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+          self.selected = self.selected.saturating_sub(1);
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+          self.selected = (self.selected + 1).min(last);
+        }
+        KeyCode::PageUp => self.selected = self.selected.saturating_sub(20),
+        KeyCode::PageDown => self.selected = (self.selected + 20).min(last),
+        KeyCode::Home => self.selected = 0,
+        KeyCode::End => self.selected = last,
+        KeyCode::Enter => {
+          if let Some(name) = self.current() {
+            self.status = format!("Rendering post for {name}...");
+            terminal.draw(|frame| self.draw(frame, &log))?;
+
+            let preview = preview(bot, &name)
+              .await
+              .unwrap_or_else(|error| format!("error: {error:#}"));
+
+            self.previews.insert(name, preview);
+            self.status = HELP.into();
+          }
+        }
+        KeyCode::Char('b') => {
+          if let Some(name) = self.current() {
+            bot.db.optout(OptoutKind::Crate, &name)?;
+            self.remove(&format!("Banned {name}"));
+          }
+        }
+        KeyCode::Char('q') => {
+          if let Some(name) = self.current() {
+            if self.queued.contains(&name) {
+              bot.db.dequeue(&name)?;
+              self.queued.retain(|queued| *queued != name);
+              self.status = format!("Removed {name} from the queue");
+            } else {
+              bot.db.enqueue(&name)?;
+              self.status = format!("Queued {name} to be featured next");
+              self.queued.push(name);
+            }
+          }
+        }
+        KeyCode::Char('s') => {
+          if let Some(name) = self.current() {
+            bot.db.update(&name)?;
+            self.remove(&format!("Skipped {name}"));
+          }
+        }
+        _ => {}
+      }
+    }
+  }
+
+  fn current(&self) -> Option<String> {
+    self.crates.get(self.selected).cloned()
+  }
+
+  fn remove(&mut self, status: &str) {
+    let name = self.crates.remove(self.selected);
+    self.queued.retain(|queued| *queued != name);
+    self.selected = self.selected.min(self.crates.len().saturating_sub(1));
+    self.status = status.into();
+  }
+
+  fn draw(&self, frame: &mut Frame, log: &str) {
+    let [main, status] =
+      Layout::vertical([Constraint::Min(0), Constraint::Length(1)])
+        .areas(frame.area());
+
+    let [list, right] =
+      Layout::horizontal([Constraint::Percentage(35), Constraint::Min(0)])
+        .areas(main);
+
+    let [preview, log_area] =
+      Layout::vertical([Constraint::Percentage(40), Constraint::Min(0)])
+        .areas(right);
+
+    // Only the visible window of crates is rendered, since the database can
+    // hold every crate on the registry.
+    let height = usize::from(list.height.saturating_sub(2)).max(1);
+
+    let offset = self.selected.saturating_sub(height - 1);
+
+    let items = self
+      .crates
+      .iter()
+      .enumerate()
+      .skip(offset)
+      .take(height)
+      .map(|(i, name)| {
+        let label = if self.queued.contains(name) {
+          format!("{name} [queued]")
+        } else {
+          name.clone()
+        };
+
+        let item = ListItem::new(label);
+
+        if i == self.selected {
+          item.style(Style::new().add_modifier(Modifier::REVERSED))
+        } else {
+          item
+        }
+      })
+      .collect::<Vec<ListItem>>();
+
+    frame.render_widget(
+      List::new(items).block(
+        Block::bordered()
+          .title(format!("Unvisited crates ({})", self.crates.len())),
+      ),
+      list,
+    );
+
+    let text = self
+      .current()
+      .and_then(|name| self.previews.get(&name).cloned())
+      .unwrap_or_else(|| "Press enter to preview the post".into());
+
+    frame.render_widget(
+      Paragraph::new(text)
+        .block(Block::bordered().title("Preview"))
+        .wrap(Wrap { trim: false }),
+      preview,
+    );
+
+    let lines = log.lines().collect::<Vec<&str>>();
+
+    let visible = usize::from(log_area.height.saturating_sub(2));
+
+    frame.render_widget(
+      Paragraph::new(lines[lines.len().saturating_sub(visible)..].join("\n"))
+        .block(Block::bordered().title("Log")),
+      log_area,
+    );
+
+    frame.render_widget(Paragraph::new(self.status.as_str()), status);
+  }
+}
+
+/// The post that would be published for crate `name` right now, drafted
+/// exactly as the next tick would.
+async fn preview(bot: &Bot, name: &str) -> Result<String> {
+  let full_crate = bot.full_crate(name).await?;
+
+  Ok(bot.draft(&full_crate, bot.db.series()? + 1).await?.text)
+}
+
+/// The end of the log file, starting at a line boundary.
+fn tail(path: &Path) -> Result<String> {
+  let mut file = fs::File::open(path)?;
+
+  let len = file.metadata()?.len();
+
+  file.seek(SeekFrom::Start(len.saturating_sub(LOG_TAIL)))?;
+
+  let mut bytes = Vec::new();
+
+  file.read_to_end(&mut bytes)?;
+
+  let text = String::from_utf8_lossy(&bytes);
+
+  Ok(match text.split_once('\n') {
+    Some((_, rest)) if len > LOG_TAIL => rest.into(),
+    _ => text.into(),
+  })
+}
@@ -0,0 +1,20 @@
+use super::*;
+
+/// Totals over the bot's history, as reported by `cratebot stats`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct Summary {
+  /// Size of the database file, excluding its write-ahead log.
+  pub(crate) bytes: u64,
+  /// Average likes, reposts, quotes, and replies per post with engagement
+  /// recorded.
+  pub(crate) engagement: Option<f64>,
+  pub(crate) last_sync: Option<DateTime<Utc>>,
+  /// Posts on each platform. Every tweet counts for Twitter, while only
+  /// featured crates are cross-posted elsewhere.
+  pub(crate) platforms: BTreeMap<String, u64>,
+  pub(crate) posted: u64,
+  /// Crates rejected by a selection filter.
+  pub(crate) skipped: u64,
+  pub(crate) tracked: u64,
+  pub(crate) visited: u64,
+}
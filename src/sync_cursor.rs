@@ -0,0 +1,53 @@
+use super::*;
+
+/// Where the next sync of a source resumes: the last page fetched, which is
+/// fetched again in case crates were added to it. crates.io returns a seek
+/// token for each page, which unlike a page number stays put when crates
+/// before it are deleted. Registries that return no token fall back to page
+/// numbers.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SyncCursor {
+  pub(crate) page: u64,
+  pub(crate) seek: Option<String>,
+  pub(crate) sort: String,
+}
+
+impl SyncCursor {
+  pub(crate) fn new(page: u64) -> Self {
+    Self {
+      page,
+      seek: None,
+      sort: SYNC_SORT.into(),
+    }
+  }
+
+  /// The cursor for the page after this one, given the `next_page` query
+  /// string the registry returned for this one, like `?seek=abc&per_page=100`.
+  pub(crate) fn next(&self, next_page: Option<&str>) -> Result<Self> {
+    let seek = match next_page {
+      Some(next_page) => reqwest::Url::parse("http://localhost/")?
+        .join(next_page)?
+        .query_pairs()
+        .find(|(key, _)| key == "seek")
+        .map(|(_, seek)| seek.into_owned()),
+      None => None,
+    };
+
+    Ok(Self {
+      page: self.page + 1,
+      seek,
+      sort: self.sort.clone(),
+    })
+  }
+
+  pub(crate) fn query(&self) -> Vec<(&'static str, String)> {
+    let mut query = vec![("sort", self.sort.clone())];
+
+    match &self.seek {
+      Some(seek) => query.push(("seek", seek.clone())),
+      None => query.push(("page", self.page.to_string())),
+    }
+
+    query
+  }
+}
@@ -0,0 +1,87 @@
+use super::*;
+
+/// Notifies systemd when the bot is ready and pings the watchdog from the
+/// scheduler loop, between sync pages, and while waiting out the quota, so
+/// a hung bot gets restarted but a long tick doesn't. Without the `systemd`
+/// feature, or when not running under systemd, every method is a no-op.
+pub(crate) struct Systemd {
+  interval: Option<Duration>,
+  last_ping: Mutex<Instant>,
+}
+
+impl Systemd {
+  pub(crate) fn new() -> Self {
+    Self {
+      interval: Self::watchdog_interval(),
+      last_ping: Mutex::new(Instant::now()),
+    }
+  }
+
+  #[cfg(feature = "systemd")]
+  fn watchdog_interval() -> Option<Duration> {
+    let mut usec = 0;
+
+    sd_notify::watchdog_enabled(false, &mut usec)
+      .then(|| Duration::from_micros(usec) / 2)
+  }
+
+  #[cfg(not(feature = "systemd"))]
+  fn watchdog_interval() -> Option<Duration> {
+    None
+  }
+
+  #[cfg(feature = "systemd")]
+  fn notify(state: sd_notify::NotifyState) -> Result {
+    Ok(sd_notify::notify(false, &[state])?)
+  }
+
+  pub(crate) fn ready(&self) -> Result {
+    #[cfg(feature = "systemd")]
+    Self::notify(sd_notify::NotifyState::Ready)?;
+
+    if let Some(interval) = self.interval {
+      log::info!("Pinging systemd watchdog every {}s", interval.as_secs());
+    }
+
+    Ok(())
+  }
+
+  pub(crate) fn ping(&self) -> Result {
+    let Some(interval) = self.interval else {
+      return Ok(());
+    };
+
+    let mut last_ping = self.last_ping.lock().unwrap();
+
+    if last_ping.elapsed() < interval {
+      return Ok(());
+    }
+
+    #[cfg(feature = "systemd")]
+    Self::notify(sd_notify::NotifyState::Watchdog)?;
+
+    *last_ping = Instant::now();
+
+    Ok(())
+  }
+
+  /// Sleeps for `duration`, waking up to ping the watchdog along the way.
+  pub(crate) async fn sleep(&self, duration: Duration) -> Result {
+    let deadline = Instant::now() + duration;
+
+    loop {
+      self.ping()?;
+
+      let left = deadline.saturating_duration_since(Instant::now());
+
+      if left.is_zero() {
+        return Ok(());
+      }
+
+      tokio::time::sleep(
+        self.interval.map_or(left, |interval| interval.min(left)),
+      )
+      .await;
+    }
+  }
+}
@@ -0,0 +1,198 @@
+use super::*;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+  Field(String, Vec<TemplateFilter>),
+  Group(Vec<Segment>),
+  Text(String),
+}
+
+/// A post template. `{field}` is replaced with the named field, and a
+/// `[bracketed]` group is dropped entirely when any field inside it is empty.
+/// Fields can be piped through filters, e.g. `{downloads|humanize_downloads}`.
+/// Literal braces and brackets are written doubled, e.g. `{{` or `[[`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(try_from = "String")]
+pub(crate) struct Template {
+  segments: Vec<Segment>,
+}
+
+impl Template {
+  pub(crate) const FIELDS: &'static [&'static str] = &[
+    "advisories",
+    "archive",
+    "created",
+    "description",
+    "downloads",
+    "edition",
+    "msrv",
+    "name",
+    "owners",
+    "series",
+    "updated",
+    "url",
+    "version",
+  ];
+
+  pub(crate) fn uses(&self, field: &str) -> bool {
+    fn uses(segments: &[Segment], field: &str) -> bool {
+      segments.iter().any(|segment| match segment {
+        Segment::Field(name, _) => name == field,
+        Segment::Group(segments) => uses(segments, field),
+        Segment::Text(_) => false,
+      })
+    }
+
+    uses(&self.segments, field)
+  }
+
+  /// Renders the template with `fields`, filtered for `locale`.
+  pub(crate) fn render(
+    &self,
+    fields: &BTreeMap<&str, String>,
+    locale: Locale,
+  ) -> String {
+    Self::render_segments(&self.segments, fields, locale).unwrap_or_default()
+  }
+
+  fn render_segments(
+    segments: &[Segment],
+    fields: &BTreeMap<&str, String>,
+    locale: Locale,
+  ) -> Option<String> {
+    let mut output = String::new();
+
+    for segment in segments {
+      match segment {
+        Segment::Field(name, filters) => match fields.get(name.as_str()) {
+          Some(value) if !value.is_empty() => output.push_str(
+            &filters.iter().fold(value.clone(), |value, filter| {
+              filter.apply(&value, locale)
+            }),
+          ),
+          _ => return None,
+        },
+        Segment::Group(segments) => {
+          if let Some(rendered) =
+            Self::render_segments(segments, fields, locale)
+          {
+            output.push_str(&rendered);
+          }
+        }
+        Segment::Text(text) => output.push_str(text),
+      }
+    }
+
+    Some(output)
+  }
+}
+
+impl Default for Template {
+  fn default() -> Self {
+    DEFAULT_TEMPLATE.parse().unwrap()
+  }
+}
+
+impl FromStr for Template {
+  type Err = anyhow::Error;
+
+  fn from_str(s: &str) -> Result<Self> {
+    let mut chars = s.chars().peekable();
+
+    let mut segments = Vec::new();
+
+    let mut group: Option<Vec<Segment>> = None;
+
+    let mut text = String::new();
+
+    while let Some(c) = chars.next() {
+      let escaped =
+        matches!(c, '{' | '}' | '[' | ']') && chars.peek() == Some(&c);
+
+      if escaped {
+        chars.next();
+        text.push(c);
+        continue;
+      }
+
+      let current = group.as_mut().unwrap_or(&mut segments);
+
+      match c {
+        '{' => {
+          let mut name = String::new();
+
+          loop {
+            match chars.next() {
+              Some('}') => break,
+              Some(c) => name.push(c),
+              None => bail!("Unterminated field `{{{name}` in template"),
+            }
+          }
+
+          let mut parts = name.split('|').map(str::trim);
+
+          let name = parts.next().unwrap_or_default().to_string();
+
+          let filters = parts
+            .map(str::parse)
+            .collect::<Result<Vec<TemplateFilter>>>()?;
+
+          if !Self::FIELDS.contains(&name.as_str()) {
+            bail!(
+              "Unknown template field `{name}`, expected one of: {}",
+              Self::FIELDS.join(", ")
+            );
+          }
+
+          if !text.is_empty() {
+            current.push(Segment::Text(mem::take(&mut text)));
+          }
+
+          current.push(Segment::Field(name, filters));
+        }
+        '[' => {
+          if group.is_some() {
+            bail!("Template groups cannot be nested");
+          }
+
+          if !text.is_empty() {
+            segments.push(Segment::Text(mem::take(&mut text)));
+          }
+
+          group = Some(Vec::new());
+        }
+        ']' => {
+          let Some(mut inner) = group.take() else {
+            bail!("Unmatched `]` in template");
+          };
+
+          if !text.is_empty() {
+            inner.push(Segment::Text(mem::take(&mut text)));
+          }
+
+          segments.push(Segment::Group(inner));
+        }
+        '}' => bail!("Unmatched `}}` in template"),
+        c => text.push(c),
+      }
+    }
+
+    if group.is_some() {
+      bail!("Unterminated `[` group in template");
+    }
+
+    if !text.is_empty() {
+      segments.push(Segment::Text(text));
+    }
+
+    Ok(Self { segments })
+  }
+}
+
+impl TryFrom<String> for Template {
+  type Error = anyhow::Error;
+
+  fn try_from(s: String) -> Result<Self> {
+    s.parse()
+  }
+}
@@ -0,0 +1,98 @@
+use super::*;
+
+/// A filter applied to a template field with `{field|filter}`. Filters that
+/// can't make sense of a value leave it unchanged.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum TemplateFilter {
+  HumanizeDownloads,
+  RelativeDate,
+  VersionBadge,
+}
+
+impl TemplateFilter {
+  pub(crate) const ALL: &'static [Self] = &[
+    Self::HumanizeDownloads,
+    Self::RelativeDate,
+    Self::VersionBadge,
+  ];
+
+  pub(crate) fn as_str(self) -> &'static str {
+    match self {
+      Self::HumanizeDownloads => "humanize_downloads",
+      Self::RelativeDate => "relative_date",
+      Self::VersionBadge => "version_badge",
+    }
+  }
+
+  pub(crate) fn apply(self, value: &str, locale: Locale) -> String {
+    match self {
+      Self::HumanizeDownloads => humanize(value),
+      Self::RelativeDate => {
+        relative_date(value, Utc::now().date_naive(), locale)
+      }
+      Self::VersionBadge => version_badge(value),
+    }
+  }
+}
+
+impl FromStr for TemplateFilter {
+  type Err = anyhow::Error;
+
+  fn from_str(s: &str) -> Result<Self> {
+    Self::ALL
+      .iter()
+      .find(|filter| filter.as_str() == s)
+      .copied()
+      .ok_or_else(|| {
+        anyhow!(
+          "Unknown template filter `{s}`, expected one of: {}",
+          Self::ALL
+            .iter()
+            .map(|filter| filter.as_str())
+            .collect::<Vec<&str>>()
+            .join(", ")
+        )
+      })
+  }
+}
+
+/// `1234567` becomes `1.2M`. Digit grouping separators from the locale are
+/// ignored.
+fn humanize(value: &str) -> String {
+  let digits = value
+    .chars()
+    .filter(|c| !matches!(c, ',' | '.' | ' '))
+    .collect::<String>();
+
+  let Ok(n) = digits.parse::<u64>() else {
+    return value.into();
+  };
+
+  for (scale, suffix) in [(1_000_000_000, "B"), (1_000_000, "M"), (1_000, "K")]
+  {
+    if n >= scale {
+      let scaled = format!("{:.1}", n as f64 / scale as f64);
+      return format!("{}{suffix}", scaled.trim_end_matches(".0"));
+    }
+  }
+
+  n.to_string()
+}
+
+/// `2024-01-01` becomes `3 weeks ago` in `locale`, relative to `today`.
+fn relative_date(value: &str, today: NaiveDate, locale: Locale) -> String {
+  match NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+    Ok(date) => locale.ago((today - date).num_days()),
+    Err(_) => value.into(),
+  }
+}
+
+/// `0.3.1` becomes `v0.3.1 (pre-1.0)`, and `2.0.0-rc.1` becomes
+/// `v2.0.0-rc.1 (pre-release)`.
+fn version_badge(value: &str) -> String {
+  match semver::Version::parse(value) {
+    Ok(version) if !version.pre.is_empty() => format!("v{value} (pre-release)"),
+    Ok(version) if version.major == 0 => format!("v{value} (pre-1.0)"),
+    _ => format!("v{value}"),
+  }
+}
@@ -0,0 +1,117 @@
+use {super::*, serde_json::Value};
+
+/// A database for integration tests to seed before a bot is opened with it,
+/// since `Db` itself isn't public.
+pub struct Seed(Db);
+
+impl Seed {
+  pub fn open(path: &Path) -> Result<Self, Error> {
+    Ok(Self(Db::open(Some(path.into()))?))
+  }
+
+  /// A bot with `config` and this database.
+  pub async fn bot(self, config: Config) -> Result<Bot, Error> {
+    Bot::with_db(config, self.0).await
+  }
+
+  /// Flags `name` for review for `reason`, and approves it.
+  pub fn approve(&self, name: &str, reason: &str) -> Result<(), Error> {
+    self.0.flag(name, reason)?;
+
+    Ok(self.0.set_review(name, ReviewStatus::Approved)?)
+  }
+
+  /// Queues `name` to be featured ahead of other candidates.
+  pub fn enqueue(&self, name: &str) -> Result<(), Error> {
+    Ok(self.0.enqueue(name)?)
+  }
+
+  /// Records the crates.io user `owner`, as the registry serves it, as an
+  /// owner of `name`.
+  pub fn owner(&self, owner: &Value, name: &str) -> Result<(), Error> {
+    let owner = User::deserialize(owner).map_err(anyhow::Error::from)?;
+
+    Ok(self.0.upsert_owner(&owner, name)?)
+  }
+
+  /// Overrides what the registry has for `name` when it's posted.
+  pub fn set_override(
+    &self,
+    name: &str,
+    description: Option<&str>,
+    hashtags: Option<&[&str]>,
+    link: Option<&str>,
+  ) -> Result<(), Error> {
+    Ok(self.0.set_override(
+      name,
+      &Override {
+        description: description.map(str::to_owned),
+        hashtags:
+          hashtags.map(|hashtags| {
+            hashtags.iter().map(|&tag| tag.to_owned()).collect()
+          }),
+        link: link.map(str::to_owned),
+        note: None,
+      },
+    )?)
+  }
+
+  /// Resumes the next sync of the default source at `page`, with the seek
+  /// token the registry returned for it, if any.
+  pub fn set_sync_cursor(
+    &self,
+    page: u64,
+    seek: Option<&str>,
+  ) -> Result<(), Error> {
+    Ok(self.0.set_sync_cursor(
+      None,
+      &SyncCursor {
+        seek: seek.map(str::to_owned),
+        ..SyncCursor::new(page)
+      },
+    )?)
+  }
+
+  /// Records a snapshot of the registry taken at `date`.
+  pub fn snapshot(
+    &self,
+    date: DateTime<Utc>,
+    crates: u64,
+    downloads: u64,
+    versions: u64,
+    categories: &[(&str, u64)],
+  ) -> Result<(), Error> {
+    Ok(
+      self.0.record_snapshot(&Snapshot {
+        categories: categories
+          .iter()
+          .map(|&(category, crates)| (category.to_owned(), crates))
+          .collect(),
+        crates,
+        date,
+        downloads,
+        versions,
+      })?,
+    )
+  }
+
+  /// Syncs `crates`, as the registry lists them, as if from the default
+  /// source.
+  pub fn sync(
+    &self,
+    crates: impl IntoIterator<Item = Value>,
+  ) -> Result<(), Error> {
+    let crates = crates
+      .into_iter()
+      .map(Crate::deserialize)
+      .collect::<Result<Vec<Crate>, serde_json::Error>>()
+      .map_err(anyhow::Error::from)?;
+
+    Ok(self.0.sync(crates, None)?)
+  }
+
+  /// Marks `name` as visited, as if it had already been featured.
+  pub fn visit(&self, name: &str) -> Result<(), Error> {
+    Ok(self.0.update(name)?)
+  }
+}
@@ -0,0 +1,82 @@
+use super::*;
+
+/// What to do with a crate whose name is a single edit away from one of the
+/// most downloaded crates, like `serde_jsom` or `tokioo`.
+#[derive(Debug, Default, Clone, Copy, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Typosquat {
+  Off,
+  /// Flag the crate for manual review, and don't feature it until an
+  /// operator approves it.
+  #[default]
+  Review,
+  /// Don't feature the crate.
+  Skip,
+}
+
+impl Typosquat {
+  /// The popular crate `name` could be mistaken for, if it's within one
+  /// insertion, deletion, substitution, or swap of adjacent characters.
+  /// Names are compared after the normalization `safety::lookalike` uses,
+  /// which catches exact lookalikes itself. Popular crates are never
+  /// typosquats of each other.
+  pub(crate) fn target<'a>(
+    name: &str,
+    popular: &'a [String],
+  ) -> Option<&'a str> {
+    let canonical = |name: &str| name.to_lowercase().replace('_', "-");
+
+    if popular
+      .iter()
+      .any(|popular| canonical(popular) == canonical(name))
+    {
+      return None;
+    }
+
+    let normalized = normalize(name);
+
+    popular
+      .iter()
+      .find(|popular| {
+        let other = normalize(popular);
+        other != normalized && distance(&normalized, &other) <= 1
+      })
+      .map(String::as_str)
+  }
+}
+
+fn normalize(name: &str) -> Vec<char> {
+  safety::normalize(name).chars().collect()
+}
+
+/// Optimal string alignment distance between `a` and `b`, which counts a swap
+/// of adjacent characters as a single edit.
+fn distance(a: &[char], b: &[char]) -> usize {
+  // Names whose lengths differ by more than one can't be a single edit apart,
+  // and most popular crates can be ruled out without filling in the table.
+  if a.len().abs_diff(b.len()) > 1 {
+    return 2;
+  }
+
+  let mut rows = vec![(0..=b.len()).collect::<Vec<usize>>()];
+
+  for i in 1..=a.len() {
+    let mut row = vec![i; b.len() + 1];
+
+    for j in 1..=b.len() {
+      let cost = usize::from(a[i - 1] != b[j - 1]);
+
+      row[j] = (rows[i - 1][j] + 1)
+        .min(row[j - 1] + 1)
+        .min(rows[i - 1][j - 1] + cost);
+
+      if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+        row[j] = row[j].min(rows[i - 2][j - 2] + 1);
+      }
+    }
+
+    rows.push(row);
+  }
+
+  rows[a.len()][b.len()]
+}
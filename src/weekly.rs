@@ -0,0 +1,102 @@
+use super::*;
+
+/// A week of registry activity, from the snapshots taken over the course of
+/// it.
+#[derive(Debug, PartialEq)]
+pub(crate) struct Weekly {
+  pub(crate) category: Option<(String, u64)>,
+  pub(crate) crates: u64,
+  pub(crate) versions: u64,
+}
+
+impl Weekly {
+  /// Aggregates `snapshots`, oldest first, against the first of them, or
+  /// `None` if they span less than `WEEKLY_MIN_HISTORY`.
+  pub(crate) fn compute(snapshots: &[Snapshot]) -> Option<Self> {
+    let (base, rest) = snapshots.split_first()?;
+
+    let latest = rest.last()?;
+
+    if (latest.date - base.date).to_std().ok()? < WEEKLY_MIN_HISTORY {
+      return None;
+    }
+
+    Some(Self {
+      category: latest
+        .categories
+        .iter()
+        .filter_map(|(category, crates)| {
+          let growth = crates.checked_sub(*base.categories.get(category)?)?;
+          (growth > 0).then(|| (category.clone(), growth))
+        })
+        .max_by(|(a, a_growth), (b, b_growth)| {
+          a_growth.cmp(b_growth).then(b.cmp(a))
+        }),
+      crates: latest.crates.saturating_sub(base.crates),
+      versions: rest.iter().map(|snapshot| snapshot.versions).sum(),
+    })
+  }
+
+  pub(crate) fn text(&self, locale: Locale) -> String {
+    let mut text = format!(
+      "This week in new crates: {} new crates and {} crate updates published.",
+      locale.number(self.crates),
+      locale.number(self.versions),
+    );
+
+    if let Some((category, growth)) = &self.category {
+      text.push_str(&format!(
+        " Fastest-growing category: {category} (+{} crates).",
+        locale.number(*growth),
+      ));
+    }
+
+    text
+  }
+}
+
+/// Posts a summary of the past week once per week, once there are enough
+/// snapshots to cover it.
+pub(crate) async fn tick(bot: &Bot) -> Result<Option<Published>> {
+  let week = Utc::now().with_timezone(&bot.config.timezone).iso_week();
+
+  let week = format!("{}-W{:02}", week.year(), week.week());
+
+  if bot.db.state(WEEKLY)?.as_deref() == Some(week.as_str()) {
+    return Ok(None);
+  }
+
+  let snapshots = bot
+    .db
+    .snapshots_since(Utc::now() - chrono::Duration::weeks(1))?;
+
+  let Some(weekly) = Weekly::compute(&snapshots) else {
+    log::info!("Not enough snapshots for weekly statistics yet");
+    return Ok(None);
+  };
+
+  let text = compose::tag(
+    &compose::fit(&weekly.text(bot.config.locale)),
+    &[],
+    &bot.config.hashtags,
+  );
+
+  let (id, _) = bot.tweet("weekly", &week, text.clone()).await?;
+
+  bot.db.set_state(WEEKLY, &week)?;
+
+  bot
+    .db
+    .audit(Action::Published, None, &format!("weekly tweet {id}"))?;
+
+  let published = Published {
+    kind: "weekly",
+    name: week,
+    replies: Vec::new(),
+    series: None,
+    text,
+    tweet_id: Some(id),
+  };
+
+  Ok(Some(published))
+}
@@ -0,0 +1,86 @@
+use super::*;
+
+/// Selection weights learned from post engagement. Each keyword and category
+/// of a featured crate is scored by the smoothed ratio of the average
+/// engagement of posts carrying it to the average engagement of all posts, so
+/// a weight of 1 is neutral. Candidates whose keywords are unknown, because
+/// they were never indexed, keep the neutral weight.
+#[derive(Debug, Default)]
+pub(crate) struct Weights {
+  keywords: BTreeMap<String, f64>,
+}
+
+impl Weights {
+  pub(crate) fn load(db: &Db) -> Result<Self> {
+    let mut totals = BTreeMap::<String, (u64, u64)>::new();
+
+    let (mut posts, mut engagement) = (0, 0);
+
+    for post in db.posts()? {
+      let Some(total) = post.engagement.map(Engagement::total) else {
+        continue;
+      };
+
+      posts += 1;
+      engagement += total;
+
+      for keyword in db.keywords(&post.name)? {
+        let (count, sum) = totals.entry(keyword).or_default();
+        *count += 1;
+        *sum += total;
+      }
+    }
+
+    if posts == 0 {
+      return Ok(Self::default());
+    }
+
+    let average = engagement as f64 / posts as f64;
+
+    Ok(Self {
+      keywords: totals
+        .into_iter()
+        .map(|(keyword, (count, sum))| {
+          (keyword, (1.0 + sum as f64 / count as f64) / (1.0 + average))
+        })
+        .collect(),
+    })
+  }
+
+  pub(crate) fn weight(&self, keywords: &[String]) -> f64 {
+    let weights = keywords
+      .iter()
+      .filter_map(|keyword| self.keywords.get(keyword))
+      .collect::<Vec<&f64>>();
+
+    if weights.is_empty() {
+      return 1.0;
+    }
+
+    weights.iter().copied().sum::<f64>() / weights.len() as f64
+  }
+
+  /// Orders `candidates` by weighted random sampling without replacement,
+  /// giving each candidate the sort key `u^(1/w)` for uniform `u`.
+  pub(crate) fn order(
+    &self,
+    candidates: Vec<String>,
+    keywords: &BTreeMap<String, Vec<String>>,
+    rng: &mut impl Rng,
+  ) -> Vec<String> {
+    let mut keyed = candidates
+      .into_iter()
+      .map(|name| {
+        let weight = keywords
+          .get(&name)
+          .map_or(1.0, |keywords| self.weight(keywords));
+
+        (rng.gen::<f64>().powf(1.0 / weight), name)
+      })
+      .collect::<Vec<(f64, String)>>();
+
+    keyed.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    keyed.into_iter().map(|(_, name)| name).collect()
+  }
+}
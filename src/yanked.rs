@@ -0,0 +1,21 @@
+use super::*;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Yanked {
+  pub(crate) available: bool,
+  pub(crate) latest: bool,
+}
+
+impl From<&FullCrate> for Yanked {
+  fn from(full_crate: &FullCrate) -> Self {
+    Self {
+      available: full_crate.versions.iter().any(|version| !version.yanked),
+      latest: full_crate
+        .versions
+        .iter()
+        .max_by_key(|version| version.created_at)
+        .map(|version| version.yanked)
+        .unwrap_or_default(),
+    }
+  }
+}
@@ -0,0 +1,84 @@
+use {cratebot::format, proptest::prelude::*};
+
+fn name() -> impl Strategy<Value = String> {
+  "[a-zA-Z][a-zA-Z0-9_-]{0,63}"
+}
+
+fn description() -> impl Strategy<Value = Option<String>> {
+  let c = prop_oneof![
+    any::<char>(),
+    proptest::char::range('a', 'z'),
+    Just(' '),
+    // control characters
+    proptest::char::range('\u{0}', '\u{1F}'),
+    // Hebrew and Arabic
+    proptest::char::range('\u{0590}', '\u{06FF}'),
+    // bidirectional overrides and isolates
+    proptest::char::range('\u{202A}', '\u{202E}'),
+    proptest::char::range('\u{2066}', '\u{2069}'),
+    // CJK
+    proptest::char::range('\u{4E00}', '\u{9FFF}'),
+    // emoji, joiners, and variation selectors
+    proptest::char::range('\u{1F300}', '\u{1FAFF}'),
+    Just('\u{200D}'),
+    Just('\u{FE0F}'),
+  ];
+
+  proptest::option::of(
+    proptest::collection::vec(c, 0..600).prop_map(String::from_iter),
+  )
+}
+
+fn owners() -> impl Strategy<Value = Vec<String>> {
+  proptest::collection::vec("[a-zA-Z0-9][a-zA-Z0-9-]{0,38}", 0..12)
+}
+
+proptest! {
+  #[test]
+  fn post_fits(
+    name in name(),
+    description in description(),
+    owners in owners(),
+  ) {
+    let post = format::post(
+      &name,
+      description.as_deref(),
+      &owners.iter().map(String::as_str).collect::<Vec<&str>>(),
+    );
+
+    prop_assert!(
+      format::length(&post) <= format::MAX_LENGTH,
+      "post is {} long: {post:?}",
+      format::length(&post),
+    );
+  }
+
+  #[test]
+  fn post_ends_with_crate_url(
+    name in name(),
+    description in description(),
+    owners in owners(),
+  ) {
+    let post = format::post(
+      &name,
+      description.as_deref(),
+      &owners.iter().map(String::as_str).collect::<Vec<&str>>(),
+    );
+
+    let (text, url) = post.rsplit_once('\n').unwrap();
+
+    prop_assert_eq!(url, format!("https://crates.io/crates/{name}"));
+
+    prop_assert!(!text.is_empty());
+
+    prop_assert!(!text.contains(|c: char| c.is_control()));
+  }
+}
+
+#[test]
+fn wide_characters_count_double() {
+  assert_eq!(format::length("abc"), 3);
+  assert_eq!(format::length("日本語"), 6);
+  assert_eq!(format::length("🦀"), 2);
+  assert_eq!(format::length("see https://crates.io/crates/serde"), 27);
+}
@@ -0,0 +1,108 @@
+use {
+  crate::common::*,
+  wiremock::{
+    matchers::{method, path},
+    Mock, ResponseTemplate,
+  },
+};
+
+#[tokio::test]
+async fn tick_syncs_selects_and_posts() {
+  let crates_io = crates_io().await;
+  let twitter = twitter().await;
+
+  let dir = tempfile::tempdir().unwrap();
+
+  let bot = bot(dir.path(), &crates_io, &twitter, &[]).await;
+
+  let report = bot.tick().await.unwrap();
+
+  assert_eq!(report.synced.len(), 1);
+  assert_eq!(report.synced[0].crates, 1);
+  assert_eq!(report.published.len(), 1);
+  assert_eq!(report.published[0].name, "frobnicate");
+  assert_eq!(report.published[0].tweet_id, Some(1001));
+
+  let requests = twitter.received_requests().await.unwrap();
+
+  assert_eq!(requests.len(), 1);
+
+  assert_eq!(
+    status(&requests[0]),
+    format!(
+      "(alice) / frobnicate: Frobnicates widgets\n{}/crates/frobnicate",
+      crates_io.uri()
+    ),
+  );
+
+  assert!(crates_io
+    .received_requests()
+    .await
+    .unwrap()
+    .iter()
+    .all(
+      |request| request.headers.get(&"user-agent".into()).is_some_and(
+        |agent| agent.last().as_str().ends_with("(test@example.com)")
+      )
+    ));
+
+  let db = sqlite::open(dir.path().join("db.sqlite")).unwrap();
+
+  let mut statement = db
+    .prepare("SELECT series, name, tweet_id, text FROM posts")
+    .unwrap();
+
+  assert_eq!(statement.next().unwrap(), sqlite::State::Row);
+  assert_eq!(statement.read::<i64>(0).unwrap(), 1);
+  assert_eq!(statement.read::<String>(1).unwrap(), "frobnicate");
+  assert_eq!(statement.read::<i64>(2).unwrap(), 1001);
+  assert_eq!(statement.read::<String>(3).unwrap(), status(&requests[0]));
+  assert_eq!(statement.next().unwrap(), sqlite::State::Done);
+
+  let mut statement = db
+    .prepare("SELECT kind, name, tweet_id FROM published")
+    .unwrap();
+
+  assert_eq!(statement.next().unwrap(), sqlite::State::Row);
+  assert_eq!(statement.read::<String>(0).unwrap(), "feature");
+  assert_eq!(statement.read::<String>(1).unwrap(), "frobnicate");
+  assert_eq!(statement.read::<i64>(2).unwrap(), 1001);
+  assert_eq!(statement.next().unwrap(), sqlite::State::Done);
+}
+
+#[tokio::test]
+async fn tick_skips_deleted_crate_and_marks_it_gone() {
+  let crates_io = crates_io().await;
+
+  Mock::given(method("GET"))
+    .and(path("/api/v1/crates/ghost"))
+    .respond_with(ResponseTemplate::new(404))
+    .expect(1)
+    .mount(&crates_io)
+    .await;
+
+  let twitter = twitter().await;
+
+  let dir = tempfile::tempdir().unwrap();
+
+  let seed = seed(dir.path());
+
+  // The deleted crate is queued so it's tried before the one that exists.
+  seed.sync([krate("ghost", "")]).unwrap();
+
+  seed.enqueue("ghost").unwrap();
+
+  let bot = seed.bot(config(&crates_io, &twitter, &[])).await.unwrap();
+
+  let report = bot.tick().await.unwrap();
+
+  assert_eq!(report.published[0].name, "frobnicate");
+
+  let db = sqlite::open(dir.path().join("db.sqlite")).unwrap();
+
+  let mut statement = db.prepare("SELECT name FROM gone").unwrap();
+
+  assert_eq!(statement.next().unwrap(), sqlite::State::Row);
+  assert_eq!(statement.read::<String>(0).unwrap(), "ghost");
+  assert_eq!(statement.next().unwrap(), sqlite::State::Done);
+}
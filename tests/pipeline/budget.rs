@@ -0,0 +1,68 @@
+use {
+  crate::common::*,
+  serde_json::json,
+  wiremock::{
+    matchers::{method, path},
+    Mock, MockServer, ResponseTemplate,
+  },
+};
+
+#[tokio::test]
+async fn tick_ends_thread_at_monthly_tweet_cap() {
+  let crates_io = crates_io().await;
+
+  Mock::given(method("GET"))
+    .and(path("/api/v1/crates/frobnicate/1.2.3/readme"))
+    .respond_with(ResponseTemplate::new(200).set_body_string(
+      "<ul>\n<li>Frobnicates <code>widgets</code> quickly</li>\n</ul>\n",
+    ))
+    .mount(&crates_io)
+    .await;
+
+  let twitter = MockServer::start().await;
+
+  Mock::given(method("POST"))
+    .and(path("/1.1/statuses/update.json"))
+    .respond_with(
+      ResponseTemplate::new(200).set_body_json(json!({ "id": 1001 })),
+    )
+    .expect(2)
+    .mount(&twitter)
+    .await;
+
+  let dir = tempfile::tempdir().unwrap();
+
+  let bot = bot(
+    dir.path(),
+    &crates_io,
+    &twitter,
+    &[
+      ("MODE", "deep-dive".to_owned()),
+      ("MONTHLY_TWEET_CAP", "2".to_owned()),
+    ],
+  )
+  .await;
+
+  let report = bot.tick().await.unwrap();
+
+  assert_eq!(report.published.len(), 1);
+  assert_eq!(report.published[0].replies.len(), 1);
+
+  let db = sqlite::open(dir.path().join("db.sqlite")).unwrap();
+
+  let mut statement =
+    db.prepare("SELECT kind, replies FROM published").unwrap();
+
+  assert_eq!(statement.next().unwrap(), sqlite::State::Row);
+  assert_eq!(statement.read::<String>(0).unwrap(), "deep-dive");
+  assert_eq!(statement.read::<i64>(1).unwrap(), 1);
+  assert_eq!(statement.next().unwrap(), sqlite::State::Done);
+
+  let mut statement = db
+    .prepare("SELECT COUNT(*) FROM audit WHERE action = 'budget-exhausted'")
+    .unwrap();
+
+  statement.next().unwrap();
+
+  assert_eq!(statement.read::<i64>(0).unwrap(), 1);
+}
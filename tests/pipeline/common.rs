@@ -0,0 +1,292 @@
+use {
+  cratebot::{testing::Seed, Bot, Config},
+  flate2::{write::GzEncoder, Compression},
+  serde_json::{json, Value},
+  std::path::Path,
+  wiremock::{
+    matchers::{method, path, query_param},
+    Mock, MockServer, Request, ResponseTemplate,
+  },
+};
+
+const DATE: &str = "2022-08-01T00:00:00Z";
+
+pub fn krate(name: &str, description: &str) -> Value {
+  json!({
+    "id": name,
+    "name": name,
+    "description": description,
+    "license": "MIT",
+    "documentation": null,
+    "homepage": null,
+    "repository": format!("https://github.com/example/{name}"),
+    "downloads": 1234,
+    "recent_downloads": 100,
+    "categories": null,
+    "keywords": null,
+    "versions": null,
+    "max_version": "1.2.3",
+    "links": {
+      "owner_team": "",
+      "owner_user": "",
+      "owners": "",
+      "reverse_dependencies": "",
+      "version_downloads": "",
+      "versions": null,
+    },
+    "created_at": DATE,
+    "updated_at": DATE,
+    "exact_match": null,
+  })
+}
+
+/// Body of `/crates/{name}`, with a single version 1.2.3.
+pub fn crate_json(name: &str, description: &str) -> Value {
+  json!({
+    "categories": [],
+    "crate": krate(name, description),
+    "keywords": [],
+    "versions": [{
+      "crate": name,
+      "created_at": DATE,
+      "updated_at": DATE,
+      "dl_path": format!("/api/v1/crates/{name}/1.2.3/download"),
+      "downloads": 1234,
+      "features": {},
+      "id": 1,
+      "num": "1.2.3",
+      "yanked": false,
+      "license": "MIT",
+      "readme_path": null,
+      "links": {
+        "dependencies": "",
+        "version_downloads": "",
+      },
+      "crate_size": null,
+      "published_by": null,
+    }],
+  })
+}
+
+pub fn crate_response(name: &str, description: &str) -> ResponseTemplate {
+  ResponseTemplate::new(200).set_body_json(crate_json(name, description))
+}
+
+pub fn page(crates: &[Value]) -> ResponseTemplate {
+  ResponseTemplate::new(200).set_body_json(json!({
+    "crates": crates,
+    "meta": { "total": crates.len() },
+  }))
+}
+
+/// Fake crates.io serving one crate, `frobnicate`, owned by `alice`, and an
+/// empty advisory database.
+pub async fn crates_io() -> MockServer {
+  let server = MockServer::start().await;
+
+  let name = "frobnicate";
+
+  Mock::given(method("GET"))
+    .and(path("/api/v1/crates"))
+    .and(query_param("page", "1"))
+    .respond_with(page(&[krate(name, "Frobnicates widgets")]))
+    .expect(1)
+    .mount(&server)
+    .await;
+
+  Mock::given(method("GET"))
+    .and(path("/api/v1/crates"))
+    .and(query_param("page", "2"))
+    .respond_with(page(&[]))
+    .expect(1)
+    .mount(&server)
+    .await;
+
+  Mock::given(method("GET"))
+    .and(path("/api/v1/crates"))
+    .and(query_param("user_id", "7"))
+    .respond_with(page(&[krate(name, "Frobnicates widgets")]))
+    .with_priority(1)
+    .mount(&server)
+    .await;
+
+  Mock::given(method("GET"))
+    .and(path(format!("/api/v1/crates/{name}")))
+    .respond_with(crate_response(name, "Frobnicates widgets"))
+    .expect(1)
+    .mount(&server)
+    .await;
+
+  Mock::given(method("GET"))
+    .and(path(format!("/api/v1/crates/{name}/1.2.3/authors")))
+    .respond_with(
+      ResponseTemplate::new(200)
+        .set_body_json(json!({ "meta": { "names": [] } })),
+    )
+    .mount(&server)
+    .await;
+
+  Mock::given(method("GET"))
+    .and(path(format!("/api/v1/crates/{name}/1.2.3/dependencies")))
+    .respond_with(
+      ResponseTemplate::new(200).set_body_json(json!({ "dependencies": [] })),
+    )
+    .mount(&server)
+    .await;
+
+  Mock::given(method("GET"))
+    .and(path(format!("/api/v1/crates/{name}/downloads")))
+    .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+      "version_downloads": [],
+      "meta": { "extra_downloads": [] },
+    })))
+    .mount(&server)
+    .await;
+
+  Mock::given(method("GET"))
+    .and(path(format!("/api/v1/crates/{name}/owners")))
+    .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+      "users": [{
+        "avatar": null,
+        "email": null,
+        "id": 7,
+        "kind": "user",
+        "login": "alice",
+        "name": "Alice",
+        "url": "https://github.com/alice",
+      }],
+    })))
+    .mount(&server)
+    .await;
+
+  Mock::given(method("GET"))
+    .and(path(format!("/api/v1/crates/{name}/reverse_dependencies")))
+    .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+      "dependencies": [],
+      "versions": [],
+      "meta": { "total": 0 },
+    })))
+    .mount(&server)
+    .await;
+
+  mount_advisory_db(&server).await;
+
+  server
+}
+
+/// Serves an advisory database with no advisories at `/advisory-db.tar.gz`.
+pub async fn mount_advisory_db(server: &MockServer) {
+  let mut archive =
+    tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+
+  let mut header = tar::Header::new_gnu();
+  header.set_entry_type(tar::EntryType::Directory);
+  header.set_size(0);
+  header.set_mode(0o755);
+  header.set_cksum();
+
+  archive
+    .append_data(&mut header, "advisory-db-main/crates/", &[][..])
+    .unwrap();
+
+  Mock::given(method("GET"))
+    .and(path("/advisory-db.tar.gz"))
+    .respond_with(
+      ResponseTemplate::new(200)
+        .set_body_bytes(archive.into_inner().unwrap().finish().unwrap()),
+    )
+    .expect(1)
+    .mount(server)
+    .await;
+}
+
+/// Fake Twitter API that accepts exactly one tweet, with ID 1001.
+pub async fn twitter() -> MockServer {
+  let server = MockServer::start().await;
+
+  Mock::given(method("POST"))
+    .and(path("/1.1/statuses/update.json"))
+    .respond_with(
+      ResponseTemplate::new(200).set_body_json(json!({ "id": 1001 })),
+    )
+    .expect(1)
+    .mount(&server)
+    .await;
+
+  server
+}
+
+/// The text of a tweet posted to the fake Twitter API.
+pub fn status(request: &Request) -> String {
+  url::form_urlencoded::parse(&request.body)
+    .find(|(key, _)| key == "status")
+    .map(|(_, value)| value.into_owned())
+    .unwrap()
+}
+
+/// Settings for a bot that syncs from `registry` and posts to `twitter`,
+/// with `vars` set on top of credentials and settings that keep it off the
+/// network.
+pub fn vars(
+  registry: &MockServer,
+  twitter: &MockServer,
+  vars: &[(&str, String)],
+) -> Vec<(String, String)> {
+  [
+    ("ACCESS_TOKEN_KEY", "key".to_owned()),
+    ("ACCESS_TOKEN_SECRET", "secret".to_owned()),
+    (
+      "ADVISORY_DB_URL",
+      format!("{}/advisory-db.tar.gz", registry.uri()),
+    ),
+    ("API_BURST", "100".to_owned()),
+    ("API_RATE", "100".to_owned()),
+    ("CONSUMER_KEY", "key".to_owned()),
+    ("CONSUMER_SECRET", "secret".to_owned()),
+    ("CONTACT", "test@example.com".to_owned()),
+    ("HASHTAGS", String::new()),
+    ("REGISTRY_URL", registry.uri()),
+    ("SAFETY", "false".to_owned()),
+    ("TWITTER_URL", twitter.uri()),
+  ]
+  .iter()
+  .chain(vars)
+  .map(|(key, value)| ((*key).to_owned(), value.clone()))
+  .collect()
+}
+
+/// The config for `vars`. Unlike `Config::from_env`, nothing process-wide is
+/// read or changed, so tests can run in parallel.
+pub fn config(
+  registry: &MockServer,
+  twitter: &MockServer,
+  settings: &[(&str, String)],
+) -> Config {
+  Config::from_vars(vars(registry, twitter, settings), None).unwrap()
+}
+
+/// The database in `dir`, for seeding before the bot is opened with it.
+pub fn seed(dir: &Path) -> Seed {
+  Seed::open(&dir.join("db.sqlite")).unwrap()
+}
+
+/// A bot with the config for `settings` and the database in `dir`.
+pub async fn bot(
+  dir: &Path,
+  registry: &MockServer,
+  twitter: &MockServer,
+  settings: &[(&str, String)],
+) -> Bot {
+  seed(dir)
+    .bot(config(registry, twitter, settings))
+    .await
+    .unwrap()
+}
+
+/// A crate as listed by the registry, for seeding the database.
+pub fn listed(name: &str, downloads: u64, created_at: &str) -> Value {
+  let mut krate = krate(name, "");
+  krate["downloads"] = json!(downloads);
+  krate["created_at"] = json!(created_at);
+  krate
+}
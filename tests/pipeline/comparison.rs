@@ -0,0 +1,121 @@
+use {
+  crate::common::*,
+  serde_json::json,
+  wiremock::{
+    matchers::{method, path, query_param},
+    Mock, MockServer, ResponseTemplate,
+  },
+};
+
+/// A registry with two crates in the same category, and the rest of the API
+/// left unserved.
+async fn comparison_registry() -> MockServer {
+  let server = MockServer::start().await;
+
+  let crates = [("frobnicate", 5000), ("widgetry", 1234)];
+
+  Mock::given(method("GET"))
+    .and(path("/api/v1/crates"))
+    .and(query_param("page", "1"))
+    .respond_with(page(
+      &crates
+        .iter()
+        .map(|(name, downloads)| {
+          let mut krate = krate(name, "Widgets");
+          krate["downloads"] = json!(downloads);
+          krate
+        })
+        .collect::<Vec<_>>(),
+    ))
+    .mount(&server)
+    .await;
+
+  Mock::given(method("GET"))
+    .and(path("/api/v1/crates"))
+    .and(query_param("page", "2"))
+    .respond_with(page(&[]))
+    .mount(&server)
+    .await;
+
+  for (name, downloads) in crates {
+    let mut body = crate_json(name, "Widgets");
+
+    body["crate"]["downloads"] = json!(downloads);
+
+    body["categories"] = json!([{
+      "category": "GUI",
+      "crates_cnt": 2,
+      "created_at": "2022-08-01T00:00:00Z",
+      "description": "Graphical user interfaces",
+      "id": "gui",
+      "slug": "gui",
+    }]);
+
+    Mock::given(method("GET"))
+      .and(path(format!("/api/v1/crates/{name}")))
+      .respond_with(ResponseTemplate::new(200).set_body_json(body))
+      .mount(&server)
+      .await;
+  }
+
+  mount_advisory_db(&server).await;
+
+  server
+}
+
+#[tokio::test]
+async fn tick_compares_crates_in_a_category() {
+  let registry = comparison_registry().await;
+
+  let twitter = MockServer::start().await;
+
+  Mock::given(method("POST"))
+    .and(path("/1.1/statuses/update.json"))
+    .respond_with(
+      ResponseTemplate::new(200).set_body_json(json!({ "id": 1001 })),
+    )
+    .expect(3)
+    .mount(&twitter)
+    .await;
+
+  let dir = tempfile::tempdir().unwrap();
+
+  let bot = bot(
+    dir.path(),
+    &registry,
+    &twitter,
+    &[("MODE", "comparison".to_owned())],
+  )
+  .await;
+
+  let report = bot.tick().await.unwrap();
+
+  assert_eq!(report.published.len(), 1);
+  assert_eq!(report.published[0].kind, "comparison");
+  assert_eq!(report.published[0].name, "gui");
+
+  let posts = twitter
+    .received_requests()
+    .await
+    .unwrap()
+    .iter()
+    .map(status)
+    .collect::<Vec<String>>();
+
+  assert_eq!(
+    posts,
+    [
+      "Comparing gui crates: frobnicate vs widgetry".to_owned(),
+      format!(
+        "frobnicate: 5,000 downloads, last updated 2022-08-01, 0 \
+         dependencies\n{}/crates/frobnicate",
+        registry.uri()
+      ),
+      format!(
+        "widgetry: 1,234 downloads, last updated 2022-08-01, 0 \
+         dependencies\n{}/crates/widgetry",
+        registry.uri()
+      ),
+    ],
+  );
+}
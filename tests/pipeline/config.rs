@@ -0,0 +1,35 @@
+use {crate::common::*, cratebot::Config, std::fs};
+
+#[tokio::test]
+async fn settings_in_config_file_override_environment() {
+  let crates_io = crates_io().await;
+  let twitter = twitter().await;
+
+  let dir = tempfile::tempdir().unwrap();
+
+  let file = dir.path().join("cratebot.toml");
+
+  fs::write(
+    &file,
+    "template = \"New crate {name}\\n{url}\"\nhashtags = [\"rust\", \"crates\"]\n",
+  )
+  .unwrap();
+
+  let config = Config::from_vars(
+    vars(&crates_io, &twitter, &[("TEMPLATE", "{name}".to_owned())]),
+    Some(&file),
+  )
+  .unwrap();
+
+  let bot = seed(dir.path()).bot(config).await.unwrap();
+
+  let report = bot.tick().await.unwrap();
+
+  assert_eq!(
+    report.published[0].text,
+    format!(
+      "New crate frobnicate\n{}/crates/frobnicate\n#rust #crates",
+      crates_io.uri()
+    ),
+  );
+}
@@ -0,0 +1,59 @@
+use {
+  crate::common::*,
+  chrono::Utc,
+  serde_json::json,
+  wiremock::{
+    matchers::{method, path, query_param},
+    Mock, MockServer, ResponseTemplate,
+  },
+};
+
+#[tokio::test]
+async fn tick_drafts_daily_replies_like_featured_posts() {
+  let crates_io = crates_io().await;
+
+  let mut new = krate("frobnicate", "Frobnicates widgets for everyone");
+  new["created_at"] = json!(Utc::now().to_rfc3339());
+
+  Mock::given(method("GET"))
+    .and(path("/api/v1/crates"))
+    .and(query_param("sort", "new"))
+    .respond_with(page(&[new, krate("old", "An old crate")]))
+    .with_priority(1)
+    .expect(1)
+    .mount(&crates_io)
+    .await;
+
+  let twitter = MockServer::start().await;
+
+  Mock::given(method("POST"))
+    .and(path("/1.1/statuses/update.json"))
+    .respond_with(
+      ResponseTemplate::new(200).set_body_json(json!({ "id": 1001 })),
+    )
+    .expect(2)
+    .mount(&twitter)
+    .await;
+
+  let dir = tempfile::tempdir().unwrap();
+
+  let bot = bot(
+    dir.path(),
+    &crates_io,
+    &twitter,
+    &[
+      ("MODE", "daily".to_owned()),
+      ("TEMPLATE", "Crate of the day #{series}: {name}".to_owned()),
+    ],
+  )
+  .await;
+
+  let report = bot.tick().await.unwrap();
+
+  assert_eq!(report.published.len(), 1);
+  assert_eq!(report.published[0].replies.len(), 1);
+
+  let requests = twitter.received_requests().await.unwrap();
+
+  assert_eq!(status(&requests[1]), "Crate of the day #1: frobnicate");
+}
@@ -0,0 +1,49 @@
+use {crate::common::*, wiremock::MockServer};
+
+#[tokio::test]
+async fn open_migrates_legacy_timestamps_to_fixed_width_rfc3339() {
+  let twitter = MockServer::start().await;
+
+  let dir = tempfile::tempdir().unwrap();
+
+  drop(seed(dir.path()));
+
+  // Older versions wrote timestamps in chrono's display format, which `Db`
+  // no longer writes, so they're seeded directly.
+  sqlite::open(dir.path().join("db.sqlite"))
+    .unwrap()
+    .execute(
+      "INSERT INTO posts (series, name, tweet_id, date) VALUES \
+       (1, 'frobnicate', 1001, '2022-08-01 12:00:00.5 UTC'); \
+       INSERT INTO state (key, value) VALUES \
+       ('last_run', '2022-08-01 13:00:00 UTC'), ('series', '1');",
+    )
+    .unwrap();
+
+  drop(bot(dir.path(), &twitter, &twitter, &[]).await);
+
+  let db = sqlite::open(dir.path().join("db.sqlite")).unwrap();
+
+  let mut statement = db.prepare("SELECT date FROM posts").unwrap();
+
+  assert_eq!(statement.next().unwrap(), sqlite::State::Row);
+  assert_eq!(
+    statement.read::<String>(0).unwrap(),
+    "2022-08-01T12:00:00.500000000Z"
+  );
+
+  let mut statement = db
+    .prepare(
+      "SELECT value FROM state WHERE key IN ('last_run', 'series') \
+       ORDER BY key",
+    )
+    .unwrap();
+
+  assert_eq!(statement.next().unwrap(), sqlite::State::Row);
+  assert_eq!(
+    statement.read::<String>(0).unwrap(),
+    "2022-08-01T13:00:00.000000000Z"
+  );
+  assert_eq!(statement.next().unwrap(), sqlite::State::Row);
+  assert_eq!(statement.read::<String>(0).unwrap(), "1");
+}
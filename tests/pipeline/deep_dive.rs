@@ -0,0 +1,76 @@
+use {
+  crate::common::*,
+  serde_json::json,
+  wiremock::{
+    matchers::{method, path},
+    Mock, MockServer, ResponseTemplate,
+  },
+};
+
+#[tokio::test]
+async fn tick_posts_deep_dive_thread() {
+  let crates_io = crates_io().await;
+
+  Mock::given(method("GET"))
+    .and(path("/api/v1/crates/frobnicate/1.2.3/readme"))
+    .respond_with(ResponseTemplate::new(200).set_body_string(
+      "<h1>frobnicate</h1>\n\
+       <ul>\n\
+       <li>Frobnicates <code>widgets</code> quickly</li>\n\
+       <li>No unsafe code</li>\n\
+       </ul>\n\
+       <pre><code class=\"language-rust\">frobnicate::run();</code></pre>\n",
+    ))
+    .mount(&crates_io)
+    .await;
+
+  let twitter = MockServer::start().await;
+
+  Mock::given(method("POST"))
+    .and(path("/1.1/statuses/update.json"))
+    .respond_with(
+      ResponseTemplate::new(200).set_body_json(json!({ "id": 1001 })),
+    )
+    .expect(4)
+    .mount(&twitter)
+    .await;
+
+  let dir = tempfile::tempdir().unwrap();
+
+  let bot = bot(
+    dir.path(),
+    &crates_io,
+    &twitter,
+    &[("MODE", "deep-dive".to_owned())],
+  )
+  .await;
+
+  let report = bot.tick().await.unwrap();
+
+  assert_eq!(report.published.len(), 1);
+  assert_eq!(report.published[0].kind, "deep-dive");
+  assert_eq!(report.published[0].replies.len(), 3);
+
+  let posts = twitter
+    .received_requests()
+    .await
+    .unwrap()
+    .iter()
+    .map(status)
+    .collect::<Vec<String>>();
+
+  assert_eq!(
+    posts,
+    [
+      format!(
+        "Deep dive: frobnicate 1.2.3 by alice\nFrobnicates widgets\n{}/crates/frobnicate",
+        crates_io.uri()
+      ),
+      "Key features:\n- Frobnicates widgets quickly\n- No unsafe code".into(),
+      "Example from the frobnicate README:\nfrobnicate::run();".into(),
+      "Maintenance: 1,234 downloads and 0 dependents. First published \
+       2022-08-01, last updated 2022-08-01.\nhttps://github.com/example/frobnicate"
+        .into(),
+    ],
+  );
+}
@@ -0,0 +1,59 @@
+use {
+  crate::common::*,
+  wiremock::{
+    matchers::{method, path},
+    Mock,
+  },
+};
+
+#[tokio::test]
+async fn tick_skips_candidate_whose_post_is_too_long() {
+  let crates_io = crates_io().await;
+
+  let long = "a".repeat(60);
+
+  Mock::given(method("GET"))
+    .and(path(format!("/api/v1/crates/{long}")))
+    .respond_with(crate_response(&long, "Widgets"))
+    .expect(1)
+    .mount(&crates_io)
+    .await;
+
+  let twitter = twitter().await;
+
+  let dir = tempfile::tempdir().unwrap();
+
+  let seed = seed(dir.path());
+
+  // The crate with the long name is queued so it's drafted first.
+  seed.sync([krate(&long, "")]).unwrap();
+
+  seed.enqueue(&long).unwrap();
+
+  let bot = seed
+    .bot(config(
+      &crates_io,
+      &twitter,
+      &[("TEMPLATE", "{name} {name} {name} {name} {name}".to_owned())],
+    ))
+    .await
+    .unwrap();
+
+  let report = bot.tick().await.unwrap();
+
+  assert_eq!(report.published[0].name, "frobnicate");
+
+  let db = sqlite::open(dir.path().join("db.sqlite")).unwrap();
+
+  let mut statement = db
+    .prepare("SELECT detail FROM audit WHERE action = 'rejected' AND name = ?")
+    .unwrap()
+    .bind(1, long.as_str())
+    .unwrap();
+
+  assert_eq!(statement.next().unwrap(), sqlite::State::Row);
+  assert_eq!(
+    statement.read::<String>(0).unwrap(),
+    "post is 304 characters, over the 280 limit"
+  );
+}
@@ -0,0 +1,41 @@
+use {
+  crate::common::*,
+  cratebot::Error,
+  wiremock::{
+    matchers::{method, path},
+    Mock, MockServer, ResponseTemplate,
+  },
+};
+
+#[tokio::test]
+async fn tick_does_not_report_deny_feed_failure_as_registry_error() {
+  let crates_io = crates_io().await;
+
+  let feed = MockServer::start().await;
+
+  Mock::given(method("GET"))
+    .and(path("/deny.txt"))
+    .respond_with(ResponseTemplate::new(503))
+    .expect(1)
+    .mount(&feed)
+    .await;
+
+  let twitter = MockServer::start().await;
+
+  let dir = tempfile::tempdir().unwrap();
+
+  let bot = bot(
+    dir.path(),
+    &crates_io,
+    &twitter,
+    &[
+      ("DENY_FEED", format!("{}/deny.txt", feed.uri())),
+      ("SAFETY", "true".to_owned()),
+    ],
+  )
+  .await;
+
+  let error = bot.tick().await.unwrap_err();
+
+  assert!(matches!(error, Error::Other(_)), "{error}");
+}
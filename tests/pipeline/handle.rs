@@ -0,0 +1,64 @@
+use {
+  crate::common::*,
+  serde_json::json,
+  wiremock::{
+    matchers::{method, path},
+    Mock, MockServer, ResponseTemplate,
+  },
+};
+
+#[tokio::test]
+async fn tick_credits_owners_by_discovered_handle() {
+  let crates_io = crates_io().await;
+  let twitter = twitter().await;
+
+  let github = MockServer::start().await;
+
+  Mock::given(method("GET"))
+    .and(path("/users/alice"))
+    .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+      "bio": "Rustacean, also at @alice@hachyderm.io.",
+      "blog": "",
+      "twitter_username": "alice_rs",
+    })))
+    .expect(1)
+    .mount(&github)
+    .await;
+
+  let dir = tempfile::tempdir().unwrap();
+
+  let bot = bot(
+    dir.path(),
+    &crates_io,
+    &twitter,
+    &[
+      ("GITHUB_URL", github.uri()),
+      ("HANDLE_DISCOVERY", "true".to_owned()),
+    ],
+  )
+  .await;
+
+  bot.tick().await.unwrap();
+
+  let requests = twitter.received_requests().await.unwrap();
+
+  assert_eq!(
+    status(&requests[0]),
+    format!(
+      "(@alice_rs) / frobnicate: Frobnicates widgets\n{}/crates/frobnicate",
+      crates_io.uri()
+    ),
+  );
+
+  let db = sqlite::open(dir.path().join("db.sqlite")).unwrap();
+
+  let mut statement = db
+    .prepare("SELECT login, handle, fediverse FROM handles")
+    .unwrap();
+
+  assert_eq!(statement.next().unwrap(), sqlite::State::Row);
+  assert_eq!(statement.read::<String>(0).unwrap(), "alice");
+  assert_eq!(statement.read::<String>(1).unwrap(), "alice_rs");
+  assert_eq!(statement.read::<String>(2).unwrap(), "@alice@hachyderm.io");
+  assert_eq!(statement.next().unwrap(), sqlite::State::Done);
+}
@@ -0,0 +1,68 @@
+use {
+  crate::common::*,
+  serde_json::Value,
+  std::fs,
+  wiremock::{
+    matchers::{method, path},
+    Mock, MockServer, ResponseTemplate,
+  },
+};
+
+#[tokio::test]
+async fn tick_runs_selected_and_published_hooks() {
+  let crates_io = crates_io().await;
+  let twitter = twitter().await;
+
+  let webhook = MockServer::start().await;
+
+  Mock::given(method("POST"))
+    .and(path("/hook"))
+    .respond_with(ResponseTemplate::new(204))
+    .expect(1)
+    .mount(&webhook)
+    .await;
+
+  let dir = tempfile::tempdir().unwrap();
+
+  let bot = bot(
+    dir.path(),
+    &crates_io,
+    &twitter,
+    &[
+      (
+        "HOOK_ON_SELECTED",
+        format!(
+          "cd '{}' && echo $CRATEBOT_EVENT > event && cat > selected.json",
+          dir.path().display()
+        ),
+      ),
+      ("HOOK_ON_PUBLISHED", format!("{}/hook", webhook.uri())),
+    ],
+  )
+  .await;
+
+  bot.tick().await.unwrap();
+
+  assert_eq!(
+    fs::read_to_string(dir.path().join("event")).unwrap(),
+    "selected\n"
+  );
+
+  let selected = serde_json::from_str::<Value>(
+    &fs::read_to_string(dir.path().join("selected.json")).unwrap(),
+  )
+  .unwrap();
+
+  assert_eq!(selected["event"], "selected");
+  assert_eq!(selected["name"], "frobnicate");
+  assert_eq!(selected["version"], "1.2.3");
+
+  let requests = webhook.received_requests().await.unwrap();
+
+  let published = serde_json::from_slice::<Value>(&requests[0].body).unwrap();
+
+  assert_eq!(published["event"], "published");
+  assert_eq!(published["kind"], "feature");
+  assert_eq!(published["name"], "frobnicate");
+  assert_eq!(published["tweet_id"], 1001);
+}
@@ -0,0 +1,103 @@
+use {
+  crate::common::*,
+  wiremock::{
+    matchers::{header, method, path, query_param},
+    Mock, MockServer, ResponseTemplate,
+  },
+};
+
+const TOKEN: &str = "cio0123456789";
+
+/// A registry that requires a token, and only serves crate listings, crate
+/// metadata, and its sparse index.
+async fn private_registry() -> MockServer {
+  let server = MockServer::start().await;
+
+  let name = "acme-widgets";
+
+  Mock::given(method("GET"))
+    .and(path("/api/v1/crates"))
+    .and(query_param("page", "1"))
+    .and(header("authorization", TOKEN))
+    .respond_with(page(&[krate(name, "Internal widget toolkit")]))
+    .mount(&server)
+    .await;
+
+  Mock::given(method("GET"))
+    .and(path("/api/v1/crates"))
+    .and(query_param("page", "2"))
+    .and(header("authorization", TOKEN))
+    .respond_with(page(&[]))
+    .mount(&server)
+    .await;
+
+  Mock::given(method("GET"))
+    .and(path(format!("/api/v1/crates/{name}")))
+    .and(header("authorization", TOKEN))
+    .respond_with(crate_response(name, "Internal widget toolkit"))
+    .mount(&server)
+    .await;
+
+  Mock::given(method("GET"))
+    .and(path(format!("/index/ac/me/{name}")))
+    .and(header("authorization", TOKEN))
+    .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+      "{{\"name\":\"{name}\",\"vers\":\"1.2.2\",\"deps\":[],\"yanked\":false}}\n\
+       {{\"name\":\"{name}\",\"vers\":\"1.2.3\",\"deps\":[{{\"name\":\"serde\",\
+       \"req\":\"^1\",\"features\":[],\"optional\":false,\
+       \"default_features\":true,\"target\":null,\"kind\":\"normal\"}}],\
+       \"yanked\":false}}\n"
+    )))
+    .expect(1)
+    .mount(&server)
+    .await;
+
+  mount_advisory_db(&server).await;
+
+  server
+}
+
+#[tokio::test]
+async fn tick_posts_from_private_registry() {
+  let registry = private_registry().await;
+  let twitter = twitter().await;
+
+  let dir = tempfile::tempdir().unwrap();
+
+  let bot = bot(
+    dir.path(),
+    &registry,
+    &twitter,
+    &[
+      ("INDEX_URL", format!("sparse+{}/index/", registry.uri())),
+      ("REGISTRY_TOKEN", TOKEN.to_owned()),
+    ],
+  )
+  .await;
+
+  let report = bot.tick().await.unwrap();
+
+  assert_eq!(report.published.len(), 1);
+  assert_eq!(report.published[0].name, "acme-widgets");
+
+  let requests = twitter.received_requests().await.unwrap();
+
+  assert_eq!(
+    status(&requests[0]),
+    format!(
+      "acme-widgets: Internal widget toolkit\n{}/crates/acme-widgets",
+      registry.uri()
+    ),
+  );
+
+  assert!(registry
+    .received_requests()
+    .await
+    .unwrap()
+    .iter()
+    .filter(|request| request.url.path() != "/advisory-db.tar.gz")
+    .all(|request| request
+      .headers
+      .get(&"authorization".into())
+      .is_some_and(|token| token.last().as_str() == TOKEN)));
+}
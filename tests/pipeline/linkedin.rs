@@ -0,0 +1,185 @@
+use {
+  crate::common::*,
+  chrono::{Datelike, Utc},
+  serde_json::{json, Value},
+  wiremock::{
+    matchers::{body_string_contains, header, method, path},
+    Mock, MockServer, ResponseTemplate,
+  },
+};
+
+#[tokio::test]
+async fn tick_posts_to_linkedin_refreshing_revoked_token() {
+  let crates_io = crates_io().await;
+  let twitter = twitter().await;
+
+  let linkedin = MockServer::start().await;
+
+  for token in ["revoked", "fresh"] {
+    Mock::given(method("POST"))
+      .and(path("/oauth/v2/accessToken"))
+      .and(body_string_contains("grant_type=refresh_token"))
+      .and(body_string_contains("refresh_token=refresh"))
+      .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+        "access_token": token,
+        "expires_in": 5_184_000,
+      })))
+      .up_to_n_times(1)
+      .expect(1)
+      .mount(&linkedin)
+      .await;
+  }
+
+  Mock::given(method("POST"))
+    .and(path("/v2/ugcPosts"))
+    .and(header("authorization", "Bearer revoked"))
+    .respond_with(ResponseTemplate::new(401))
+    .expect(1)
+    .mount(&linkedin)
+    .await;
+
+  Mock::given(method("POST"))
+    .and(path("/v2/ugcPosts"))
+    .and(header("authorization", "Bearer fresh"))
+    .respond_with(
+      ResponseTemplate::new(201)
+        .insert_header("x-restli-id", "urn:li:share:42"),
+    )
+    .expect(1)
+    .mount(&linkedin)
+    .await;
+
+  let dir = tempfile::tempdir().unwrap();
+
+  let bot = bot(
+    dir.path(),
+    &crates_io,
+    &twitter,
+    &[
+      ("LINKEDIN_API_URL", linkedin.uri()),
+      ("LINKEDIN_AUTH_URL", linkedin.uri()),
+      ("LINKEDIN_CLIENT_ID", "client".to_owned()),
+      ("LINKEDIN_CLIENT_SECRET", "secret".to_owned()),
+      ("LINKEDIN_ORGANIZATION", "1337".to_owned()),
+      ("LINKEDIN_REFRESH_TOKEN", "refresh".to_owned()),
+    ],
+  )
+  .await;
+
+  let report = bot.tick().await.unwrap();
+
+  let post = linkedin
+    .received_requests()
+    .await
+    .unwrap()
+    .into_iter()
+    .rfind(|request| request.url.path() == "/v2/ugcPosts")
+    .unwrap()
+    .body_json::<Value>()
+    .unwrap();
+
+  assert_eq!(post["author"], "urn:li:organization:1337");
+  assert_eq!(
+    post["specificContent"]["com.linkedin.ugc.ShareContent"]["shareCommentary"]
+      ["text"],
+    report.published[0].text.as_str(),
+  );
+
+  let db = sqlite::open(dir.path().join("db.sqlite")).unwrap();
+
+  let mut statement = db.prepare("SELECT linkedin_id FROM posts").unwrap();
+
+  assert_eq!(statement.next().unwrap(), sqlite::State::Row);
+  assert_eq!(statement.read::<String>(0).unwrap(), "urn:li:share:42");
+}
+
+#[tokio::test]
+async fn tick_cross_posts_on_this_day_to_linkedin() {
+  let crates_io = crates_io().await;
+
+  let twitter = MockServer::start().await;
+
+  Mock::given(method("POST"))
+    .and(path("/1.1/statuses/update.json"))
+    .respond_with(
+      ResponseTemplate::new(200).set_body_json(json!({ "id": 1001 })),
+    )
+    .expect(2)
+    .mount(&twitter)
+    .await;
+
+  let linkedin = MockServer::start().await;
+
+  Mock::given(method("POST"))
+    .and(path("/oauth/v2/accessToken"))
+    .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+      "access_token": "token",
+      "expires_in": 5_184_000,
+    })))
+    .expect(1)
+    .mount(&linkedin)
+    .await;
+
+  Mock::given(method("POST"))
+    .and(path("/v2/ugcPosts"))
+    .respond_with(
+      ResponseTemplate::new(201)
+        .insert_header("x-restli-id", "urn:li:share:42"),
+    )
+    .expect(2)
+    .mount(&linkedin)
+    .await;
+
+  let dir = tempfile::tempdir().unwrap();
+
+  let seed = seed(dir.path());
+
+  let today = Utc::now().date_naive();
+
+  seed
+    .sync([listed(
+      "serde",
+      5_000_000,
+      &format!("{}-{}T12:00:00Z", today.year() - 8, today.format("%m-%d")),
+    )])
+    .unwrap();
+
+  seed.visit("serde").unwrap();
+
+  let bot = seed
+    .bot(config(
+      &crates_io,
+      &twitter,
+      &[
+        ("LINKEDIN_API_URL", linkedin.uri()),
+        ("LINKEDIN_AUTH_URL", linkedin.uri()),
+        ("LINKEDIN_CLIENT_ID", "client".to_owned()),
+        ("LINKEDIN_CLIENT_SECRET", "secret".to_owned()),
+        ("LINKEDIN_ORGANIZATION", "1337".to_owned()),
+        ("LINKEDIN_REFRESH_TOKEN", "refresh".to_owned()),
+        ("ON_THIS_DAY", "true".to_owned()),
+      ],
+    ))
+    .await
+    .unwrap();
+
+  let report = bot.tick().await.unwrap();
+
+  assert_eq!(report.published[1].kind, "on-this-day");
+
+  let post = linkedin
+    .received_requests()
+    .await
+    .unwrap()
+    .into_iter()
+    .rfind(|request| request.url.path() == "/v2/ugcPosts")
+    .unwrap()
+    .body_json::<Value>()
+    .unwrap();
+
+  assert_eq!(
+    post["specificContent"]["com.linkedin.ugc.ShareContent"]["shareCommentary"]
+      ["text"],
+    report.published[1].text.as_str(),
+  );
+}
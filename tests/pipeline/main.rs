@@ -0,0 +1,25 @@
+mod bot;
+mod budget;
+mod common;
+mod comparison;
+mod config;
+mod daily;
+mod db;
+mod deep_dive;
+mod draft;
+mod error;
+mod handle;
+mod hook;
+mod index;
+mod linkedin;
+mod moderation;
+mod nostr;
+mod on_this_day;
+mod overrides;
+mod ranking;
+mod script;
+mod spotlight;
+mod sync_cursor;
+mod typosquat;
+mod weekly;
+mod yanked;
@@ -0,0 +1,27 @@
+use crate::common::*;
+
+#[tokio::test]
+async fn tick_drops_description_with_blocked_word() {
+  let crates_io = crates_io().await;
+  let twitter = twitter().await;
+
+  let dir = tempfile::tempdir().unwrap();
+
+  let bot = bot(
+    dir.path(),
+    &crates_io,
+    &twitter,
+    &[("MODERATION_WORDS", "gadgets,Widgets".to_owned())],
+  )
+  .await;
+
+  let report = bot.tick().await.unwrap();
+
+  assert_eq!(
+    report.published[0].text,
+    format!(
+      "(alice) / frobnicate\n{}/crates/frobnicate",
+      crates_io.uri()
+    ),
+  );
+}
@@ -0,0 +1,230 @@
+use {
+  crate::common::*,
+  futures::{SinkExt, StreamExt},
+  k256::schnorr::{Signature, VerifyingKey},
+  serde_json::{json, Value},
+  sha2::{Digest, Sha256},
+  std::sync::{Arc, Mutex},
+  tokio::net::TcpListener,
+  tokio_tungstenite::tungstenite::Message,
+  wiremock::{
+    matchers::{method, path},
+    Mock, MockServer, ResponseTemplate,
+  },
+};
+
+fn unhex(hex: &str) -> Vec<u8> {
+  (0..hex.len())
+    .step_by(2)
+    .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+    .collect()
+}
+
+/// A relay that asks for the first note to be sent again later, and accepts
+/// every note after that.
+async fn relay(events: Arc<Mutex<Vec<Value>>>) -> String {
+  let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+
+  let url = format!("ws://{}", listener.local_addr().unwrap());
+
+  tokio::spawn(async move {
+    loop {
+      let (stream, _) = listener.accept().await.unwrap();
+
+      let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+
+      let Some(Ok(Message::Text(text))) = socket.next().await else {
+        continue;
+      };
+
+      let (kind, event) =
+        serde_json::from_str::<(String, Value)>(&text).unwrap();
+
+      assert_eq!(kind, "EVENT");
+
+      let first = {
+        let mut events = events.lock().unwrap();
+        events.push(event.clone());
+        events.len() == 1
+      };
+
+      let reply = if first {
+        json!(["OK", event["id"], false, "rate-limited: slow down"])
+      } else {
+        json!(["OK", event["id"], true, ""])
+      };
+
+      socket
+        .send(Message::Text(json!(["NOTICE", "hello"]).to_string()))
+        .await
+        .unwrap();
+
+      socket.send(Message::Text(reply.to_string())).await.unwrap();
+    }
+  });
+
+  url
+}
+
+#[tokio::test]
+async fn tick_publishes_signed_note_to_relay() {
+  let crates_io = crates_io().await;
+  let twitter = twitter().await;
+
+  let events = Arc::new(Mutex::new(Vec::new()));
+
+  let relay = relay(events.clone()).await;
+
+  let dir = tempfile::tempdir().unwrap();
+
+  let bot = bot(
+    dir.path(),
+    &crates_io,
+    &twitter,
+    &[("NOSTR_KEY", "01".repeat(32)), ("NOSTR_RELAYS", relay)],
+  )
+  .await;
+
+  let report = bot.tick().await.unwrap();
+
+  let events = events.lock().unwrap().clone();
+
+  assert_eq!(events.len(), 2);
+
+  let event = &events[1];
+
+  assert_eq!(event["kind"], 1);
+  assert_eq!(event["content"], report.published[0].text.as_str());
+
+  let id = Sha256::digest(
+    serde_json::to_string(&json!([
+      0,
+      event["pubkey"],
+      event["created_at"],
+      1,
+      [],
+      event["content"],
+    ]))
+    .unwrap(),
+  );
+
+  assert_eq!(unhex(event["id"].as_str().unwrap()), id.as_slice());
+
+  VerifyingKey::from_bytes(&unhex(event["pubkey"].as_str().unwrap()))
+    .unwrap()
+    .verify_raw(
+      &id,
+      &Signature::try_from(unhex(event["sig"].as_str().unwrap()).as_slice())
+        .unwrap(),
+    )
+    .unwrap();
+
+  let db = sqlite::open(dir.path().join("db.sqlite")).unwrap();
+
+  let mut statement = db
+    .prepare("SELECT nostr_id, nostr_error FROM posts")
+    .unwrap();
+
+  assert_eq!(statement.next().unwrap(), sqlite::State::Row);
+  assert_eq!(
+    statement.read::<String>(0).unwrap(),
+    event["id"].as_str().unwrap()
+  );
+  assert_eq!(statement.read::<Option<String>>(1).unwrap(), None);
+}
+
+#[tokio::test]
+async fn tick_publishes_to_relay_when_twitter_is_down() {
+  let crates_io = crates_io().await;
+
+  let twitter = MockServer::start().await;
+
+  Mock::given(method("POST"))
+    .and(path("/1.1/statuses/update.json"))
+    .respond_with(ResponseTemplate::new(503))
+    .expect(1)
+    .mount(&twitter)
+    .await;
+
+  let events = Arc::new(Mutex::new(Vec::new()));
+
+  let relay = relay(events.clone()).await;
+
+  let dir = tempfile::tempdir().unwrap();
+
+  let bot = bot(
+    dir.path(),
+    &crates_io,
+    &twitter,
+    &[("NOSTR_KEY", "01".repeat(32)), ("NOSTR_RELAYS", relay)],
+  )
+  .await;
+
+  let report = bot.tick().await.unwrap();
+
+  assert_eq!(report.published.len(), 1);
+  assert_eq!(report.published[0].tweet_id, None);
+  assert!(report.published[0].replies.is_empty());
+
+  assert_eq!(events.lock().unwrap().len(), 2);
+
+  let db = sqlite::open(dir.path().join("db.sqlite")).unwrap();
+
+  let mut statement = db
+    .prepare(
+      "SELECT tweet_id, twitter_error, twitter_attempts, nostr_id FROM posts",
+    )
+    .unwrap();
+
+  assert_eq!(statement.next().unwrap(), sqlite::State::Row);
+  assert_eq!(statement.read::<Option<i64>>(0).unwrap(), None);
+  assert!(statement.read::<Option<String>>(1).unwrap().is_some());
+  assert_eq!(statement.read::<i64>(2).unwrap(), 1);
+  assert!(statement.read::<Option<String>>(3).unwrap().is_some());
+}
+
+#[tokio::test]
+async fn tick_publishes_note_in_relay_locale() {
+  let crates_io = crates_io().await;
+  let twitter = twitter().await;
+
+  let events = Arc::new(Mutex::new(Vec::new()));
+
+  let relay = relay(events.clone()).await;
+
+  let dir = tempfile::tempdir().unwrap();
+
+  let bot = bot(
+    dir.path(),
+    &crates_io,
+    &twitter,
+    &[
+      ("NOSTR_KEY", "01".repeat(32)),
+      ("NOSTR_LOCALE", "de".to_owned()),
+      ("NOSTR_RELAYS", relay),
+    ],
+  )
+  .await;
+
+  let report = bot.tick().await.unwrap();
+
+  let events = events.lock().unwrap().clone();
+
+  assert!(report.published[0]
+    .text
+    .starts_with("(alice) / frobnicate: "));
+
+  let url = report.published[0].text.lines().last().unwrap();
+
+  assert_eq!(
+    events[1]["content"],
+    format!("frobnicate von alice: Frobnicates widgets\n{url}"),
+  );
+
+  let db = sqlite::open(dir.path().join("db.sqlite")).unwrap();
+
+  let mut statement = db.prepare("SELECT nostr_text FROM posts").unwrap();
+
+  assert_eq!(statement.next().unwrap(), sqlite::State::Row);
+  assert_eq!(statement.read::<String>(0).unwrap(), events[1]["content"]);
+}
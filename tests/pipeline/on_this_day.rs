@@ -0,0 +1,82 @@
+use {
+  crate::common::*,
+  chrono::{Datelike, Utc},
+  serde_json::json,
+  wiremock::{
+    matchers::{method, path},
+    Mock, MockServer, ResponseTemplate,
+  },
+};
+
+#[tokio::test]
+async fn tick_posts_crates_first_published_on_this_day() {
+  let crates_io = crates_io().await;
+
+  let twitter = MockServer::start().await;
+
+  Mock::given(method("POST"))
+    .and(path("/1.1/statuses/update.json"))
+    .respond_with(
+      ResponseTemplate::new(200).set_body_json(json!({ "id": 1001 })),
+    )
+    .expect(2)
+    .mount(&twitter)
+    .await;
+
+  let dir = tempfile::tempdir().unwrap();
+
+  let seed = seed(dir.path());
+
+  let today = Utc::now().date_naive();
+
+  // Whole leap years back, so the day exists even on February 29th.
+  let created = |years: i32| {
+    format!(
+      "{}-{}T12:00:00Z",
+      today.year() - years,
+      today.format("%m-%d")
+    )
+  };
+
+  let crates = [
+    ("serde", 5_000_000, 8),
+    ("rand", 3_000_000, 4),
+    ("obscure", 10, 4),
+    ("fresh", 9_000_000, 0),
+  ];
+
+  seed
+    .sync(crates.iter().map(|(name, downloads, years)| {
+      listed(name, *downloads, &created(*years))
+    }))
+    .unwrap();
+
+  // Already featured, so the feature tick picks `frobnicate`.
+  for (name, ..) in crates {
+    seed.visit(name).unwrap();
+  }
+
+  let bot = seed
+    .bot(config(
+      &crates_io,
+      &twitter,
+      &[("ON_THIS_DAY", "true".to_owned())],
+    ))
+    .await
+    .unwrap();
+
+  let report = bot.tick().await.unwrap();
+
+  assert_eq!(report.published.len(), 2);
+  assert_eq!(report.published[1].kind, "on-this-day");
+  assert_eq!(
+    report.published[1].text,
+    format!(
+      "On this day in Rust history:\n\
+       {}: serde was first published, 5,000,000 downloads since\n\
+       {}: rand was first published, 3,000,000 downloads since",
+      today.year() - 8,
+      today.year() - 4,
+    )
+  );
+}
@@ -0,0 +1,30 @@
+use crate::common::*;
+
+#[tokio::test]
+async fn tick_posts_overridden_description_link_and_hashtags() {
+  let crates_io = crates_io().await;
+  let twitter = twitter().await;
+
+  let dir = tempfile::tempdir().unwrap();
+
+  let seed = seed(dir.path());
+
+  seed
+    .set_override(
+      "frobnicate",
+      Some("Widgets, frobnicated"),
+      Some(&["rust", "widgets"]),
+      Some("https://example.com/frobnicate-1.0"),
+    )
+    .unwrap();
+
+  let bot = seed.bot(config(&crates_io, &twitter, &[])).await.unwrap();
+
+  let report = bot.tick().await.unwrap();
+
+  assert_eq!(
+    report.published[0].text,
+    "(alice) / frobnicate: Widgets, frobnicated\n\
+     https://example.com/frobnicate-1.0\n#rust #widgets",
+  );
+}
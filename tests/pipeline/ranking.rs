@@ -0,0 +1,32 @@
+use crate::common::*;
+
+#[tokio::test]
+async fn tick_features_queued_crate_first() {
+  let crates_io = crates_io().await;
+  let twitter = twitter().await;
+
+  let dir = tempfile::tempdir().unwrap();
+
+  let seed = seed(dir.path());
+
+  // Unvisited crates the registry doesn't serve, so selecting any of them
+  // instead of the queued crate fails the tick.
+  seed
+    .sync((0..50).map(|i| krate(&format!("unserved-{i}"), "")))
+    .unwrap();
+
+  seed.enqueue("frobnicate").unwrap();
+
+  let bot = seed.bot(config(&crates_io, &twitter, &[])).await.unwrap();
+
+  let report = bot.tick().await.unwrap();
+
+  assert_eq!(report.published.len(), 1);
+  assert_eq!(report.published[0].name, "frobnicate");
+
+  let db = sqlite::open(dir.path().join("db.sqlite")).unwrap();
+
+  let mut statement = db.prepare("SELECT name FROM queue").unwrap();
+
+  assert_eq!(statement.next().unwrap(), sqlite::State::Done);
+}
@@ -0,0 +1,40 @@
+use {crate::common::*, std::fs};
+
+#[tokio::test]
+async fn tick_features_crate_passing_script() {
+  let crates_io = crates_io().await;
+  let twitter = twitter().await;
+
+  let dir = tempfile::tempdir().unwrap();
+
+  let script = dir.path().join("curate.rhai");
+
+  fs::write(
+    &script,
+    r#"
+      fn filter(krate) {
+        if krate.downloads < 1000 {
+          return "too few downloads";
+        }
+        krate.name == "frobnicate" && krate.owners == ["alice"]
+      }
+
+      fn score(krate) {
+        krate.downloads / 2
+      }
+    "#,
+  )
+  .unwrap();
+
+  let bot = bot(
+    dir.path(),
+    &crates_io,
+    &twitter,
+    &[("SCRIPT", script.display().to_string())],
+  )
+  .await;
+
+  let report = bot.tick().await.unwrap();
+
+  assert_eq!(report.published[0].name, "frobnicate");
+}
@@ -0,0 +1,99 @@
+use {
+  crate::common::*,
+  serde_json::{json, Value},
+  wiremock::{
+    matchers::{method, path, query_param},
+    Mock, MockServer, ResponseTemplate,
+  },
+};
+
+#[tokio::test]
+async fn tick_spotlights_prolific_owner() {
+  let registry = MockServer::start().await;
+
+  Mock::given(method("GET"))
+    .and(path("/api/v1/crates"))
+    .and(query_param("page", "1"))
+    .respond_with(page(&[]))
+    .mount(&registry)
+    .await;
+
+  let names = ["alpha", "beta", "gamma", "delta", "epsilon"];
+
+  Mock::given(method("GET"))
+    .and(path("/api/v1/crates"))
+    .and(query_param("user_id", "7"))
+    .respond_with(page(
+      &names
+        .iter()
+        .zip(1..)
+        .map(|(name, i)| {
+          let mut krate = krate(name, "Widgets");
+          krate["downloads"] = json!(i * 1000);
+          krate
+        })
+        .collect::<Vec<Value>>(),
+    ))
+    .with_priority(1)
+    .mount(&registry)
+    .await;
+
+  let twitter = MockServer::start().await;
+
+  Mock::given(method("POST"))
+    .and(path("/1.1/statuses/update.json"))
+    .respond_with(
+      ResponseTemplate::new(200).set_body_json(json!({ "id": 1001 })),
+    )
+    .expect(4)
+    .mount(&twitter)
+    .await;
+
+  let dir = tempfile::tempdir().unwrap();
+
+  let seed = seed(dir.path());
+
+  let alice = json!({
+    "avatar": null,
+    "email": null,
+    "id": 7,
+    "kind": "user",
+    "login": "alice",
+    "name": "Alice",
+    "url": "https://github.com/alice",
+  });
+
+  for name in names {
+    seed.owner(&alice, name).unwrap();
+  }
+
+  let bot = seed
+    .bot(config(
+      &registry,
+      &twitter,
+      &[("MODE", "spotlight".to_owned())],
+    ))
+    .await
+    .unwrap();
+
+  let report = bot.tick().await.unwrap();
+
+  assert_eq!(report.published[0].kind, "spotlight");
+  assert_eq!(report.published[0].name, "alice");
+  assert_eq!(report.published[0].replies.len(), 3);
+
+  assert_eq!(
+    status(&twitter.received_requests().await.unwrap()[1]),
+    format!("epsilon: Widgets\n{}/crates/epsilon", registry.uri()),
+  );
+
+  let db = sqlite::open(dir.path().join("db.sqlite")).unwrap();
+
+  let mut statement = db
+    .prepare("SELECT login, tweet_id FROM spotlights")
+    .unwrap();
+
+  assert_eq!(statement.next().unwrap(), sqlite::State::Row);
+  assert_eq!(statement.read::<String>(0).unwrap(), "alice");
+  assert_eq!(statement.read::<i64>(1).unwrap(), 1001);
+}
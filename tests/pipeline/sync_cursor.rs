@@ -0,0 +1,84 @@
+use {
+  crate::common::*,
+  serde_json::{json, Value},
+  wiremock::{
+    matchers::{method, path, query_param},
+    Mock, MockServer, ResponseTemplate,
+  },
+};
+
+fn seek_page(crates: &[Value], next: Option<&str>) -> ResponseTemplate {
+  ResponseTemplate::new(200).set_body_json(json!({
+    "crates": crates,
+    "meta": {
+      "next_page": next.map(|seek| format!("?seek={seek}&per_page=100")),
+      "total": 2,
+    },
+  }))
+}
+
+#[tokio::test]
+async fn sync_resumes_from_stored_seek_cursor() {
+  let registry = MockServer::start().await;
+
+  Mock::given(method("GET"))
+    .and(path("/api/v1/crates"))
+    .and(query_param("page", "1"))
+    .respond_with(page(&[]))
+    .expect(0)
+    .mount(&registry)
+    .await;
+
+  for (seek, crates, next) in [
+    (
+      "2",
+      vec![krate("frobnicate", "Frobnicates widgets")],
+      Some("3"),
+    ),
+    ("3", vec![krate("widgetry", "Widgets")], Some("4")),
+    ("4", Vec::new(), None),
+  ] {
+    Mock::given(method("GET"))
+      .and(path("/api/v1/crates"))
+      .and(query_param("seek", seek))
+      .and(query_param("sort", "alpha"))
+      .respond_with(seek_page(&crates, next))
+      .expect(1)
+      .mount(&registry)
+      .await;
+  }
+
+  for name in ["frobnicate", "widgetry"] {
+    Mock::given(method("GET"))
+      .and(path(format!("/api/v1/crates/{name}")))
+      .respond_with(crate_response(name, "Widgets"))
+      .mount(&registry)
+      .await;
+  }
+
+  mount_advisory_db(&registry).await;
+
+  let twitter = twitter().await;
+
+  let dir = tempfile::tempdir().unwrap();
+
+  let seed = seed(dir.path());
+
+  seed.set_sync_cursor(2, Some("2")).unwrap();
+
+  let bot = seed.bot(config(&registry, &twitter, &[])).await.unwrap();
+
+  let report = bot.tick().await.unwrap();
+
+  assert_eq!(report.synced[0].page, 2);
+  assert_eq!(report.synced[0].crates, 2);
+
+  let db = sqlite::open(dir.path().join("db.sqlite")).unwrap();
+
+  let mut statement = db.prepare("SELECT page, seek FROM sync_state").unwrap();
+
+  assert_eq!(statement.next().unwrap(), sqlite::State::Row);
+  assert_eq!(statement.read::<i64>(0).unwrap(), 3);
+  assert_eq!(statement.read::<String>(1).unwrap(), "3");
+  assert_eq!(statement.next().unwrap(), sqlite::State::Done);
+}
@@ -0,0 +1,80 @@
+use crate::common::*;
+
+#[tokio::test]
+async fn tick_notes_popular_crate_with_similar_name() {
+  let crates_io = crates_io().await;
+  let twitter = twitter().await;
+
+  let dir = tempfile::tempdir().unwrap();
+
+  let seed = seed(dir.path());
+
+  // Enough popular crates that `frobnicate` isn't one of them. It's queued
+  // so none of them are selected instead, and the sync starts over from the
+  // first page the registry serves.
+  seed
+    .sync(
+      (1..1000)
+        .map(|i| {
+          listed(&format!("popular-{i}"), 2_000_000, "2015-05-15T00:00:00Z")
+        })
+        .chain([listed("Frobnicat", 1_000_000, "2015-05-15T00:00:00Z")]),
+    )
+    .unwrap();
+
+  seed.enqueue("frobnicate").unwrap();
+
+  seed.set_sync_cursor(1, None).unwrap();
+
+  seed
+    .approve(
+      "frobnicate",
+      "name is one edit away from popular crate Frobnicat",
+    )
+    .unwrap();
+
+  let bot = seed
+    .bot(config(
+      &crates_io,
+      &twitter,
+      &[("TYPOSQUAT_NOTE", "true".to_owned())],
+    ))
+    .await
+    .unwrap();
+
+  let report = bot.tick().await.unwrap();
+
+  assert_eq!(
+    report.published[0].text,
+    format!(
+      "(alice) / frobnicate: Frobnicates widgets\n{}/crates/frobnicate\nNot \
+       to be confused with Frobnicat",
+      crates_io.uri()
+    ),
+  );
+}
+
+#[tokio::test]
+async fn tick_features_popular_crate_next_to_similar_name() {
+  let crates_io = crates_io().await;
+  let twitter = twitter().await;
+
+  let dir = tempfile::tempdir().unwrap();
+
+  let seed = seed(dir.path());
+
+  seed
+    .sync([
+      listed("frobnicate", 1_000_000, "2015-05-15T00:00:00Z"),
+      listed("Frobnicat", 1_000_000, "2015-05-15T00:00:00Z"),
+    ])
+    .unwrap();
+
+  seed.visit("Frobnicat").unwrap();
+
+  let bot = seed.bot(config(&crates_io, &twitter, &[])).await.unwrap();
+
+  let report = bot.tick().await.unwrap();
+
+  assert_eq!(report.published[0].name, "frobnicate");
+}
@@ -0,0 +1,129 @@
+use {
+  crate::common::*,
+  chrono::{Duration, Utc},
+  serde_json::{json, Value},
+  wiremock::{
+    matchers::{method, path, query_param},
+    Mock, MockServer, ResponseTemplate,
+  },
+};
+
+fn category(slug: &str, crates: u64) -> Value {
+  json!({
+    "category": slug,
+    "crates_cnt": crates,
+    "created_at": "2022-08-01T00:00:00Z",
+    "description": "",
+    "id": slug,
+    "slug": slug,
+  })
+}
+
+#[tokio::test]
+async fn tick_posts_weekly_stats_from_snapshots() {
+  let crates_io = crates_io().await;
+
+  Mock::given(method("GET"))
+    .and(path("/api/v1/summary"))
+    .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+      "num_crates": 142,
+      "num_downloads": 1_000_000,
+    })))
+    .expect(1)
+    .mount(&crates_io)
+    .await;
+
+  Mock::given(method("GET"))
+    .and(path("/api/v1/categories"))
+    .and(query_param("page", "1"))
+    .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+      "categories": [category("gui", 13), category("web", 30)],
+    })))
+    .mount(&crates_io)
+    .await;
+
+  Mock::given(method("GET"))
+    .and(path("/api/v1/categories"))
+    .and(query_param("page", "2"))
+    .respond_with(
+      ResponseTemplate::new(200).set_body_json(json!({ "categories": [] })),
+    )
+    .mount(&crates_io)
+    .await;
+
+  let updated = |name: &str, date: String| {
+    let mut krate = krate(name, "Updated recently");
+    krate["updated_at"] = json!(date);
+    krate
+  };
+
+  Mock::given(method("GET"))
+    .and(path("/api/v1/crates"))
+    .and(query_param("sort", "recent-updates"))
+    .respond_with(page(&[
+      updated("alpha", Utc::now().to_rfc3339()),
+      updated("beta", Utc::now().to_rfc3339()),
+      updated("gamma", "2022-08-01T00:00:00Z".into()),
+    ]))
+    .with_priority(1)
+    .expect(1)
+    .mount(&crates_io)
+    .await;
+
+  let twitter = MockServer::start().await;
+
+  Mock::given(method("POST"))
+    .and(path("/1.1/statuses/update.json"))
+    .respond_with(
+      ResponseTemplate::new(200).set_body_json(json!({ "id": 1001 })),
+    )
+    .expect(2)
+    .mount(&twitter)
+    .await;
+
+  let dir = tempfile::tempdir().unwrap();
+
+  let seed = seed(dir.path());
+
+  seed
+    .snapshot(
+      Utc::now() - Duration::days(7),
+      100,
+      900_000,
+      0,
+      &[("gui", 10), ("web", 20)],
+    )
+    .unwrap();
+
+  let bot = seed
+    .bot(config(
+      &crates_io,
+      &twitter,
+      &[("WEEKLY_STATS", "true".to_owned())],
+    ))
+    .await
+    .unwrap();
+
+  let report = bot.tick().await.unwrap();
+
+  assert_eq!(report.published.len(), 2);
+  assert_eq!(report.published[1].kind, "weekly");
+  assert_eq!(
+    report.published[1].text,
+    "This week in new crates: 42 new crates and 2 crate updates published. \
+     Fastest-growing category: web (+10 crates)."
+  );
+
+  let db = sqlite::open(dir.path().join("db.sqlite")).unwrap();
+
+  let mut statement = db
+    .prepare("SELECT crates, versions FROM snapshots ORDER BY date")
+    .unwrap();
+
+  assert_eq!(statement.next().unwrap(), sqlite::State::Row);
+  assert_eq!(statement.read::<i64>(0).unwrap(), 100);
+  assert_eq!(statement.next().unwrap(), sqlite::State::Row);
+  assert_eq!(statement.read::<i64>(0).unwrap(), 142);
+  assert_eq!(statement.read::<i64>(1).unwrap(), 2);
+  assert_eq!(statement.next().unwrap(), sqlite::State::Done);
+}
@@ -0,0 +1,56 @@
+use {
+  crate::common::*,
+  serde_json::json,
+  wiremock::{
+    matchers::{method, path, query_param},
+    Mock, MockServer, ResponseTemplate,
+  },
+};
+
+#[tokio::test]
+async fn tick_features_crate_whose_latest_version_is_yanked() {
+  let registry = MockServer::start().await;
+
+  Mock::given(method("GET"))
+    .and(path("/api/v1/crates"))
+    .and(query_param("page", "1"))
+    .respond_with(page(&[krate("frobnicate", "Frobnicates widgets")]))
+    .mount(&registry)
+    .await;
+
+  Mock::given(method("GET"))
+    .and(path("/api/v1/crates"))
+    .and(query_param("page", "2"))
+    .respond_with(page(&[]))
+    .mount(&registry)
+    .await;
+
+  let mut body = crate_json("frobnicate", "Frobnicates widgets");
+
+  let mut older = body["versions"][0].clone();
+  older["id"] = json!(0);
+  older["num"] = json!("1.2.2");
+
+  body["versions"][0]["yanked"] = json!(true);
+  body["versions"].as_array_mut().unwrap().push(older);
+
+  Mock::given(method("GET"))
+    .and(path("/api/v1/crates/frobnicate"))
+    .respond_with(ResponseTemplate::new(200).set_body_json(body))
+    .expect(1)
+    .mount(&registry)
+    .await;
+
+  mount_advisory_db(&registry).await;
+
+  let twitter = twitter().await;
+
+  let dir = tempfile::tempdir().unwrap();
+
+  let bot = bot(dir.path(), &registry, &twitter, &[]).await;
+
+  let report = bot.tick().await.unwrap();
+
+  assert_eq!(report.published.len(), 1);
+  assert_eq!(report.published[0].name, "frobnicate");
+}